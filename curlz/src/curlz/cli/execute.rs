@@ -25,9 +25,11 @@ pub fn execute() -> crate::Result<()> {
 
     match args.command {
         SubCommands::Request(ref r) => r.execute(),
-        SubCommands::Bookmark(_b) => {
-            todo!()
-        }
+        SubCommands::Bookmark(ref b) => b.execute(),
+        SubCommands::Import(ref i) => i.execute(),
+        SubCommands::Export(ref e) => e.execute(),
+        SubCommands::Codegen(ref c) => c.execute(),
+        SubCommands::Templates(ref t) => t.execute(),
         #[cfg(feature = "x-http-lang")]
         SubCommands::HttpFile(ref hf) => hf.execute(),
     }
@@ -40,7 +40,7 @@ mod tests {
 
     #[test]
     fn should_parse_the_header_arg_flag_away() {
-        let mut args = vec![
+        let mut args = [
             "-H",
             "foo: bar",
             "--header",
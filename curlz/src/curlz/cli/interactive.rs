@@ -1,6 +1,7 @@
 use crate::Result;
 
-use dialoguer::Input;
+use dialoguer::{Input, Select};
+use std::io::IsTerminal;
 
 pub fn user_question(prompt: &str, default: &Option<String>) -> Result<String> {
     let mut i = Input::<String>::new();
@@ -10,3 +11,18 @@ pub fn user_question(prompt: &str, default: &Option<String>) -> Result<String> {
     }
     i.interact().map_err(Into::<anyhow::Error>::into)
 }
+
+/// lets the user pick one of the `items`, it returns its index
+pub fn user_choice(prompt: &str, items: &[String]) -> Result<usize> {
+    Select::new()
+        .with_prompt(prompt)
+        .items(items)
+        .default(0)
+        .interact()
+        .map_err(Into::<anyhow::Error>::into)
+}
+
+/// prompts are only asked when stdin is a terminal and `--non-interactive` was not given
+pub fn is_interactive(non_interactive: bool) -> bool {
+    !non_interactive && std::io::stdin().is_terminal()
+}
@@ -1,4 +1,14 @@
+use crate::cli::interactive::{is_interactive, user_choice};
+use crate::domain::bookmark::{
+    load_bookmark, Bookmark, BookmarkCollection, ChainedBookmarkCollection, LoadBookmark,
+    LoadedBookmark,
+};
+use crate::domain::http::HttpMethod;
+
+use anyhow::bail;
 use clap::{Args, Subcommand};
+use log::info;
+use std::str::FromStr;
 
 #[derive(Clone, Debug, Args)]
 #[command(arg_required_else_help = true)]
@@ -10,19 +20,165 @@ pub struct BookmarkCli {
 
 #[derive(Clone, Debug, Subcommand)]
 pub enum BookmarkCommands {
-    List,
-    Rename {
+    /// lists the bookmarks of the project and the global ones, with their origin
+    List {
+        /// only the bookmarks in a folder, like `users/`
         #[clap(value_parser)]
-        name: String,
+        folder: Option<String>,
+        /// only the bookmarks with a tag, like `--tag payments`
+        #[clap(long = "tag", number_of_values = 1, value_parser)]
+        tags: Vec<String>,
+    },
+    /// renames a bookmark, in the project or the global bookmarks it comes from
+    Rename {
+        #[clap(flatten)]
+        bookmark: BookmarkArgs,
+        /// the new slug of the bookmark
         #[clap(value_parser)]
         new_name: String,
     },
+    /// removes a bookmark
     Remove {
-        #[clap(value_parser)]
-        name: String,
+        #[clap(flatten)]
+        bookmark: BookmarkArgs,
     },
+    /// prints a bookmark as yaml
     Show {
-        #[clap(value_parser)]
-        name: String,
+        #[clap(flatten)]
+        bookmark: BookmarkArgs,
     },
 }
+
+/// the arguments that pick a bookmark
+#[derive(Clone, Debug, Args)]
+pub struct BookmarkArgs {
+    /// the slug of the bookmark
+    #[clap(value_parser)]
+    pub name: String,
+
+    /// the http method of the bookmark, needed only when the slug is bookmarked for several methods
+    #[clap(short = 'X', long = "request", value_parser)]
+    pub http_method: Option<String>,
+
+    /// Never prompt for the method of a slug that is bookmarked for several methods
+    #[clap(long = "non-interactive", action)]
+    pub non_interactive: bool,
+}
+
+impl BookmarkArgs {
+    fn find(&self, collection: &ChainedBookmarkCollection) -> crate::Result<Bookmark> {
+        let method = self
+            .http_method
+            .as_deref()
+            .map(HttpMethod::from_str)
+            .transpose()?;
+
+        find_bookmark(&self.name, method, collection, self.non_interactive)
+    }
+}
+
+impl BookmarkCli {
+    pub fn execute(&self) -> crate::Result<()> {
+        match &self.command {
+            BookmarkCommands::List { folder, tags } => list_bookmarks(folder.as_deref(), tags),
+            BookmarkCommands::Rename { bookmark, new_name } => {
+                let collection = ChainedBookmarkCollection::new()?;
+                let found = bookmark.find(&collection)?;
+                collection.rename(&found, new_name)?;
+                info!("Bookmark `{}` renamed to: {}", found.slug, new_name);
+                Ok(())
+            }
+            BookmarkCommands::Remove { bookmark } => {
+                let collection = ChainedBookmarkCollection::new()?;
+                let found = bookmark.find(&collection)?;
+                collection.remove(&found.slug, &found.request.method)?;
+                info!("Bookmark removed: {}", found.slug);
+                Ok(())
+            }
+            BookmarkCommands::Show { bookmark } => {
+                let found = bookmark.find(&ChainedBookmarkCollection::new()?)?;
+                print!("{}", serde_yaml::to_string(&found)?);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn list_bookmarks(folder: Option<&str>, tags: &[String]) -> crate::Result<()> {
+    let bookmarks = ChainedBookmarkCollection::new()?
+        .list_with_origin()?
+        .into_iter()
+        .filter(|(_, b)| folder.is_none_or(|folder| b.is_in(folder)) && b.has_tags(tags))
+        .collect::<Vec<_>>();
+    if bookmarks.is_empty() {
+        info!("No bookmarks found");
+    }
+    for (origin, bookmark) in bookmarks {
+        let mut line = format!(
+            "{:<7} {:<7} {}",
+            String::from(&bookmark.request.method),
+            origin,
+            bookmark.slug
+        );
+        if !bookmark.tags.is_empty() {
+            line.push_str(&format!(" [{}]", bookmark.tags.join(", ")));
+        }
+        if let Some(description) = bookmark.description.as_ref() {
+            line.push_str(&format!(" - {}", description));
+        }
+        println!("{}", line);
+    }
+
+    Ok(())
+}
+
+/// finds the bookmark of a `slug`, of the `method` or of the one it was saved with,
+/// when the slug is bookmarked for several methods the user picks one
+pub(crate) fn find_bookmark(
+    slug: &str,
+    method: Option<HttpMethod>,
+    collection: &impl BookmarkCollection,
+    non_interactive: bool,
+) -> crate::Result<Bookmark> {
+    match load_bookmark(LoadBookmark::new(slug, method), collection)? {
+        LoadedBookmark::Found(bookmark) => Ok(*bookmark),
+        LoadedBookmark::Ambiguous(mut bookmarks) => {
+            let methods = bookmarks
+                .iter()
+                .map(|b| String::from(&b.request.method))
+                .collect::<Vec<_>>();
+            if !is_interactive(non_interactive) {
+                bail!(
+                    "the bookmark `{}` exists for several methods, pick one with `-X {}`",
+                    slug,
+                    methods.join("|")
+                );
+            }
+            let index = user_choice(
+                &format!("The bookmark `{}` exists for several methods", slug),
+                &methods,
+            )?;
+
+            Ok(bookmarks.remove(index))
+        }
+        LoadedBookmark::NotFound { methods, .. } if !methods.is_empty() => {
+            let methods = methods.iter().map(String::from).collect::<Vec<_>>();
+            bail!(
+                "the bookmark `{}` does not exist for this method, but for `-X {}`",
+                slug,
+                methods.join("|")
+            )
+        }
+        LoadedBookmark::NotFound { suggestions, .. } if !suggestions.is_empty() => {
+            bail!(
+                "the bookmark `{}` does not exist, did you mean `{}`?",
+                slug,
+                suggestions.join("` or `")
+            )
+        }
+        LoadedBookmark::NotFound { .. } => bail!(
+            "the bookmark `{}` does not exist, see `curlz bookmark list`",
+            slug
+        ),
+    }
+}
@@ -0,0 +1,133 @@
+use crate::cli::interactive::is_interactive;
+use crate::domain::bookmark::ChainedBookmarkCollection;
+use crate::domain::codegen::{
+    codegen, CodeGenerator, GoNethttp, Httpie, JsFetch, PythonRequests, RustReqwest, Wget,
+};
+use crate::domain::environment::create_environment;
+use crate::domain::http::{HttpAuth, HttpBody, HttpHeaders, HttpMethod, HttpRequest};
+use crate::domain::request::RequestContext;
+use crate::template::session::RenderSession;
+
+use super::bookmark::find_bookmark;
+
+use anyhow::Context;
+use clap::{Args, ValueEnum};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, Args)]
+pub struct CodegenCli {
+    /// the slug of the bookmark
+    #[clap(value_parser)]
+    pub bookmark: String,
+
+    /// the http method of the bookmark, needed only when the slug is bookmarked for several methods
+    #[clap(short = 'X', long = "request", value_parser)]
+    pub http_method: Option<String>,
+
+    /// the http client the code is generated for
+    #[clap(long, value_enum)]
+    pub lang: Lang,
+
+    /// Render all templates with the environment, instead of turning placeholders into variables of the code
+    #[clap(long, action)]
+    pub render: bool,
+
+    /// Provide an `.env` or a yaml containing template variables, it is used by `--render`
+    #[clap(long = "env-file", value_parser, default_value = ".env")]
+    pub env_file: PathBuf,
+
+    /// Never prompt, prompts are answered by the env file or `CURLZ_<LABEL>` env vars
+    #[clap(long = "non-interactive", action)]
+    pub non_interactive: bool,
+}
+
+/// the http clients code can be generated for
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Lang {
+    RustReqwest,
+    PythonRequests,
+    JsFetch,
+    GoNethttp,
+    Httpie,
+    Wget,
+}
+
+impl Lang {
+    fn generator(&self) -> &'static dyn CodeGenerator {
+        match self {
+            Lang::RustReqwest => &RustReqwest,
+            Lang::PythonRequests => &PythonRequests,
+            Lang::JsFetch => &JsFetch,
+            Lang::GoNethttp => &GoNethttp,
+            Lang::Httpie => &Httpie,
+            Lang::Wget => &Wget,
+        }
+    }
+}
+
+impl CodegenCli {
+    pub fn execute(&self) -> crate::Result<()> {
+        let method = self
+            .http_method
+            .as_deref()
+            .map(HttpMethod::from_str)
+            .transpose()?;
+        let bookmark = find_bookmark(
+            &self.bookmark,
+            method,
+            &ChainedBookmarkCollection::new()?,
+            self.non_interactive,
+        )?;
+
+        let mut request = bookmark.request;
+        if self.render {
+            let env = create_environment(&self.env_file, &request.placeholders)?;
+            let session = RenderSession::for_project(is_interactive(self.non_interactive))?;
+            let context = RequestContext::new(&env).with_session(session);
+            request = render_request(&context, &request)
+                .with_context(|| format!("cannot render the bookmark `{}`", bookmark.slug))?;
+            context.ensure_answered()?;
+        }
+        print!("{}", codegen(&request, self.lang.generator())?);
+
+        Ok(())
+    }
+}
+
+/// renders the templates of the url, headers, body, curl params and digest credentials of a `request`
+fn render_request(context: &RequestContext, request: &HttpRequest) -> crate::Result<HttpRequest> {
+    let renderer = context.renderer_with_placeholders(&request.placeholders)?;
+    let mut headers = HttpHeaders::default();
+    for (name, value) in request.headers.as_ref() {
+        headers.push(name, renderer.render(value, "header")?);
+    }
+    let body = match &request.body {
+        HttpBody::InlineText(text) => HttpBody::InlineText(renderer.render(text, "body")?),
+        other => other.clone(),
+    };
+    let curl_params = request
+        .curl_params
+        .iter()
+        .map(|param| renderer.render(param, "param"))
+        .collect::<crate::Result<Vec<_>>>()?;
+    let auth = match request.auth.as_ref() {
+        Some(HttpAuth::Digest { user, password }) => Some(HttpAuth::Digest {
+            user: renderer.render(user, "user")?,
+            password: renderer.render(password, "password")?,
+        }),
+        None => None,
+    };
+
+    Ok(HttpRequest {
+        url: renderer
+            .render(request.url.as_ref(), "url")?
+            .as_str()
+            .into(),
+        headers,
+        body,
+        curl_params,
+        auth,
+        ..request.clone()
+    })
+}
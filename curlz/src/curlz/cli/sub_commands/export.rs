@@ -0,0 +1,62 @@
+use crate::domain::bookmark::{BookmarkCollection, ChainedBookmarkCollection};
+use crate::domain::import::export_postman;
+
+use anyhow::Context;
+use clap::{Args, Subcommand};
+use log::info;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, Args)]
+#[command(arg_required_else_help = true)]
+pub struct ExportCli {
+    #[clap(subcommand)]
+    pub command: ExportCommands,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum ExportCommands {
+    /// exports all bookmarks as postman collection v2.1
+    Postman(ExportPostmanCli),
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct ExportPostmanCli {
+    /// the name of the collection
+    #[clap(long, value_parser, default_value = "curlz")]
+    pub name: String,
+
+    /// the file the collection is written to, it is printed to stdout if omitted
+    #[clap(short, long, value_parser)]
+    pub output: Option<PathBuf>,
+}
+
+impl ExportCli {
+    pub fn execute(&self) -> crate::Result<()> {
+        match &self.command {
+            ExportCommands::Postman(postman) => postman.execute(),
+        }
+    }
+}
+
+impl ExportPostmanCli {
+    pub fn execute(&self) -> crate::Result<()> {
+        let bookmarks = ChainedBookmarkCollection::new()?.list()?;
+        let collection = export_postman(&self.name, &bookmarks)?;
+
+        write_output(self.output.as_deref(), collection, bookmarks.len())
+    }
+}
+
+/// writes the exported bookmarks to the `output` file, or prints them if omitted
+fn write_output(output: Option<&Path>, exported: String, count: usize) -> crate::Result<()> {
+    match output {
+        Some(output) => {
+            std::fs::write(output, exported)
+                .with_context(|| format!("cannot write `{}`", output.display()))?;
+            info!("{} bookmarks exported to: {}", count, output.display());
+        }
+        None => println!("{}", exported),
+    }
+
+    Ok(())
+}
@@ -1,11 +1,13 @@
+use crate::cli::interactive::is_interactive;
 use crate::domain::environment::create_environment;
 use crate::domain::http_lang::parse_request_file;
 use crate::domain::request::Verbosity::Verbose;
-use crate::domain::request::{issue_request_with_curl, IssueRequest};
+use crate::domain::request::{issue_request_with_curl, IssueRequest, RequestContext};
+use crate::template::session::RenderSession;
 use crate::template::variables::Placeholder;
 use crate::utils::parse_pairs;
 use clap::Parser;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 #[derive(Clone, Debug, Parser)]
 #[clap(author, version, about, long_about = None)]
@@ -19,6 +21,23 @@ pub struct HttpFileCli {
     #[clap(long, number_of_values = 1, value_parser)]
     pub define: Vec<String>,
 
+    /// Seed the random values of template functions like `uuid()` or `fake("email")` for reproducible runs
+    #[clap(long, value_parser)]
+    pub seed: Option<u64>,
+
+    /// Allow template functions like `file()` to read files outside of the current project
+    #[clap(long = "allow-outside-project", action)]
+    pub allow_outside_project: bool,
+
+    /// Never prompt, prompts are answered by `--define`, the env file or `CURLZ_<LABEL>` env vars,
+    /// this is the default when stdin is not a terminal
+    #[clap(long = "non-interactive", action)]
+    pub non_interactive: bool,
+
+    /// Render undefined template variables empty, instead of failing before the request is sent
+    #[clap(long, action)]
+    pub lenient: bool,
+
     /// Provide an http request file
     #[clap(value_parser)]
     pub http_file: PathBuf,
@@ -37,11 +56,39 @@ impl HttpFileCli {
         let env = create_environment(&self.env_file, &placeholders)?;
         let contents = std::fs::read_to_string(&self.http_file)?;
         let bookmarks = parse_request_file(contents)?;
+        let ctx = RequestContext::new(&env).with_session(self.render_session()?);
 
         for b in bookmarks {
-            issue_request_with_curl(IssueRequest::new(&b.request, Verbose), &env)?;
+            let origin = format!(
+                "request `{}` of `{}`",
+                b.slug.trim_start_matches('#').trim(),
+                self.http_file.display()
+            );
+            issue_request_with_curl(
+                IssueRequest::new(&b.request, Verbose).with_origin(origin),
+                &ctx,
+            )?;
         }
 
         Ok(())
     }
+
+    /// the template functions resolve files relative to the http file, but restricted to the project
+    fn render_session(&self) -> crate::Result<RenderSession> {
+        let base_dir = self
+            .http_file
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let session = RenderSession::for_project(is_interactive(self.non_interactive))?
+            .with_seed(self.seed)
+            .with_base_dir(base_dir)
+            .with_strict(!self.lenient);
+
+        Ok(if self.allow_outside_project {
+            session.with_sandbox(None)
+        } else {
+            session
+        })
+    }
 }
@@ -0,0 +1,353 @@
+use crate::cli::interactive;
+use crate::cli::interactive::is_interactive;
+use crate::domain::bookmark::{save_bookmark, BookmarkFolderCollection, SaveBookmark};
+use crate::domain::environment::merge_into_env_file;
+use crate::domain::http::HttpRequest;
+use crate::domain::import::{
+    detect_replacements, import_curl, BrunoFormat, HarFormat, HurlFormat, ImportFormat,
+    ImportedCollection, InsomniaFormat, OpenApiFormat, PostmanFormat,
+};
+
+use anyhow::bail;
+use clap::{Args, Subcommand};
+use dialoguer::Confirm;
+use log::info;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, Args)]
+#[command(arg_required_else_help = true)]
+pub struct ImportCli {
+    #[clap(subcommand)]
+    pub command: ImportCommands,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum ImportCommands {
+    /// imports a curl command line, like "Copy as cURL" of a browser, as bookmark
+    Curl(ImportCurlCli),
+    /// imports a postman collection v2.1 as bookmarks, its variables go into a yaml environment file
+    Postman(ImportPostmanCli),
+    /// generates a bookmark per operation of an OpenAPI 3 spec, the server url goes into a yaml environment file
+    Openapi(ImportOpenapiCli),
+    /// imports the requests of a HAR file, like of browser devtools, as bookmarks
+    Har(ImportHarCli),
+    /// imports the requests of an Insomnia export, its environment goes into a yaml environment file
+    Insomnia(ImportInsomniaCli),
+    /// imports the `.bru` requests of a Bruno collection folder, an environment goes into a yaml environment file
+    Bruno(ImportBrunoCli),
+    /// imports the requests of a Hurl file, its responses and asserts are left out
+    Hurl(ImportHurlCli),
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct ImportCurlCli {
+    /// the curl command, it is read from stdin if omitted
+    #[clap(value_parser)]
+    pub command: Option<String>,
+
+    #[clap(long = "bookmark-as", value_parser)]
+    pub save_bookmark_as: Option<String>,
+
+    /// Save the bookmark to the global bookmarks of the user, like `~/.config/curlz/bookmarks`, instead of the project
+    #[clap(long, action)]
+    pub global: bool,
+
+    /// Replace all detected values, like the host, ids and bearer tokens, by placeholders without asking
+    #[clap(long, action)]
+    pub placeholders: bool,
+
+    /// Never prompt, detected values are kept unless `--placeholders` is given,
+    /// this is the default when stdin is not a terminal
+    #[clap(long = "non-interactive", action)]
+    pub non_interactive: bool,
+}
+
+/// the arguments all imports of files share
+#[derive(Clone, Debug, Args)]
+pub struct ImportArgs {
+    /// the `.env` or yaml file the variables are merged into, the same file `curlz r` reads by default
+    #[clap(long = "env-file", value_parser, default_value = ".env")]
+    pub env_file: PathBuf,
+
+    /// Print which bookmarks and variables would be written, without writing them
+    #[clap(long = "dry-run", action)]
+    pub dry_run: bool,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct ImportPostmanCli {
+    /// the exported postman collection, like `collection.json`
+    #[clap(value_parser)]
+    pub collection: PathBuf,
+
+    /// an exported postman environment, its values override the collection variables
+    #[clap(long = "env", value_parser)]
+    pub environment: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub args: ImportArgs,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct ImportOpenapiCli {
+    /// the OpenAPI spec, like `openapi.yaml` or `openapi.json`
+    #[clap(value_parser)]
+    pub spec: PathBuf,
+
+    #[clap(flatten)]
+    pub args: ImportArgs,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct ImportHarCli {
+    /// the HAR file, like `session.har`
+    #[clap(value_parser)]
+    pub file: PathBuf,
+
+    /// Import only the requests to this host and its subdomains, like `api.example.com`
+    #[clap(long, value_parser)]
+    pub filter: Option<String>,
+
+    #[clap(flatten)]
+    pub args: ImportArgs,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct ImportInsomniaCli {
+    /// the Insomnia export, like `insomnia.json`
+    #[clap(value_parser)]
+    pub export: PathBuf,
+
+    /// the name of a sub environment, its values override the base environment
+    #[clap(long = "env", value_parser)]
+    pub environment: Option<String>,
+
+    #[clap(flatten)]
+    pub args: ImportArgs,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct ImportBrunoCli {
+    /// the folder of the Bruno collection
+    #[clap(value_parser)]
+    pub folder: PathBuf,
+
+    /// the name of an environment of the `environments` folder, like `local`
+    #[clap(long = "env", value_parser)]
+    pub environment: Option<String>,
+
+    #[clap(flatten)]
+    pub args: ImportArgs,
+}
+
+#[derive(Clone, Debug, Args)]
+pub struct ImportHurlCli {
+    /// the Hurl file, like `users.hurl`
+    #[clap(value_parser)]
+    pub file: PathBuf,
+
+    /// a file of `name=value` variables, like for `hurl --variables-file`
+    #[clap(long = "variables-file", value_parser)]
+    pub variables_file: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub args: ImportArgs,
+}
+
+impl ImportCli {
+    pub fn execute(&self) -> crate::Result<()> {
+        match &self.command {
+            ImportCommands::Curl(curl) => curl.execute(),
+            ImportCommands::Postman(postman) => postman.execute(),
+            ImportCommands::Openapi(openapi) => openapi.execute(),
+            ImportCommands::Har(har) => har.execute(),
+            ImportCommands::Insomnia(insomnia) => insomnia.execute(),
+            ImportCommands::Bruno(bruno) => bruno.execute(),
+            ImportCommands::Hurl(hurl) => hurl.execute(),
+        }
+    }
+}
+
+impl ImportCurlCli {
+    pub fn execute(&self) -> crate::Result<()> {
+        let command = match self.command.as_ref() {
+            Some(command) => command.clone(),
+            None => {
+                let mut command = String::new();
+                std::io::stdin().read_to_string(&mut command)?;
+                command
+            }
+        };
+        let mut request = import_curl(&command)?;
+        self.replace_values(&mut request)?;
+
+        let slug = if let Some(slug) = self.save_bookmark_as.as_ref() {
+            slug.clone()
+        } else if !is_interactive(self.non_interactive) {
+            bail!("a bookmark name cannot be asked in a non-interactive run, use `--bookmark-as <name>` instead")
+        } else {
+            interactive::user_question("Please enter a bookmark name", &None)?
+        };
+        let mut bookmarks = if self.global {
+            BookmarkFolderCollection::global()?
+        } else {
+            BookmarkFolderCollection::new()?
+        };
+        save_bookmark(SaveBookmark::new(slug.as_str(), &request), &mut bookmarks)?;
+        info!("Request bookmarked as: {}", slug);
+
+        Ok(())
+    }
+
+    /// replaces the detected values by placeholders, either all of them or those the user confirms
+    fn replace_values(&self, request: &mut HttpRequest) -> crate::Result<()> {
+        let interactive = is_interactive(self.non_interactive);
+        for replacement in detect_replacements(request) {
+            let replace = if self.placeholders {
+                true
+            } else if interactive {
+                Confirm::new()
+                    .with_prompt(format!(
+                        "Replace `{}` by `{}`?",
+                        shorten(&replacement.value),
+                        replacement.template()
+                    ))
+                    .default(true)
+                    .interact()?
+            } else {
+                false
+            };
+            if replace {
+                replacement.apply(request);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ImportPostmanCli {
+    pub fn execute(&self) -> crate::Result<()> {
+        let format = PostmanFormat {
+            environment: self.environment.clone(),
+        };
+
+        import(&format, &self.collection, &self.args)
+    }
+}
+
+impl ImportOpenapiCli {
+    pub fn execute(&self) -> crate::Result<()> {
+        import(&OpenApiFormat, &self.spec, &self.args)
+    }
+}
+
+impl ImportHarCli {
+    pub fn execute(&self) -> crate::Result<()> {
+        let format = HarFormat {
+            filter: self.filter.clone(),
+        };
+
+        import(&format, &self.file, &self.args)
+    }
+}
+
+impl ImportInsomniaCli {
+    pub fn execute(&self) -> crate::Result<()> {
+        let format = InsomniaFormat {
+            environment: self.environment.clone(),
+        };
+
+        import(&format, &self.export, &self.args)
+    }
+}
+
+impl ImportBrunoCli {
+    pub fn execute(&self) -> crate::Result<()> {
+        let format = BrunoFormat {
+            environment: self.environment.clone(),
+        };
+
+        import(&format, &self.folder, &self.args)
+    }
+}
+
+impl ImportHurlCli {
+    pub fn execute(&self) -> crate::Result<()> {
+        let format = HurlFormat {
+            variables: self.variables_file.clone(),
+        };
+
+        import(&format, &self.file, &self.args)
+    }
+}
+
+/// imports the file or folder at `path` in a `format`, then saves its bookmarks and variables,
+/// or only prints them on a dry run
+fn import(format: &impl ImportFormat, path: &Path, args: &ImportArgs) -> crate::Result<()> {
+    let imported = format.import(path)?;
+    if imported.bookmarks.is_empty() {
+        bail!("`{}` contains no requests to import", path.display());
+    }
+
+    if args.dry_run {
+        preview_imported(&imported, &args.env_file)
+    } else {
+        save_imported(&imported, &args.env_file)
+    }
+}
+
+/// saves the bookmarks of an `imported` collection and merges its variables into the `env_file`
+fn save_imported(imported: &ImportedCollection, env_file: &Path) -> crate::Result<()> {
+    let mut bookmarks = BookmarkFolderCollection::new()?;
+    for bookmark in imported.bookmarks.iter() {
+        save_bookmark(
+            SaveBookmark::new(bookmark.slug.as_str(), &bookmark.request)
+                .with_description(bookmark.description.clone())
+                .with_tags(bookmark.tags.clone()),
+            &mut bookmarks,
+        )?;
+        info!("Request bookmarked as: {}", bookmark.slug);
+    }
+    if !imported.variables.is_empty() {
+        merge_into_env_file(env_file, &imported.variables)?;
+        info!(
+            "{} variables written to: {}",
+            imported.variables.len(),
+            env_file.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// prints the bookmark files that an `imported` collection would create or update,
+/// and the variables it would write to the `env_file`
+fn preview_imported(imported: &ImportedCollection, env_file: &Path) -> crate::Result<()> {
+    let bookmarks = BookmarkFolderCollection::new()?;
+    for bookmark in imported.bookmarks.iter() {
+        let path = bookmarks.bookmark_path(&bookmark.slug, &bookmark.request.method);
+        let action = if path.exists() { "update" } else { "create" };
+        println!(
+            "would {} bookmark `{}` in {}",
+            action,
+            bookmark.slug,
+            path.display()
+        );
+    }
+    for name in imported.variables.keys() {
+        println!("would write variable `{}` to {}", name, env_file.display());
+    }
+
+    Ok(())
+}
+
+/// long values, like tokens, are shortened for prompts
+fn shorten(value: &str) -> String {
+    const MAX: usize = 24;
+    match value.char_indices().nth(MAX) {
+        Some((i, _)) => format!("{}…", &value[..i]),
+        None => value.to_string(),
+    }
+}
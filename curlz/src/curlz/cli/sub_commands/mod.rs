@@ -1,10 +1,18 @@
 use clap::Subcommand;
 
 mod bookmark;
+mod codegen;
+mod export;
+mod import;
 mod request;
+mod templates;
 
 pub use bookmark::*;
+pub use codegen::*;
+pub use export::*;
+pub use import::*;
 pub use request::*;
+pub use templates::*;
 
 #[cfg(feature = "x-http-lang")]
 mod http_file;
@@ -14,10 +22,18 @@ pub use http_file::*;
 #[derive(Clone, Debug, Subcommand)]
 pub enum SubCommands {
     #[command(alias("r"))]
-    Request(RequestCli),
+    Request(Box<RequestCli>),
     #[command(alias("b"))]
     /// similar to git remote, we want to support `list`, `add`, `rename`, `remove` and `show`
     Bookmark(BookmarkCli),
+    /// imports requests from other tools as bookmarks, like a curl command line
+    Import(ImportCli),
+    /// exports the bookmarks for other tools, like as postman collection
+    Export(ExportCli),
+    /// generates the code of a bookmark for other http clients, like `reqwest` or `fetch`
+    Codegen(CodegenCli),
+    /// the template library of `.curlz/templates/`, its macros can be imported by requests
+    Templates(TemplatesCli),
     #[cfg(feature = "x-http-lang")]
     HttpFile(HttpFileCli),
 }
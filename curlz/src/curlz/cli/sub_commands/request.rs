@@ -1,20 +1,24 @@
 use crate::cli::interactive;
-use crate::cli::HeaderArgs;
+use crate::cli::interactive::is_interactive;
+use crate::cli::sub_commands::find_bookmark;
 use crate::domain::bookmark::{
-    load_bookmark, save_bookmark, BookmarkCollection, BookmarkFolderCollection, LoadBookmark,
+    save_bookmark, BookmarkCollection, BookmarkFolderCollection, ChainedBookmarkCollection,
     SaveBookmark,
 };
 use crate::domain::http::{
-    HttpBody, HttpHeaders, HttpMethod, HttpRequest, HttpUri, HttpVersion::Http11,
+    parse_curl_args, CurlArgs, HttpAuth, HttpBody, HttpHeaders, HttpMethod, HttpRequest,
+    HttpVersion::Http11,
 };
+use crate::domain::import::basic_auth;
 use crate::domain::request::Verbosity::{Silent, Verbose};
-use crate::domain::request::{issue_request_with_curl, IssueRequest};
+use crate::domain::request::{issue_request_with_curl, IssueRequest, RequestContext};
+use crate::template::session::RenderSession;
 use crate::template::variables::Placeholder;
 use crate::utils::parse_pairs;
 
 use crate::domain::environment::create_environment;
 use anyhow::{bail, Context};
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use clap_verbosity_flag::{InfoLevel, Verbosity};
 use log::info;
 use std::path::PathBuf;
@@ -33,6 +37,18 @@ pub struct RequestCli {
     #[clap(long = "bookmark", action)]
     pub save_bookmark: bool,
 
+    /// Describe the bookmark, like what the request is for
+    #[clap(long, value_parser)]
+    pub description: Option<String>,
+
+    /// Tag the bookmark, like `--tag payments --tag admin`, see `curlz bookmark list --tag payments`
+    #[clap(long = "tag", number_of_values = 1, value_parser)]
+    pub tags: Vec<String>,
+
+    /// Save the bookmark to the global bookmarks of the user, like `~/.config/curlz/bookmarks`, instead of the project
+    #[clap(long, action)]
+    pub global: bool,
+
     /// Provide an `.env` or a yaml containing template variables
     #[clap(long = "env-file", value_parser, default_value = ".env")]
     pub env_file: PathBuf,
@@ -41,8 +57,26 @@ pub struct RequestCli {
     #[clap(long, number_of_values = 1, value_parser)]
     pub define: Vec<String>,
 
-    #[clap(short = 'X', long = "request", value_parser, default_value = "GET")]
-    pub http_method: String,
+    /// Seed the random values of template functions like `uuid()` or `fake("email")` for reproducible runs
+    #[clap(long, value_parser)]
+    pub seed: Option<u64>,
+
+    /// Allow template functions like `file()` to read files outside of the current project
+    #[clap(long = "allow-outside-project", action)]
+    pub allow_outside_project: bool,
+
+    /// Never prompt, prompts are answered by `--define`, the env file or `CURLZ_<LABEL>` env vars,
+    /// this is the default when stdin is not a terminal
+    #[clap(long = "non-interactive", action)]
+    pub non_interactive: bool,
+
+    /// Render undefined template variables empty, instead of failing before the request is sent
+    #[clap(long, action)]
+    pub lenient: bool,
+
+    /// the http method, it is `GET` for urls and the method a bookmark was saved with for bookmarks
+    #[clap(short = 'X', long = "request", value_parser)]
+    pub http_method: Option<String>,
 
     /// set one ore more http headers in the form of `"Header-Name: Value"`
     ///
@@ -73,9 +107,21 @@ pub struct RequestCli {
     ///       curlz will prompt for the password interactively
     ///
     /// Equivalent to: `curlz -H 'Authorization: Basic {{ basic("user", "password") }}'`
+    ///
+    /// see `--auth-type` for other authentication schemes than basic auth
     #[clap(short = 'u', long = "user", value_parser)]
     pub user: Option<String>,
 
+    /// the authentication scheme that is used for the credentials of `-u | --user`
+    ///
+    /// - `basic`: sends `Authorization: Basic {{ basic("user", "password") }}`
+    ///
+    /// - `bearer`: takes the whole `-u` value as token and sends `Authorization: Bearer <token>`
+    ///
+    /// - `digest`: answers the digest challenge of a first `401` response from the server, that is requested without body
+    #[clap(long = "auth-type", value_enum, default_value_t = AuthType::Basic)]
+    pub auth_type: AuthType,
+
     #[clap(value_parser)]
     pub bookmark_or_url: Option<String>,
 
@@ -83,6 +129,15 @@ pub struct RequestCli {
     pub raw: Vec<String>,
 }
 
+/// the authentication schemes supported by `-u | --user`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum AuthType {
+    #[default]
+    Basic,
+    Bearer,
+    Digest,
+}
+
 impl RequestCli {
     pub fn parse_define_as_placeholders(&self) -> Vec<Placeholder> {
         self.define
@@ -95,6 +150,21 @@ impl RequestCli {
     }
 }
 
+impl RequestCli {
+    /// the template functions of a request resolve files relative to the current project
+    fn render_session(&self) -> crate::Result<RenderSession> {
+        let session = RenderSession::for_project(is_interactive(self.non_interactive))?
+            .with_seed(self.seed)
+            .with_strict(!self.lenient);
+
+        Ok(if self.allow_outside_project {
+            session.with_sandbox(None)
+        } else {
+            session
+        })
+    }
+}
+
 /// parses `key=value` strings into tuples of (key, value)
 #[inline]
 fn parse_define(define: &str) -> Option<(&str, &str)> {
@@ -105,22 +175,39 @@ impl RequestCli {
     pub fn execute(&self) -> crate::Result<()> {
         let placeholders = self.parse_define_as_placeholders();
         let env = create_environment(&self.env_file, &placeholders)?;
-        let mut raw = self.raw.clone();
-
-        let method = extract_method(&mut raw)
-            .unwrap_or_else(|| HttpMethod::from_str(self.http_method.as_str()))?;
+        // the raw args after `--` are curl arguments, those curlz does not know are passed on to curl
+        let CurlArgs {
+            method: raw_method,
+            url: raw_url,
+            headers: raw_headers,
+            body: raw_body,
+            user: raw_user,
+            curl_params: mut raw,
+        } = parse_curl_args(&self.raw)?;
+
+        let method = match raw_method {
+            Some(method) => Some(method),
+            None => self
+                .http_method
+                .as_deref()
+                .map(HttpMethod::from_str)
+                .transpose()?,
+        };
 
         // headers
         let mut headers: HttpHeaders = self.headers.as_slice().into();
-        let (mut raw, headers_args) = extract_headers(&raw);
-        let headers_raw: HttpHeaders = headers_args.into();
-        headers.merge(&headers_raw);
+        headers.merge(&raw_headers);
         if self.json.is_some() {
             headers.push("Content-Type", "application/json");
             headers.push("Accept", "application/json");
         }
-        if self.user.is_some() {
-            parse_user_to_header(self.user.as_ref().unwrap(), &mut headers)?;
+        let mut auth = None;
+        if let Some(user) = self.user.as_ref().or(raw_user.as_ref()) {
+            match self.auth_type {
+                AuthType::Basic => parse_user_to_header(user, &mut headers),
+                AuthType::Bearer => parse_token_to_header(user, &mut headers),
+                AuthType::Digest => auth = Some(parse_user_to_digest_auth(user)),
+            }
         }
 
         let body = self
@@ -128,76 +215,89 @@ impl RequestCli {
             .as_ref()
             .map(|b| HttpBody::InlineText(b.to_string()))
             .or_else(|| self.json.clone().map(HttpBody::InlineText))
-            .unwrap_or_default();
+            .unwrap_or(raw_body);
 
         let request = if let Some(bookmark_or_url) = self.bookmark_or_url.as_ref() {
+            // curl requests any further url too
+            raw.extend(raw_url.map(|url| url.as_ref().to_string()));
             if is_url(bookmark_or_url) {
                 // here we are certain we got an URL
                 HttpRequest {
-                    // todo: also replace placeholders in there..
                     url: bookmark_or_url.to_string().try_into()?,
-                    method,
+                    method: method.unwrap_or(HttpMethod::Get),
                     version: Http11,
                     headers,
                     body,
                     placeholders,
-                    // todo: implement placeholder scanning..
                     curl_params: raw,
+                    auth,
                 }
             } else {
-                let bookmark_collection = bookmark_collection()?;
                 // here we might have a bookmark slug, but not sure yet
-                let bookmark = load_bookmark(
-                    LoadBookmark::new(bookmark_or_url, method),
-                    &bookmark_collection,
-                )
-                .context("No Bookmark with the given name found")?;
+                let bookmark = find_bookmark(
+                    bookmark_or_url,
+                    method,
+                    &bookmark_collection()?,
+                    self.non_interactive,
+                )?;
 
                 bookmark.request().update(|request| {
                     request.headers.merge(&headers);
                     request.curl_params.extend_from_slice(&raw);
+                    if body != HttpBody::None {
+                        request.body = body.clone();
+                    }
+                    if auth.is_some() {
+                        request.auth = auth.clone();
+                    }
                 })
             }
         } else {
-            // try to extract an URL from the raw args provided
-            extract_url(&mut raw)
-                .context("Raw arguments did not contain any URL")
-                .map(|url| HttpRequest {
-                    url,
-                    method,
-                    version: Http11,
-                    headers,
-                    body,
-                    placeholders,
-                    // todo: implement placeholder scanning..
-                    curl_params: raw,
-                })?
+            HttpRequest {
+                url: raw_url.context("Raw arguments did not contain any URL")?,
+                method: method.unwrap_or(HttpMethod::Get),
+                version: Http11,
+                headers,
+                body,
+                placeholders,
+                curl_params: raw,
+                auth,
+            }
         };
 
+        let mut issue = IssueRequest::new(
+            &request,
+            if self.verbose.is_silent() {
+                Silent
+            } else {
+                Verbose
+            },
+        );
+        if let Some(slug) = self.bookmark_or_url.as_ref().filter(|b| !is_url(b)) {
+            issue = issue.with_origin(format!("bookmark `{}`", slug));
+        }
         issue_request_with_curl(
-            IssueRequest::new(
-                &request,
-                if self.verbose.is_silent() {
-                    Silent
-                } else {
-                    Verbose
-                },
-            ),
-            &env,
+            issue,
+            &RequestContext::new(&env).with_session(self.render_session()?),
         )?;
 
         if self.save_bookmark || self.save_bookmark_as.is_some() {
             let slug = if let Some(answer) = self.save_bookmark_as.as_ref() {
                 answer.clone()
+            } else if !is_interactive(self.non_interactive) {
+                bail!("a bookmark name cannot be asked in a non-interactive run, use `--bookmark-as <name>` instead")
             } else {
                 interactive::user_question("Please enter a bookmark name", &None)?
             };
 
-            let mut bookmark_collection = bookmark_collection()?;
-            save_bookmark(
-                SaveBookmark::new(slug.as_str(), &request),
-                &mut bookmark_collection,
-            )?;
+            let bookmark = SaveBookmark::new(slug.as_str(), &request)
+                .with_description(self.description.clone())
+                .with_tags(self.tags.clone());
+            if self.global {
+                save_bookmark(bookmark, &mut BookmarkFolderCollection::global()?)?;
+            } else {
+                save_bookmark(bookmark, &mut bookmark_collection()?)?;
+            }
 
             info!("Request bookmarked as: {}", slug);
         }
@@ -206,31 +306,36 @@ impl RequestCli {
     }
 }
 
-fn parse_user_to_header(user: &str, headers: &mut HttpHeaders) -> crate::Result<()> {
-    let user_pw: Vec<&str> = user.split_terminator(':').collect();
-    let header_value = match user_pw.len() {
-        1 => {
-            format!(
-                r#"Basic {{{{ basic("{}", prompt_password()) }}}}"#,
-                user_pw.first().unwrap()
-            )
-        }
-        2 => {
-            format!(
-                r#"Basic {{{{ basic("{}", "{}") }}}}"#,
-                user_pw.first().unwrap(),
-                user_pw.get(1).unwrap()
-            )
-        }
-        _ => bail!("-u | -user argument was invalid"),
+/// turns `-u user:password` into a basic auth header, the password is everything after the first `:`,
+/// in cases where only the user is provided, the password will be prompted for
+fn parse_user_to_header(user: &str, headers: &mut HttpHeaders) {
+    let header_value = match user.split_once(':') {
+        Some((user, password)) => basic_auth(user, password),
+        None => basic_auth(user, "{{ prompt_password() }}"),
     };
     headers.push("Authorization", header_value);
+}
 
-    Ok(())
+/// turns the whole `-u` value into a bearer token header
+fn parse_token_to_header(token: &str, headers: &mut HttpHeaders) {
+    headers.push("Authorization", format!("Bearer {}", token));
+}
+
+/// turns `-u user:password` into credentials for a digest challenge,
+/// in cases where only the user is provided, the password will be prompted for
+fn parse_user_to_digest_auth(user: &str) -> HttpAuth {
+    let (user, password) = user
+        .split_once(':')
+        .unwrap_or((user, "{{ prompt_password() }}"));
+
+    HttpAuth::Digest {
+        user: user.to_string(),
+        password: password.to_string(),
+    }
 }
 
 fn bookmark_collection() -> crate::Result<impl BookmarkCollection> {
-    BookmarkFolderCollection::new()
+    ChainedBookmarkCollection::new()
 }
 
 /// checks if a string is a URL
@@ -240,74 +345,6 @@ fn is_url(potential_url: impl AsRef<str>) -> bool {
     trimmed_url.starts_with("http") || trimmed_url.starts_with("{{")
 }
 
-/// Extracts the http headers from command line arguments
-/// If a header `-H | --header` is found, it's removed from the `raw_args`
-fn extract_headers(raw_args: &Vec<String>) -> (Vec<String>, HeaderArgs) {
-    let headers = HeaderArgs::from(raw_args);
-
-    let mut non_header_args = vec![];
-    let mut i = 0_usize;
-    while i < raw_args.len() {
-        match raw_args.get(i).unwrap().as_str() {
-            "-H" | "--header" => {
-                i += 2;
-            }
-            v => {
-                non_header_args.push(v.to_string());
-                i += 1;
-            }
-        }
-    }
-
-    (non_header_args, headers)
-}
-
-/// Extracts the http method from the command line arguments
-/// If a method `-X | --request` is found, it's removed from the `raw_args`
-///
-/// ## Fallible
-/// If the method is provided but invalid, an error is returned
-///
-/// ## None
-/// simple: in case no http method is provided, None is returned
-fn extract_method(raw_args: &mut Vec<String>) -> Option<crate::Result<HttpMethod>> {
-    let mut method = None;
-
-    let copy = raw_args.clone();
-    copy.iter()
-        .enumerate()
-        .step_by(2)
-        .zip(copy.iter().enumerate().skip(1).step_by(2))
-        .for_each(|((ik, key), (iv, value))| {
-            if key.as_str().eq("-X") || key.as_str().eq("--request") {
-                raw_args.remove(ik);
-                raw_args.remove(iv);
-                method = Some(HttpMethod::from_str(value.as_str()));
-            }
-        });
-
-    method
-}
-
-/// extracts a `http://` or `https://` URL from the command line arguments `raw_args`
-/// if a URL is found it's removed from the `raw_args` vector and returned
-/// If no URL is found, returns `None`
-fn extract_url(raw_args: &mut Vec<String>) -> Option<HttpUri> {
-    if let Some(potential_url) = raw_args.last().cloned() {
-        if potential_url.trim_start_matches('\'').starts_with("http") {
-            raw_args.pop();
-            // todo: no unwrap here:
-            Some(potential_url.try_into().unwrap())
-        } else if potential_url.starts_with("{{") {
-            todo!("placeholder evaluation at the beginning of URLs")
-        } else {
-            None
-        }
-    } else {
-        None
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -320,73 +357,38 @@ mod tests {
     }
 
     #[test]
-    fn should_extract_a_url_as_last_argument() {
-        let mut args = vec!["--request", "GET", "http://example.com"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        let url = extract_url(&mut args);
+    fn should_parse_user_to_header() {
+        let mut headers = HttpHeaders::default();
+        parse_user_to_header("john:secret", &mut headers);
         assert_eq!(
-            url,
-            Some("http://example.com".to_string().try_into().unwrap())
-        );
-        assert_eq!(args.len(), 2);
-    }
-
-    #[test]
-    fn should_extract_method() {
-        let mut args = vec!["--request", "GET", "http://example.com"]
-            .iter()
-            .map(|s| s.to_string())
-            .collect();
-        let method = extract_method(&mut args).unwrap().unwrap();
-        assert_eq!(method, HttpMethod::Get);
-        assert_eq!(args.len(), 1);
+            headers.get("Authorization").unwrap(),
+            r#"Basic {{ basic("john", "secret") }}"#
+        )
     }
 
     #[test]
-    fn should_extract_headers() {
-        let args = vec![
-            "-vvv",
-            "-H",
-            "foo: bar",
-            "--header",
-            "Accept: application/json",
-            "--header",
-            r#"Authorization: Baerer {{ jwt({"foo": "bar"}) }}"#,
-            "http://example.com",
-        ]
-        .iter()
-        .map(ToString::to_string)
-        .collect();
-        let (args, headers) = extract_headers(&args);
+    fn should_parse_user_to_header_with_a_colon_in_the_password() {
+        let mut headers = HttpHeaders::default();
+        parse_user_to_header("john:se:cret", &mut headers);
         assert_eq!(
-            headers.as_ref(),
-            &[
-                "foo: bar".to_string(),
-                "Accept: application/json".to_string(),
-                r#"Authorization: Baerer {{ jwt({"foo": "bar"}) }}"#.to_string(),
-            ]
-        );
-        // TODO: it's unclear why this here fails:
-        assert_eq!(args.len(), 2);
-        assert_eq!(args.first(), Some(&"-vvv".to_string()));
-        assert_eq!(args.last(), Some(&"http://example.com".to_string()));
+            headers.get("Authorization").unwrap(),
+            r#"Basic {{ basic("john", "se:cret") }}"#
+        )
     }
 
     #[test]
-    fn should_parse_user_to_header() {
+    fn should_parse_user_to_header_with_a_quote_in_the_password() {
         let mut headers = HttpHeaders::default();
-        parse_user_to_header("john:secret", &mut headers).unwrap();
+        parse_user_to_header(r#"john:se"cret"#, &mut headers);
         assert_eq!(
             headers.get("Authorization").unwrap(),
-            r#"Basic {{ basic("john", "secret") }}"#
+            r#"Basic {{ basic("john", "se\"cret") }}"#
         )
     }
     #[test]
     fn should_parse_user_to_header_without_password() {
         let mut headers = HttpHeaders::default();
-        parse_user_to_header("john", &mut headers).unwrap();
+        parse_user_to_header("john", &mut headers);
         assert_eq!(
             headers.get("Authorization").unwrap(),
             r#"Basic {{ basic("john", prompt_password()) }}"#
@@ -394,9 +396,30 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "-u | -user argument was invalid")]
-    fn should_bail_on_garbage_in() {
+    fn should_parse_token_to_bearer_header() {
         let mut headers = HttpHeaders::default();
-        parse_user_to_header("john:xxxx:asdfa", &mut headers).unwrap();
+        parse_token_to_header("{{ token }}", &mut headers);
+        assert_eq!(
+            headers.get("Authorization").unwrap(),
+            r#"Bearer {{ token }}"#
+        )
+    }
+
+    #[test]
+    fn should_parse_user_to_digest_auth() {
+        assert_eq!(
+            parse_user_to_digest_auth("john:se:cret"),
+            HttpAuth::Digest {
+                user: "john".to_string(),
+                password: "se:cret".to_string()
+            }
+        );
+        assert_eq!(
+            parse_user_to_digest_auth("john"),
+            HttpAuth::Digest {
+                user: "john".to_string(),
+                password: "{{ prompt_password() }}".to_string()
+            }
+        );
     }
 }
@@ -0,0 +1,40 @@
+use crate::domain::bookmark::{project_dir, WORKSPACE_FOLDER};
+use crate::template::library::{list_templates, TEMPLATES_FOLDER};
+
+use clap::{Args, Subcommand};
+use log::info;
+
+#[derive(Clone, Debug, Args)]
+#[command(arg_required_else_help = true)]
+pub struct TemplatesCli {
+    #[clap(subcommand)]
+    pub command: TemplatesCommands,
+}
+
+#[derive(Clone, Debug, Subcommand)]
+pub enum TemplatesCommands {
+    /// lists the templates of `.curlz/templates/` with the macros they provide
+    List,
+}
+
+impl TemplatesCli {
+    pub fn execute(&self) -> crate::Result<()> {
+        match self.command {
+            TemplatesCommands::List => {
+                let dir = project_dir()?.join(WORKSPACE_FOLDER).join(TEMPLATES_FOLDER);
+                let templates = list_templates(&dir)?;
+                if templates.is_empty() {
+                    info!("No templates found in {}", dir.display());
+                }
+                for template in templates {
+                    println!("{}", template.name);
+                    for m in template.macros {
+                        println!("  {}", m);
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
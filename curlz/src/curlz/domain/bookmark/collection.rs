@@ -5,4 +5,8 @@ use crate::Result;
 pub trait BookmarkCollection {
     fn save(&self, bookmark: &Bookmark) -> Result<()>;
     fn load(&self, name: impl AsRef<str>, method: &HttpMethod) -> Result<Option<Bookmark>>;
+    /// all bookmarks of the collection, sorted by their slug
+    fn list(&self) -> Result<Vec<Bookmark>>;
+    /// removes the bookmark, `false` if there was none
+    fn remove(&self, name: impl AsRef<str>, method: &HttpMethod) -> Result<bool>;
 }
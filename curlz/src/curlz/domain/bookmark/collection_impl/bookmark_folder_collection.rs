@@ -1,100 +1,200 @@
 use anyhow::anyhow;
 use convert_case::{Case, Casing};
 use filenamify::filenamify;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::{env, fs};
 
 use crate::domain::bookmark::collection::BookmarkCollection;
-use crate::domain::bookmark::Bookmark;
+use crate::domain::bookmark::{slug_segments, Bookmark};
 use crate::domain::http::HttpMethod;
 use crate::Result;
 
-const WORKSPACE_FOLDER: &str = ".curlz";
+/// the folder of a project that holds bookmarks and other curlz files
+pub const WORKSPACE_FOLDER: &str = ".curlz";
 const BOOKMARK_FOLDER: &str = "bookmarks";
+/// the folder of the user's curlz files in the config folder, like `~/.config/curlz`
+const GLOBAL_FOLDER: &str = "curlz";
 
+/// the folder of the current project, that is the nearest folder from the current one upwards that holds a `.curlz` folder,
+/// or the current folder if there is none
+pub fn project_dir() -> Result<PathBuf> {
+    let current_dir =
+        env::current_dir().map_err(|e| anyhow!("cannot find the current folder: {}", e))?;
+
+    Ok(find_project_dir(&current_dir).unwrap_or(current_dir))
+}
+
+/// the nearest folder from `dir` upwards that holds a `.curlz` folder
+pub fn find_project_dir(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|dir| dir.join(WORKSPACE_FOLDER).is_dir())
+        .map(Path::to_path_buf)
+}
+
+/// the folder of the user's curlz files, like `~/.config/curlz` or `$XDG_CONFIG_HOME/curlz`
+pub fn global_dir() -> Result<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+        .ok_or_else(|| anyhow!("cannot find the config folder, set `XDG_CONFIG_HOME` or `HOME`"))?;
+
+    Ok(config_dir.join(GLOBAL_FOLDER))
+}
+
+/// the bookmarks of a folder, each in a yaml file
 pub struct BookmarkFolderCollection {
-    working_dir: PathBuf,
+    bookmarks_dir: PathBuf,
 }
 
 impl BookmarkFolderCollection {
+    /// the bookmarks of the current project, in its `.curlz/bookmarks` folder
     pub fn new() -> Result<Self> {
+        Ok(Self::of_project(&project_dir()?))
+    }
+
+    /// the bookmarks of the user, shared by all projects, like in `~/.config/curlz/bookmarks`
+    pub fn global() -> Result<Self> {
         Ok(Self {
-            working_dir: env::current_dir()
-                .map_err(|e| anyhow!("cannot create processor: {}", e))?,
+            bookmarks_dir: global_dir()?.join(BOOKMARK_FOLDER),
         })
     }
+
+    fn of_project(project_dir: &Path) -> Self {
+        Self {
+            bookmarks_dir: project_dir.join(WORKSPACE_FOLDER).join(BOOKMARK_FOLDER),
+        }
+    }
+
+    /// the file a bookmark of `slug` and `method` is stored in, like `.curlz/bookmarks/get_users.yml`,
+    /// the folders of the slug become subfolders, like `.curlz/bookmarks/users/get_get_user.yml` for `users/get-user`
+    pub fn bookmark_path(&self, slug: &str, method: &HttpMethod) -> PathBuf {
+        let mut segments = slug_segments(slug);
+        let name = segments.pop().unwrap_or_default();
+        let dir = segments
+            .into_iter()
+            .fold(self.bookmarks_dir.clone(), |dir, folder| {
+                dir.join(filenamify(folder).to_case(Case::Snake))
+            });
+        let file_name = filenamify(format!("{:?} {}", method, name)).to_case(Case::Snake);
+
+        dir.join(format!("{}.yml", file_name))
+    }
+
+    /// the file a bookmark was stored in before slugs became folders, like `.curlz/bookmarks/get_users_get_user.yml`
+    fn legacy_bookmark_path(&self, slug: &str, method: &HttpMethod) -> PathBuf {
+        let file_name = filenamify(format!("{:?} {}", method, slug)).to_case(Case::Snake);
+        self.bookmarks_dir.join(format!("{}.yml", file_name))
+    }
+}
+
+/// all bookmark files in `dir` and its subfolders
+fn bookmark_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(bookmark_files(&path)?);
+        } else if path.extension().and_then(|ext| ext.to_str()) == Some("yml") {
+            files.push(path);
+        }
+    }
+
+    Ok(files)
 }
 
 impl BookmarkCollection for BookmarkFolderCollection {
     fn save(&self, bookmark: &Bookmark) -> Result<()> {
-        let slug = bookmark.slug();
-        let request = bookmark.request();
-
-        let file_name = filenamify(format!("{:?} {}", &request.method, slug)).to_case(Case::Snake);
+        let file_path = self.bookmark_path(bookmark.slug(), &bookmark.request().method);
+        let legacy_path = self.legacy_bookmark_path(bookmark.slug(), &bookmark.request().method);
         let bookmark = serde_yaml::to_string(&bookmark)?;
 
-        let bookmarks_path = self
-            .working_dir
-            .join(WORKSPACE_FOLDER)
-            .join(BOOKMARK_FOLDER);
-        fs::create_dir_all(bookmarks_path.as_path())?;
-        {
-            fs::write(
-                bookmarks_path.join(format!("{}.yml", file_name.as_str())),
-                bookmark,
-            )
-            .map_err(|e| anyhow!("cannot write request bookmark to file: {}", e))
+        if let Some(dir) = file_path.parent() {
+            fs::create_dir_all(dir)?;
         }
+        fs::write(&file_path, bookmark)
+            .map_err(|e| anyhow!("cannot write request bookmark to file: {}", e))?;
+        if legacy_path != file_path && legacy_path.exists() {
+            fs::remove_file(legacy_path)?;
+        }
+
+        Ok(())
     }
 
     fn load(&self, name: impl AsRef<str>, method: &HttpMethod) -> Result<Option<Bookmark>> {
-        let slug = name.as_ref();
-        let bookmarks_path = self
-            .working_dir
-            .join(WORKSPACE_FOLDER)
-            .join(BOOKMARK_FOLDER);
-        let file_name = filenamify(format!("{:?} {}", method, slug)).to_case(Case::Snake);
-        let file_path = bookmarks_path.join(format!("{}.yml", file_name.as_str()));
-        if !file_path.exists() {
+        let file_path = [
+            self.bookmark_path(name.as_ref(), method),
+            self.legacy_bookmark_path(name.as_ref(), method),
+        ]
+        .into_iter()
+        .find(|path| path.exists());
+        let Some(file_path) = file_path else {
             return Ok(None);
-        }
+        };
         let bookmark = fs::read_to_string(file_path)?;
         Ok(Some(serde_yaml::from_str(&bookmark)?))
     }
+
+    fn list(&self) -> Result<Vec<Bookmark>> {
+        if !self.bookmarks_dir.is_dir() {
+            return Ok(vec![]);
+        }
+
+        let mut bookmarks = bookmark_files(&self.bookmarks_dir)?
+            .into_iter()
+            .map(|path| {
+                let bookmark = fs::read_to_string(&path)?;
+                serde_yaml::from_str(&bookmark)
+                    .map_err(|e| anyhow!("invalid bookmark `{}`: {}", path.display(), e))
+            })
+            .collect::<Result<Vec<Bookmark>>>()?;
+        bookmarks.sort_by(|a, b| a.slug.cmp(&b.slug));
+
+        Ok(bookmarks)
+    }
+
+    fn remove(&self, name: impl AsRef<str>, method: &HttpMethod) -> Result<bool> {
+        let mut removed = false;
+        for path in [
+            self.bookmark_path(name.as_ref(), method),
+            self.legacy_bookmark_path(name.as_ref(), method),
+        ] {
+            if path.exists() {
+                fs::remove_file(&path)
+                    .map_err(|e| anyhow!("cannot remove `{}`: {}", path.display(), e))?;
+                removed = true;
+            }
+        }
+
+        Ok(removed)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::domain::bookmark::SaveBookmark;
-    use crate::domain::http::HttpVersion::Http11;
-    use crate::domain::http::{HttpBody, HttpMethod};
-    use crate::domain::http::{HttpHeaders, HttpRequest};
+    use crate::domain::http::{HttpMethod, HttpRequest};
     use crate::template::variables::Placeholder;
+    use crate::test_utils::request;
     use tempfile::{tempdir, TempDir};
 
     impl BookmarkFolderCollection {
         pub fn temporary() -> (Self, TempDir) {
             let tempdir = tempdir().unwrap();
-            (
-                Self {
-                    working_dir: tempdir.path().to_path_buf(),
-                },
-                tempdir,
-            )
+            (Self::of_project(tempdir.path()), tempdir)
         }
     }
 
     #[test]
     fn should_handle_save_bookmark_command() {
         let request = HttpRequest {
-            url: "{{protonmail_api_baseurl}}/pks/lookup?op=get&search={{email}}".into(),
-            method: HttpMethod::Get,
-            version: Http11,
-            headers: HttpHeaders::default(),
-            body: HttpBody::default(),
-            curl_params: vec![],
             placeholders: vec![email_placeholder(), protonmail_api_baseurl_placeholder()],
+            ..request(
+                "{{protonmail_api_baseurl}}/pks/lookup?op=get&search={{email}}",
+                HttpMethod::Get,
+            )
         };
         let cmd = SaveBookmark::new("/protonmail/gpg/:email", &request);
 
@@ -106,7 +206,9 @@ mod tests {
                 tmp.path()
                     .join(WORKSPACE_FOLDER)
                     .join(BOOKMARK_FOLDER)
-                    .join("get_protonmail_gpg_email.yml"),
+                    .join("protonmail")
+                    .join("gpg")
+                    .join("get_email.yml"),
             )
             .unwrap(),
         )
@@ -115,12 +217,83 @@ mod tests {
         insta::assert_snapshot!(saved_bookmark);
     }
 
+    #[test]
+    fn should_list_all_bookmarks_by_slug() {
+        let (p, _tmp) = BookmarkFolderCollection::temporary();
+        p.save(&(&SaveBookmark::new("users", &request("/users", HttpMethod::Get))).into())
+            .unwrap();
+        p.save(&(&SaveBookmark::new("orders", &request("/orders", HttpMethod::Get))).into())
+            .unwrap();
+
+        let slugs = p
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|b| b.slug)
+            .collect::<Vec<_>>();
+        assert_eq!(slugs, ["orders", "users"]);
+    }
+
+    #[test]
+    fn should_list_bookmarks_of_subfolders() {
+        let (p, tmp) = BookmarkFolderCollection::temporary();
+        p.save(
+            &(&SaveBookmark::new("users/get-user", &request("/users/1", HttpMethod::Get))).into(),
+        )
+        .unwrap();
+        p.save(&(&SaveBookmark::new("orders", &request("/orders", HttpMethod::Get))).into())
+            .unwrap();
+
+        assert!(tmp
+            .path()
+            .join(WORKSPACE_FOLDER)
+            .join(BOOKMARK_FOLDER)
+            .join("users")
+            .join("get_get_user.yml")
+            .is_file());
+        let slugs = p
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|b| b.slug)
+            .collect::<Vec<_>>();
+        assert_eq!(slugs, ["orders", "users/get-user"]);
+    }
+
+    #[test]
+    fn should_load_and_move_bookmarks_of_the_flat_layout() {
+        let (p, _tmp) = BookmarkFolderCollection::temporary();
+        let bookmark: Bookmark =
+            (&SaveBookmark::new("users/get-user", &request("/users/1", HttpMethod::Get))).into();
+        let legacy_path = p.legacy_bookmark_path("users/get-user", &HttpMethod::Get);
+        fs::create_dir_all(legacy_path.parent().unwrap()).unwrap();
+        fs::write(&legacy_path, serde_yaml::to_string(&bookmark).unwrap()).unwrap();
+
+        let loaded = p.load("users/get-user", &HttpMethod::Get).unwrap();
+        assert_eq!(loaded.as_ref(), Some(&bookmark));
+
+        p.save(&bookmark).unwrap();
+        assert!(!legacy_path.exists());
+        assert_eq!(p.list().unwrap(), [bookmark]);
+    }
+
+    #[test]
+    fn should_find_the_project_dir_upwards() {
+        let project = tempdir().unwrap();
+        fs::create_dir(project.path().join(WORKSPACE_FOLDER)).unwrap();
+        let sub_dir = project.path().join("api").join("users");
+        fs::create_dir_all(&sub_dir).unwrap();
+
+        assert_eq!(find_project_dir(&sub_dir).as_deref(), Some(project.path()));
+    }
+
     fn email_placeholder() -> Placeholder {
         Placeholder {
             name: "email".to_string(),
             value: None,
             default: None,
             prompt: "enter an email address".to_string().into(),
+            ..Default::default()
         }
     }
 
@@ -130,6 +303,7 @@ mod tests {
             value: None,
             default: "https://api.protonmail.ch".to_string().into(),
             prompt: "enter the protonmail api baseurl".to_string().into(),
+            ..Default::default()
         }
     }
 }
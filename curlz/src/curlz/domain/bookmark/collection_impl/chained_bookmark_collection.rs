@@ -0,0 +1,205 @@
+use anyhow::bail;
+use log::debug;
+use std::fmt::{Display, Formatter};
+
+use crate::domain::bookmark::collection::BookmarkCollection;
+use crate::domain::bookmark::collection_impl::BookmarkFolderCollection;
+use crate::domain::bookmark::Bookmark;
+use crate::domain::http::HttpMethod;
+use crate::Result;
+
+/// the store a bookmark comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookmarkOrigin {
+    /// the `.curlz/bookmarks` folder of the project
+    Project,
+    /// the bookmarks of the user, like in `~/.config/curlz/bookmarks`
+    Global,
+}
+
+impl Display for BookmarkOrigin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BookmarkOrigin::Project => f.pad("project"),
+            BookmarkOrigin::Global => f.pad("global"),
+        }
+    }
+}
+
+/// looks bookmarks up in the project first, then in the global store,
+/// new bookmarks are saved to the project
+pub struct ChainedBookmarkCollection {
+    stores: Vec<(BookmarkOrigin, BookmarkFolderCollection)>,
+}
+
+impl ChainedBookmarkCollection {
+    pub fn new() -> Result<Self> {
+        let mut stores = vec![(BookmarkOrigin::Project, BookmarkFolderCollection::new()?)];
+        match BookmarkFolderCollection::global() {
+            Ok(global) => stores.push((BookmarkOrigin::Global, global)),
+            Err(e) => debug!("the global bookmarks are left out: {}", e),
+        }
+
+        Ok(Self { stores })
+    }
+
+    /// all bookmarks with their origin, sorted by their slug,
+    /// a global bookmark is hidden by a project bookmark of the same slug and method
+    pub fn list_with_origin(&self) -> Result<Vec<(BookmarkOrigin, Bookmark)>> {
+        let mut bookmarks: Vec<(BookmarkOrigin, Bookmark)> = vec![];
+        for (origin, store) in self.stores.iter() {
+            for bookmark in store.list()? {
+                let hidden = bookmarks.iter().any(|(_, b)| {
+                    b.slug == bookmark.slug && b.request.method == bookmark.request.method
+                });
+                if !hidden {
+                    bookmarks.push((*origin, bookmark));
+                }
+            }
+        }
+        bookmarks.sort_by(|(_, a), (_, b)| a.slug.cmp(&b.slug));
+
+        Ok(bookmarks)
+    }
+
+    /// renames a `bookmark` to `new_slug`, in the store it comes from
+    pub fn rename(&self, bookmark: &Bookmark, new_slug: &str) -> Result<()> {
+        let method = &bookmark.request.method;
+        if self.load(new_slug, method)?.is_some() {
+            bail!(
+                "the bookmark `{}` exists already for `-X {}`",
+                new_slug,
+                String::from(method)
+            );
+        }
+        for (_, store) in self.stores.iter() {
+            if store.load(&bookmark.slug, method)?.is_some() {
+                store.save(&Bookmark {
+                    slug: new_slug.to_string(),
+                    ..bookmark.clone()
+                })?;
+                store.remove(&bookmark.slug, method)?;
+                return Ok(());
+            }
+        }
+
+        bail!("the bookmark `{}` does not exist", bookmark.slug)
+    }
+}
+
+impl BookmarkCollection for ChainedBookmarkCollection {
+    fn save(&self, bookmark: &Bookmark) -> Result<()> {
+        self.stores[0].1.save(bookmark)
+    }
+
+    fn load(&self, name: impl AsRef<str>, method: &HttpMethod) -> Result<Option<Bookmark>> {
+        for (_, store) in self.stores.iter() {
+            if let Some(bookmark) = store.load(name.as_ref(), method)? {
+                return Ok(Some(bookmark));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn list(&self) -> Result<Vec<Bookmark>> {
+        Ok(self
+            .list_with_origin()?
+            .into_iter()
+            .map(|(_, bookmark)| bookmark)
+            .collect())
+    }
+
+    /// removes the bookmark from the first store that holds it, like [`Self::load`] finds it
+    fn remove(&self, name: impl AsRef<str>, method: &HttpMethod) -> Result<bool> {
+        for (_, store) in self.stores.iter() {
+            if store.remove(name.as_ref(), method)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::bookmark::SaveBookmark;
+    use crate::test_utils::request;
+    use tempfile::TempDir;
+
+    fn chained() -> (ChainedBookmarkCollection, [TempDir; 2]) {
+        let (project, project_dir) = BookmarkFolderCollection::temporary();
+        let (global, global_dir) = BookmarkFolderCollection::temporary();
+        let chained = ChainedBookmarkCollection {
+            stores: vec![
+                (BookmarkOrigin::Project, project),
+                (BookmarkOrigin::Global, global),
+            ],
+        };
+
+        (chained, [project_dir, global_dir])
+    }
+
+    fn bookmark(slug: &str, url: &str) -> Bookmark {
+        let request = request(url, HttpMethod::Get);
+        (&SaveBookmark::new(slug, &request)).into()
+    }
+
+    #[test]
+    fn should_look_bookmarks_up_in_the_project_first() {
+        let (chained, _dirs) = chained();
+        let global = &chained.stores[1].1;
+        global.save(&bookmark("users", "/global/users")).unwrap();
+        global.save(&bookmark("health", "/health")).unwrap();
+        chained.save(&bookmark("users", "/project/users")).unwrap();
+
+        let users = chained.load("users", &HttpMethod::Get).unwrap().unwrap();
+        assert_eq!(users.request.url.as_ref(), "/project/users");
+        let health = chained.load("health", &HttpMethod::Get).unwrap().unwrap();
+        assert_eq!(health.request.url.as_ref(), "/health");
+        assert!(chained.load("orders", &HttpMethod::Get).unwrap().is_none());
+    }
+
+    #[test]
+    fn should_list_bookmarks_with_their_origin() {
+        let (chained, _dirs) = chained();
+        let global = &chained.stores[1].1;
+        global.save(&bookmark("users", "/global/users")).unwrap();
+        global.save(&bookmark("health", "/health")).unwrap();
+        chained.save(&bookmark("users", "/project/users")).unwrap();
+
+        let listed = chained
+            .list_with_origin()
+            .unwrap()
+            .into_iter()
+            .map(|(origin, b)| (origin, b.slug))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            listed,
+            [
+                (BookmarkOrigin::Global, "health".to_string()),
+                (BookmarkOrigin::Project, "users".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_rename_and_remove_bookmarks_in_their_store() {
+        let (chained, _dirs) = chained();
+        let global = &chained.stores[1].1;
+        global.save(&bookmark("health", "/health")).unwrap();
+        chained.save(&bookmark("users", "/users")).unwrap();
+
+        let health = chained.load("health", &HttpMethod::Get).unwrap().unwrap();
+        chained.rename(&health, "status").unwrap();
+        assert!(global.load("status", &HttpMethod::Get).unwrap().is_some());
+        assert!(global.load("health", &HttpMethod::Get).unwrap().is_none());
+        assert!(chained.rename(&health, "users").is_err());
+
+        assert!(chained.remove("users", &HttpMethod::Get).unwrap());
+        assert!(!chained.remove("users", &HttpMethod::Get).unwrap());
+        assert!(chained.load("users", &HttpMethod::Get).unwrap().is_none());
+    }
+}
@@ -1,3 +1,7 @@
 mod bookmark_folder_collection;
+mod chained_bookmark_collection;
 
-pub use bookmark_folder_collection::BookmarkFolderCollection;
+pub use bookmark_folder_collection::{
+    find_project_dir, global_dir, project_dir, BookmarkFolderCollection, WORKSPACE_FOLDER,
+};
+pub use chained_bookmark_collection::{BookmarkOrigin, ChainedBookmarkCollection};
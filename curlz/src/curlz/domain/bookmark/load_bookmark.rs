@@ -1,14 +1,18 @@
 use crate::domain::bookmark::{Bookmark, BookmarkCollection};
 use crate::domain::http::HttpMethod;
 
+/// the most suggestions of similar slugs
+const MAX_SUGGESTIONS: usize = 3;
+
 #[derive(Debug)]
 pub struct LoadBookmark {
     pub slug: String,
-    pub http_method: HttpMethod,
+    /// the method of the bookmark, any method if omitted
+    pub http_method: Option<HttpMethod>,
 }
 
 impl LoadBookmark {
-    pub fn new(slug: impl AsRef<str>, http_method: HttpMethod) -> Self {
+    pub fn new(slug: impl AsRef<str>, http_method: Option<HttpMethod>) -> Self {
         Self {
             slug: slug.as_ref().to_owned(),
             http_method,
@@ -16,6 +20,184 @@ impl LoadBookmark {
     }
 }
 
-pub fn load_bookmark(bm: LoadBookmark, collection: &impl BookmarkCollection) -> Option<Bookmark> {
-    collection.load(bm.slug, &bm.http_method).unwrap()
+/// the outcome of loading a bookmark by its slug
+#[derive(Debug, PartialEq, Eq)]
+pub enum LoadedBookmark {
+    Found(Box<Bookmark>),
+    /// the slug is bookmarked for several methods
+    Ambiguous(Vec<Bookmark>),
+    NotFound {
+        /// the methods the slug is bookmarked for, when it is not for the requested one
+        methods: Vec<HttpMethod>,
+        /// similar slugs, the most similar first
+        suggestions: Vec<String>,
+    },
+}
+
+/// loads a bookmark by its slug, of the given method or of the one it was saved with
+pub fn load_bookmark(
+    bm: LoadBookmark,
+    collection: &impl BookmarkCollection,
+) -> crate::Result<LoadedBookmark> {
+    if let Some(method) = bm.http_method.as_ref() {
+        if let Some(bookmark) = collection.load(&bm.slug, method)? {
+            return Ok(LoadedBookmark::Found(Box::new(bookmark)));
+        }
+    }
+
+    let bookmarks = collection.list()?;
+    let mut found = bookmarks
+        .iter()
+        .filter(|b| b.slug == bm.slug)
+        .collect::<Vec<_>>();
+    if bm.http_method.is_none() {
+        match found.len() {
+            0 => {}
+            1 => return Ok(LoadedBookmark::Found(Box::new(found.remove(0).clone()))),
+            _ => {
+                return Ok(LoadedBookmark::Ambiguous(
+                    found.into_iter().cloned().collect(),
+                ))
+            }
+        }
+    }
+
+    let mut similar = bookmarks
+        .iter()
+        .map(|b| b.slug.as_str())
+        .filter(|slug| *slug != bm.slug)
+        .filter_map(|slug| similarity(&bm.slug, slug).map(|distance| (distance, slug)))
+        .collect::<Vec<_>>();
+    similar.sort();
+    let mut suggestions: Vec<String> = vec![];
+    for (_, slug) in similar {
+        if !suggestions.iter().any(|s| s == slug) && suggestions.len() < MAX_SUGGESTIONS {
+            suggestions.push(slug.to_string());
+        }
+    }
+
+    Ok(LoadedBookmark::NotFound {
+        methods: found
+            .into_iter()
+            .map(|b| b.request.method.clone())
+            .collect(),
+        suggestions,
+    })
+}
+
+/// how far a `candidate` slug is from a mistyped `slug`, if it is similar at all,
+/// like `users` for `usres` or `users/get-user` for `get-user`
+fn similarity(slug: &str, candidate: &str) -> Option<usize> {
+    let distance = edit_distance(slug, candidate);
+    let similar = distance <= (slug.chars().count() / 3).max(1)
+        || candidate.ends_with(&format!("/{}", slug))
+        || slug.ends_with(&format!("/{}", candidate));
+
+    similar.then_some(distance)
+}
+
+/// the edit distance of two strings, a swap of two adjacent characters counts as one edit, like for `usres`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::bookmark::{BookmarkFolderCollection, SaveBookmark};
+    use crate::test_utils::request;
+    use rstest::rstest;
+
+    fn collection() -> (BookmarkFolderCollection, tempfile::TempDir) {
+        let (collection, dir) = BookmarkFolderCollection::temporary();
+        for (slug, method) in [
+            ("users", HttpMethod::Get),
+            ("users", HttpMethod::Post),
+            ("orders", HttpMethod::Post),
+            ("admin/user-roles", HttpMethod::Get),
+        ] {
+            let request = request("https://example.com", method);
+            collection
+                .save(&(&SaveBookmark::new(slug, &request)).into())
+                .unwrap();
+        }
+
+        (collection, dir)
+    }
+
+    fn load(slug: &str, method: Option<HttpMethod>) -> LoadedBookmark {
+        let (collection, _dir) = collection();
+        load_bookmark(LoadBookmark::new(slug, method), &collection).unwrap()
+    }
+
+    #[test]
+    fn should_load_a_bookmark_by_its_slug_with_its_method() {
+        let LoadedBookmark::Found(bookmark) = load("orders", None) else {
+            panic!("the bookmark `orders` is not found");
+        };
+        assert_eq!(bookmark.request.method, HttpMethod::Post);
+    }
+
+    #[test]
+    fn should_load_a_bookmark_of_a_method() {
+        let LoadedBookmark::Found(bookmark) = load("users", Some(HttpMethod::Post)) else {
+            panic!("the bookmark `users` is not found");
+        };
+        assert_eq!(bookmark.request.method, HttpMethod::Post);
+    }
+
+    #[test]
+    fn should_be_ambiguous_for_a_slug_of_several_methods() {
+        let LoadedBookmark::Ambiguous(bookmarks) = load("users", None) else {
+            panic!("the bookmark `users` is not ambiguous");
+        };
+        let methods = bookmarks
+            .into_iter()
+            .map(|b| b.request.method)
+            .collect::<Vec<_>>();
+        assert!(methods.contains(&HttpMethod::Get) && methods.contains(&HttpMethod::Post));
+    }
+
+    #[test]
+    fn should_name_the_methods_a_slug_is_bookmarked_for() {
+        assert_eq!(
+            load("orders", Some(HttpMethod::Get)),
+            LoadedBookmark::NotFound {
+                methods: vec![HttpMethod::Post],
+                suggestions: vec![],
+            }
+        );
+    }
+
+    #[rstest]
+    #[case("usres", &["users"])]
+    #[case("order", &["orders"])]
+    #[case("user-roles", &["admin/user-roles"])]
+    #[case("payments", &[])]
+    fn should_suggest_similar_slugs(#[case] slug: &str, #[case] expected: &[&str]) {
+        let LoadedBookmark::NotFound { suggestions, .. } = load(slug, None) else {
+            panic!("the bookmark `{}` is found", slug);
+        };
+        assert_eq!(suggestions, expected);
+    }
 }
@@ -4,7 +4,10 @@ mod load_bookmark;
 mod save_bookmark;
 
 pub use self::collection::BookmarkCollection;
-pub use self::collection_impl::BookmarkFolderCollection;
+pub use self::collection_impl::{
+    find_project_dir, global_dir, project_dir, BookmarkFolderCollection, BookmarkOrigin,
+    ChainedBookmarkCollection, WORKSPACE_FOLDER,
+};
 pub use self::load_bookmark::*;
 pub use self::save_bookmark::*;
 
@@ -12,9 +15,16 @@ use crate::domain::http::HttpRequest;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Bookmark {
+    /// the name of the bookmark, a `/` separates its folders, like `users/get-user`
     pub slug: String,
+    /// what the request is for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// like `payments`, bookmarks can be listed by their tags
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
     pub request: HttpRequest,
 }
 
@@ -38,13 +48,94 @@ impl Bookmark {
     pub fn request(&self) -> &HttpRequest {
         self.as_ref()
     }
+
+    /// the folders of the slug, like `["users"]` for `users/get-user`
+    pub fn folders(&self) -> Vec<&str> {
+        let mut segments = slug_segments(&self.slug);
+        segments.pop();
+        segments
+    }
+
+    /// whether the bookmark is the `folder` or in it, like `users/get-user` in `users/` or `users`
+    pub fn is_in(&self, folder: &str) -> bool {
+        let folder = slug_segments(folder);
+        let slug = slug_segments(&self.slug);
+
+        slug.starts_with(&folder)
+    }
+
+    /// whether the bookmark has all the `tags`
+    pub fn has_tags(&self, tags: &[String]) -> bool {
+        tags.iter().all(|tag| self.tags.contains(tag))
+    }
+}
+
+/// the segments of a slug, like `["protonmail", "gpg", ":email"]` for `/protonmail/gpg/:email`
+pub(crate) fn slug_segments(slug: &str) -> Vec<&str> {
+    slug.split('/')
+        .filter(|segment| !matches!(*segment, "" | "." | ".."))
+        .collect()
 }
 
 impl<'a> From<&SaveBookmark<'a>> for Bookmark {
     fn from(cmd: &SaveBookmark<'a>) -> Self {
         Self {
             slug: cmd.slug.clone(),
+            description: cmd.description.clone(),
+            tags: cmd.tags.clone(),
             request: cmd.bookmark.clone(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::http::HttpMethod;
+    use crate::test_utils::request;
+    use indoc::indoc;
+    use rstest::rstest;
+
+    fn bookmark(slug: &str) -> Bookmark {
+        let request = request("https://example.com", HttpMethod::Get);
+        (&SaveBookmark::new(slug, &request).with_tags(vec!["payments".to_string()])).into()
+    }
+
+    #[rstest]
+    #[case("users/get-user", "users/", true)]
+    #[case("users/get-user", "users", true)]
+    #[case("/users/get-user", "users/get-user", true)]
+    #[case("users-admin/get-user", "users", false)]
+    #[case("users", "users/", true)]
+    fn should_be_in_a_folder(#[case] slug: &str, #[case] folder: &str, #[case] expected: bool) {
+        assert_eq!(bookmark(slug).is_in(folder), expected);
+    }
+
+    #[test]
+    fn should_have_tags() {
+        let bookmark = bookmark("users");
+
+        assert!(bookmark.has_tags(&["payments".to_string()]));
+        assert!(bookmark.has_tags(&[]));
+        assert!(!bookmark.has_tags(&["payments".to_string(), "admin".to_string()]));
+    }
+
+    #[test]
+    fn should_read_bookmarks_without_description_and_tags() {
+        let bookmark: Bookmark = serde_yaml::from_str(indoc! {r#"
+            slug: users
+            request:
+              url: https://example.com/users
+              method: GET
+              version: HTTP/1.1
+              headers: []
+              body: None
+              curl_params: []
+              placeholders: []
+        "#})
+        .unwrap();
+
+        assert_eq!(bookmark.description, None);
+        assert!(bookmark.tags.is_empty());
+    }
+}
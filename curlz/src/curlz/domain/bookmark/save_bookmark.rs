@@ -6,6 +6,8 @@ use crate::domain::http::HttpRequest;
 pub struct SaveBookmark<'a> {
     pub slug: String,
     pub bookmark: &'a HttpRequest,
+    pub description: Option<String>,
+    pub tags: Vec<String>,
 }
 
 impl<'a> SaveBookmark<'a> {
@@ -13,13 +15,29 @@ impl<'a> SaveBookmark<'a> {
         Self {
             slug: slug.as_ref().to_owned(),
             bookmark,
+            description: None,
+            tags: vec![],
         }
     }
+
+    pub fn with_description(mut self, description: Option<String>) -> Self {
+        self.description = description;
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
 }
 
+/// saves the request as bookmark, with placeholders for all variables its templates refer to
 pub fn save_bookmark(
     bm: SaveBookmark,
     collection: &mut impl BookmarkCollection,
 ) -> crate::Result<()> {
-    collection.save(&Bookmark::from(&bm))
+    let mut bookmark = Bookmark::from(&bm);
+    bookmark.request.placeholders = bm.bookmark.discover_placeholders()?;
+
+    collection.save(&bookmark)
 }
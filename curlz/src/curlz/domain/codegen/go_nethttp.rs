@@ -0,0 +1,122 @@
+//! # net/http
+//! a program of the go [net/http](https://pkg.go.dev/net/http) package, placeholders become concatenated variables
+use crate::domain::codegen::{quote, CodeBody, CodeGenerator, CodeRequest, Part, Template};
+
+use convert_case::{Case, Casing};
+use std::fmt::Write;
+
+/// the `net/http` package of go
+#[derive(Debug, Default)]
+pub struct GoNethttp;
+
+impl CodeGenerator for GoNethttp {
+    fn generate(&self, request: &CodeRequest) -> String {
+        let mut imports = vec!["fmt", "io", "net/http"];
+        match request.body.as_ref() {
+            Some(CodeBody::Text(_)) => imports.push("strings"),
+            Some(CodeBody::File(_)) => imports.push("os"),
+            None => {}
+        }
+        if request.variables.iter().any(|v| v.secret) && !imports.contains(&"os") {
+            imports.push("os");
+        }
+        imports.sort();
+
+        let mut code = String::from("package main\n\nimport (\n");
+        for import in imports {
+            let _ = writeln!(code, "\t{}", quote(import));
+        }
+        code.push_str(")\n\nfunc main() {\n");
+        for variable in request.variables.iter() {
+            if variable.secret {
+                let _ = writeln!(
+                    code,
+                    "\t{} := os.Getenv({})",
+                    name(&variable.name),
+                    quote(&variable.env_var())
+                );
+            } else {
+                let _ = writeln!(
+                    code,
+                    "\t{} := {}",
+                    name(&variable.name),
+                    quote(&variable.value)
+                );
+            }
+        }
+        if !request.variables.is_empty() {
+            code.push('\n');
+        }
+
+        let body = match request.body.as_ref() {
+            Some(CodeBody::Text(body)) => {
+                let _ = writeln!(code, "\tbody := strings.NewReader({})", string(body));
+                "body"
+            }
+            Some(CodeBody::File(path)) => {
+                let _ = writeln!(
+                    code,
+                    "\tbody, err := os.Open({})",
+                    quote(&path.display().to_string())
+                );
+                code.push_str(PANIC_ON_ERR);
+                code.push_str("\tdefer body.Close()\n");
+                "body"
+            }
+            None => "nil",
+        };
+        let method = format!("{:?}", request.method);
+        let _ = writeln!(
+            code,
+            "\treq, err := http.NewRequest(http.Method{}, {}, {})",
+            method,
+            string(&request.url),
+            body
+        );
+        code.push_str(PANIC_ON_ERR);
+        for (name, value) in request.headers.iter() {
+            let _ = writeln!(code, "\treq.Header.Add({}, {})", quote(name), string(value));
+        }
+        if let Some((user, password)) = request.basic_auth.as_ref() {
+            let _ = writeln!(
+                code,
+                "\treq.SetBasicAuth({}, {})",
+                string(user),
+                string(password)
+            );
+        }
+        if request.digest_auth.is_some() {
+            code.push_str("\t// digest authentication is not supported by net/http\n");
+        }
+        code.push('\n');
+        code.push_str("\tres, err := http.DefaultClient.Do(req)\n");
+        code.push_str(PANIC_ON_ERR);
+        code.push_str("\tdefer res.Body.Close()\n");
+        code.push_str("\tout, err := io.ReadAll(res.Body)\n");
+        code.push_str(PANIC_ON_ERR);
+        code.push_str("\tfmt.Println(string(out))\n");
+        code.push_str("}\n");
+
+        code
+    }
+}
+
+const PANIC_ON_ERR: &str = "\tif err != nil {\n\t\tpanic(err)\n\t}\n";
+
+/// the camel case name of a variable, like `baseUrl`
+fn name(variable: &str) -> String {
+    variable.to_case(Case::Camel)
+}
+
+/// a string literal, or the concatenation of the text and variables of a `template`
+fn string(template: &Template) -> String {
+    template
+        .0
+        .iter()
+        .map(|part| match part {
+            Part::Text(text) => quote(text),
+            Part::Variable(variable) => name(variable),
+        })
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
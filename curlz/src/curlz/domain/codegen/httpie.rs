@@ -0,0 +1,43 @@
+//! # HTTPie
+//! a command line of [HTTPie](https://httpie.io), placeholders become shell variables
+use crate::domain::codegen::{
+    shell_variables, shell_word, CodeBody, CodeGenerator, CodeRequest, Template,
+};
+
+/// the `http` command of HTTPie
+#[derive(Debug, Default)]
+pub struct Httpie;
+
+impl CodeGenerator for Httpie {
+    fn generate(&self, request: &CodeRequest) -> String {
+        let mut args = vec![];
+        if let Some((user, password)) = request.basic_auth.as_ref() {
+            let credentials = Template::concat(&[user, &Template::from_text(":"), password]);
+            args.push(format!("--auth={}", shell_word(&credentials)));
+        }
+        if let Some((user, password)) = request.digest_auth.as_ref() {
+            let credentials = Template::concat(&[user, &Template::from_text(":"), password]);
+            args.push("--auth-type=digest".to_string());
+            args.push(format!("--auth={}", shell_word(&credentials)));
+        }
+        if let Some(CodeBody::Text(body)) = request.body.as_ref() {
+            args.push(format!("--raw={}", shell_word(body)));
+        }
+        args.push(request.method());
+        args.push(shell_word(&request.url));
+        for (name, value) in request.headers.iter() {
+            let header = Template::concat(&[&Template::from_text(format!("{}:", name)), value]);
+            args.push(shell_word(&header));
+        }
+        if let Some(CodeBody::File(path)) = request.body.as_ref() {
+            let path = Template::from_text(path.display().to_string());
+            args.push(format!("< {}", shell_word(&path)));
+        }
+
+        format!(
+            "{}http {}\n",
+            shell_variables(request),
+            args.join(" \\\n  ")
+        )
+    }
+}
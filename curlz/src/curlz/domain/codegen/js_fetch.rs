@@ -0,0 +1,112 @@
+//! # fetch
+//! a snippet of the [fetch](https://developer.mozilla.org/docs/Web/API/fetch) api, placeholders become template literal variables
+use crate::domain::codegen::{quote, CodeBody, CodeGenerator, CodeRequest, Part, Template};
+use crate::domain::http::HttpMethod;
+
+use convert_case::{Case, Casing};
+use std::fmt::Write;
+
+/// the `fetch` api of browsers and node
+#[derive(Debug, Default)]
+pub struct JsFetch;
+
+impl CodeGenerator for JsFetch {
+    fn generate(&self, request: &CodeRequest) -> String {
+        let mut code = String::new();
+        if matches!(request.body, Some(CodeBody::File(_))) {
+            code.push_str("import { readFile } from \"node:fs/promises\";\n\n");
+        }
+        for variable in request.variables.iter() {
+            if variable.secret {
+                let _ = writeln!(
+                    code,
+                    "const {} = process.env.{};",
+                    name(&variable.name),
+                    variable.env_var()
+                );
+            } else {
+                let _ = writeln!(
+                    code,
+                    "const {} = {};",
+                    name(&variable.name),
+                    quote(&variable.value)
+                );
+            }
+        }
+        if !request.variables.is_empty() {
+            code.push('\n');
+        }
+        if request.digest_auth.is_some() {
+            code.push_str("// digest authentication is not supported by fetch\n");
+        }
+
+        let url = string(&request.url);
+        let has_headers = !request.headers.is_empty() || request.basic_auth.is_some();
+        if request.method == HttpMethod::Get && !has_headers && request.body.is_none() {
+            let _ = writeln!(code, "const response = await fetch({});", url);
+        } else {
+            let _ = writeln!(code, "const response = await fetch({}, {{", url);
+            if request.method != HttpMethod::Get {
+                let _ = writeln!(code, "  method: {},", quote(&request.method()));
+            }
+            if has_headers {
+                code.push_str("  headers: {\n");
+                for (name, value) in request.headers.iter() {
+                    let _ = writeln!(code, "    {}: {},", quote(name), string(value));
+                }
+                if let Some((user, password)) = request.basic_auth.as_ref() {
+                    let credentials =
+                        Template::concat(&[user, &Template::from_text(":"), password]);
+                    let _ = writeln!(
+                        code,
+                        "    \"Authorization\": \"Basic \" + btoa({}),",
+                        string(&credentials)
+                    );
+                }
+                code.push_str("  },\n");
+            }
+            match request.body.as_ref() {
+                Some(CodeBody::Text(body)) => {
+                    let _ = writeln!(code, "  body: {},", string(body));
+                }
+                Some(CodeBody::File(path)) => {
+                    let _ = writeln!(
+                        code,
+                        "  body: await readFile({}),",
+                        quote(&path.display().to_string())
+                    );
+                }
+                None => {}
+            }
+            code.push_str("});\n");
+        }
+        code.push_str("console.log(await response.text());\n");
+
+        code
+    }
+}
+
+/// the camel case name of a variable, like `baseUrl`
+fn name(variable: &str) -> String {
+    variable.to_case(Case::Camel)
+}
+
+/// a string literal, or a template literal of the variables of a `template`
+fn string(template: &Template) -> String {
+    if template.is_text() {
+        return quote(&template.texts().collect::<String>());
+    }
+
+    let text = template
+        .0
+        .iter()
+        .map(|part| match part {
+            Part::Text(text) => text
+                .replace('\\', "\\\\")
+                .replace('`', "\\`")
+                .replace("${", "\\${"),
+            Part::Variable(variable) => format!("${{{}}}", name(variable)),
+        })
+        .collect::<String>();
+    format!("`{}`", text)
+}
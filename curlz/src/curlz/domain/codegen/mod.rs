@@ -0,0 +1,423 @@
+//! generates code snippets of a request for other http clients, like `reqwest` or `fetch`
+//!
+//! each client implements [`CodeGenerator`], simple placeholders like `{{ base_url }}` become variables of the code
+mod go_nethttp;
+mod httpie;
+mod js_fetch;
+mod python_requests;
+mod rust_reqwest;
+mod wget;
+
+pub use go_nethttp::*;
+pub use httpie::*;
+pub use js_fetch::*;
+pub use python_requests::*;
+pub use rust_reqwest::*;
+pub use wget::*;
+
+use crate::domain::http::{HttpAuth, HttpBody, HttpMethod, HttpRequest};
+use crate::domain::import::parse_basic_auth;
+use crate::template::variables::{Placeholder, PlaceholderType};
+
+use anyhow::bail;
+use log::warn;
+use regex::Regex;
+use std::path::PathBuf;
+
+/// a http client, like `reqwest`, that code is generated for
+pub trait CodeGenerator {
+    /// the code snippet of a `request`
+    fn generate(&self, request: &CodeRequest) -> String;
+}
+
+/// generates the code of a `request` for a http client,
+/// the parts of the request that cannot be expressed, like curl arguments, are left out with a warning
+///
+/// ## Errors
+/// in case of templates that are more than variables, like `{{ uuid() }}`, they need to be rendered first
+pub fn codegen(request: &HttpRequest, generator: &dyn CodeGenerator) -> crate::Result<String> {
+    let code = CodeRequest::new(request);
+    let expressions = code.expressions();
+    if !expressions.is_empty() {
+        bail!(
+            "the templates `{}` cannot be turned into code, render them with `--render`",
+            expressions.join("`, `")
+        );
+    }
+
+    Ok(generator.generate(&code))
+}
+
+/// a request, its templates split into text and variables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeRequest {
+    pub method: HttpMethod,
+    pub url: Template,
+    pub headers: Vec<(String, Template)>,
+    pub body: Option<CodeBody>,
+    /// the basic auth credentials of an `Authorization: Basic {{ basic(..) }}` header, as `(user, password)`
+    pub basic_auth: Option<(Template, Template)>,
+    /// the digest credentials, as `(user, password)`
+    pub digest_auth: Option<(Template, Template)>,
+    /// the variables of all templates, in the order they are used
+    pub variables: Vec<Variable>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeBody {
+    Text(Template),
+    File(PathBuf),
+}
+
+/// a variable of the code, like `base_url`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Variable {
+    pub name: String,
+    /// the value or default of its placeholder
+    pub value: String,
+    /// secrets are read from the env var of the uppercase name, like `API_TOKEN`
+    pub secret: bool,
+}
+
+impl Variable {
+    /// the env var a secret is read from, like `API_TOKEN`
+    pub fn env_var(&self) -> String {
+        self.name.to_uppercase()
+    }
+}
+
+/// a text with placeholders
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template(pub Vec<Part>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Part {
+    Text(String),
+    Variable(String),
+}
+
+impl Template {
+    /// splits `text` into text and the variables of simple placeholders like `{{ name }}`,
+    /// other templates, like `{{ uuid() }}`, are kept as text, see [`CodeRequest::expressions`]
+    pub fn parse(text: &str) -> Self {
+        let variable = Regex::new(r"\{\{\s*([A-Za-z_][A-Za-z0-9_]*)\s*\}\}").unwrap();
+        let mut parts = vec![];
+        let mut end = 0;
+        for captures in variable.captures_iter(text) {
+            let matched = captures.get(0).unwrap();
+            if matched.start() > end {
+                parts.push(Part::Text(text[end..matched.start()].to_string()));
+            }
+            parts.push(Part::Variable(captures[1].to_string()));
+            end = matched.end();
+        }
+        if end < text.len() {
+            parts.push(Part::Text(text[end..].to_string()));
+        }
+
+        Self(parts)
+    }
+
+    /// a template of a `text` without variables
+    pub fn from_text(text: impl Into<String>) -> Self {
+        Self(vec![Part::Text(text.into())])
+    }
+
+    /// the parts of all `templates`, one after the other
+    pub fn concat(templates: &[&Template]) -> Self {
+        Self(templates.iter().flat_map(|t| t.0.iter().cloned()).collect())
+    }
+
+    /// whether the template has no variables
+    pub fn is_text(&self) -> bool {
+        self.0.iter().all(|part| matches!(part, Part::Text(_)))
+    }
+
+    fn texts(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter_map(|part| match part {
+            Part::Text(text) => Some(text.as_str()),
+            Part::Variable(_) => None,
+        })
+    }
+
+    fn variables(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().filter_map(|part| match part {
+            Part::Variable(name) => Some(name.as_str()),
+            Part::Text(_) => None,
+        })
+    }
+}
+
+impl CodeRequest {
+    pub fn new(request: &HttpRequest) -> Self {
+        if !request.curl_params.is_empty() {
+            warn!(
+                "the curl arguments `{}` cannot be turned into code and are left out",
+                request.curl_params.join(" ")
+            );
+        }
+        let body = match &request.body {
+            HttpBody::InlineText(text) => Some(CodeBody::Text(Template::parse(text))),
+            HttpBody::Extern(path) => Some(CodeBody::File(path.clone())),
+            HttpBody::InlineBinary(_) => {
+                warn!("a binary body cannot be turned into code and is left out");
+                None
+            }
+            HttpBody::None => None,
+        };
+        let mut basic_auth = None;
+        let mut headers = vec![];
+        for (name, value) in request.headers.as_ref() {
+            match parse_basic_auth(value) {
+                Some((user, password)) if name.eq_ignore_ascii_case("Authorization") => {
+                    basic_auth = Some((Template::parse(&user), Template::parse(&password)))
+                }
+                _ => headers.push((name.clone(), Template::parse(value))),
+            }
+        }
+        let mut code = Self {
+            method: request.method.clone(),
+            url: Template::parse(request.url.as_ref()),
+            headers,
+            body,
+            basic_auth,
+            digest_auth: request
+                .auth
+                .as_ref()
+                .map(|HttpAuth::Digest { user, password }| {
+                    (Template::parse(user), Template::parse(password))
+                }),
+            variables: vec![],
+        };
+        code.variables = code.find_variables(&request.placeholders);
+
+        code
+    }
+
+    /// all templates of the request
+    fn templates(&self) -> Vec<&Template> {
+        let mut templates = vec![&self.url];
+        templates.extend(self.headers.iter().map(|(_, value)| value));
+        if let Some((user, password)) = self.basic_auth.as_ref() {
+            templates.extend([user, password]);
+        }
+        if let Some(CodeBody::Text(body)) = self.body.as_ref() {
+            templates.push(body);
+        }
+        if let Some((user, password)) = self.digest_auth.as_ref() {
+            templates.extend([user, password]);
+        }
+
+        templates
+    }
+
+    /// the templates that are more than variables and are kept as text, like `{{ uuid() }}`
+    pub fn expressions(&self) -> Vec<String> {
+        let expression = Regex::new(r"\{\{.*?\}\}|\{%.*?%\}").unwrap();
+        let mut expressions: Vec<String> = vec![];
+        for text in self.templates().into_iter().flat_map(|t| t.texts()) {
+            for matched in expression.find_iter(text) {
+                if !expressions.iter().any(|e| e == matched.as_str()) {
+                    expressions.push(matched.as_str().to_string());
+                }
+            }
+        }
+
+        expressions
+    }
+
+    fn find_variables(&self, placeholders: &[Placeholder]) -> Vec<Variable> {
+        let templates = self.templates();
+
+        let mut variables: Vec<Variable> = vec![];
+        for name in templates.into_iter().flat_map(|t| t.variables()) {
+            if variables.iter().any(|v| v.name == name) {
+                continue;
+            }
+            let placeholder = placeholders.iter().find(|p| p.name == name);
+            variables.push(Variable {
+                name: name.to_string(),
+                value: placeholder
+                    .and_then(|p| p.value.as_ref().or(p.default.as_ref()))
+                    .cloned()
+                    .unwrap_or_default(),
+                secret: placeholder.map(|p| p.kind) == Some(PlaceholderType::Secret),
+            });
+        }
+
+        variables
+    }
+
+    /// the method in uppercase, like `POST`
+    pub fn method(&self) -> String {
+        String::from(&self.method)
+    }
+}
+
+/// escapes `text` for a double quoted string literal, like of rust, python, js or go
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// a double quoted string literal of `text`, like `"users"`
+fn quote(text: &str) -> String {
+    format!("\"{}\"", escape(text))
+}
+
+/// a shell word of a `template`, single quoted, or double quoted when it refers to variables like `"${base_url}/users"`
+fn shell_word(template: &Template) -> String {
+    if template.is_text() {
+        let text = template.texts().collect::<String>();
+        return format!("'{}'", text.replace('\'', "'\\''"));
+    }
+
+    let text = template
+        .0
+        .iter()
+        .map(|part| match part {
+            Part::Text(text) => text
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('$', "\\$")
+                .replace('`', "\\`"),
+            Part::Variable(name) => format!("${{{}}}", name),
+        })
+        .collect::<String>();
+    format!("\"{}\"", text)
+}
+
+/// the shell variables of a `request`, secrets are taken from the environment like `api_token="${API_TOKEN}"`
+fn shell_variables(request: &CodeRequest) -> String {
+    let mut code = String::new();
+    for variable in request.variables.iter() {
+        let value = if variable.secret {
+            format!("\"${{{}}}\"", variable.env_var())
+        } else {
+            shell_word(&Template::from_text(variable.value.as_str()))
+        };
+        code.push_str(&format!("{}={}\n", variable.name, value));
+    }
+    if !request.variables.is_empty() {
+        code.push('\n');
+    }
+
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::http::HttpHeaders;
+    use crate::test_utils;
+    use indoc::indoc;
+    use rstest::rstest;
+
+    /// a request with a variable url, basic auth of a secret and a json body
+    pub(super) fn request() -> HttpRequest {
+        let mut headers = HttpHeaders::default();
+        headers.push("Authorization", r#"Basic {{ basic("john", api_token) }}"#);
+        headers.push("Content-Type", "application/json");
+
+        HttpRequest {
+            headers,
+            body: HttpBody::InlineText(
+                indoc! {r#"
+                {"name": "{{ name }}", "id": 42}"#}
+                .to_string(),
+            ),
+            placeholders: vec![
+                Placeholder {
+                    name: "base_url".to_string(),
+                    default: Some("https://api.example.com".to_string()),
+                    ..Default::default()
+                },
+                Placeholder {
+                    name: "api_token".to_string(),
+                    kind: PlaceholderType::Secret,
+                    ..Default::default()
+                },
+                Placeholder {
+                    name: "name".to_string(),
+                    default: Some("john".to_string()),
+                    ..Default::default()
+                },
+            ],
+            ..test_utils::request("{{ base_url }}/users", HttpMethod::Post)
+        }
+    }
+
+    #[test]
+    fn should_split_templates_into_text_and_variables() {
+        assert_eq!(
+            Template::parse("{{base_url}}/users/{{ id }}?x={{ uuid() }}"),
+            Template(vec![
+                Part::Variable("base_url".to_string()),
+                Part::Text("/users/".to_string()),
+                Part::Variable("id".to_string()),
+                Part::Text("?x={{ uuid() }}".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn should_refuse_templates_that_are_more_than_variables() {
+        let mut request = request();
+        request.url = "{{ base_url }}/users/{{ uuid() }}".into();
+
+        assert_eq!(
+            codegen(&request, &Httpie).unwrap_err().to_string(),
+            "the templates `{{ uuid() }}` cannot be turned into code, render them with `--render`"
+        );
+    }
+
+    #[test]
+    fn should_find_the_variables_in_order_of_use() {
+        let variables = CodeRequest::new(&request()).variables;
+
+        assert_eq!(
+            variables,
+            [
+                Variable {
+                    name: "base_url".to_string(),
+                    value: "https://api.example.com".to_string(),
+                    secret: false,
+                },
+                Variable {
+                    name: "api_token".to_string(),
+                    value: String::new(),
+                    secret: true,
+                },
+                Variable {
+                    name: "name".to_string(),
+                    value: "john".to_string(),
+                    secret: false,
+                },
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("rust_reqwest", &RustReqwest as &dyn CodeGenerator)]
+    #[case("python_requests", &PythonRequests)]
+    #[case("js_fetch", &JsFetch)]
+    #[case("go_nethttp", &GoNethttp)]
+    #[case("httpie", &Httpie)]
+    #[case("wget", &Wget)]
+    fn should_generate_code(#[case] name: &str, #[case] generator: &dyn CodeGenerator) {
+        let code = generator.generate(&CodeRequest::new(&request()));
+
+        insta::assert_snapshot!(name, code);
+    }
+}
@@ -0,0 +1,107 @@
+//! # requests
+//! a snippet of the python [requests](https://requests.readthedocs.io) library, placeholders become f-string variables
+use crate::domain::codegen::{escape, quote, CodeBody, CodeGenerator, CodeRequest, Part, Template};
+use crate::domain::http::HttpMethod;
+
+use std::fmt::Write;
+
+/// the python `requests` library
+#[derive(Debug, Default)]
+pub struct PythonRequests;
+
+impl CodeGenerator for PythonRequests {
+    fn generate(&self, request: &CodeRequest) -> String {
+        let mut code = String::new();
+        if request.variables.iter().any(|v| v.secret) {
+            code.push_str("import os\n\n");
+        }
+        code.push_str("import requests\n");
+        if request.digest_auth.is_some() {
+            code.push_str("from requests.auth import HTTPDigestAuth\n");
+        }
+        code.push('\n');
+        for variable in request.variables.iter() {
+            if variable.secret {
+                let _ = writeln!(
+                    code,
+                    "{} = os.environ[{}]",
+                    variable.name,
+                    quote(&variable.env_var())
+                );
+            } else {
+                let _ = writeln!(code, "{} = {}", variable.name, quote(&variable.value));
+            }
+        }
+        if !request.variables.is_empty() {
+            code.push('\n');
+        }
+
+        let url = string(&request.url);
+        let _ = match request.method {
+            HttpMethod::Trace | HttpMethod::Connect => writeln!(
+                code,
+                "response = requests.request(\n    {},\n    {},",
+                quote(&request.method()),
+                url
+            ),
+            _ => writeln!(
+                code,
+                "response = requests.{}(\n    {},",
+                request.method().to_lowercase(),
+                url
+            ),
+        };
+        if !request.headers.is_empty() {
+            code.push_str("    headers={\n");
+            for (name, value) in request.headers.iter() {
+                let _ = writeln!(code, "        {}: {},", quote(name), string(value));
+            }
+            code.push_str("    },\n");
+        }
+        match request.body.as_ref() {
+            Some(CodeBody::Text(body)) => {
+                let _ = writeln!(code, "    data={},", string(body));
+            }
+            Some(CodeBody::File(path)) => {
+                let _ = writeln!(
+                    code,
+                    "    data=open({}, \"rb\"),",
+                    quote(&path.display().to_string())
+                );
+            }
+            None => {}
+        }
+        if let Some((user, password)) = request.basic_auth.as_ref() {
+            let _ = writeln!(code, "    auth=({}, {}),", string(user), string(password));
+        }
+        if let Some((user, password)) = request.digest_auth.as_ref() {
+            let _ = writeln!(
+                code,
+                "    auth=HTTPDigestAuth({}, {}),",
+                string(user),
+                string(password)
+            );
+        }
+        code.push_str(")\n");
+        code.push_str("print(response.text)\n");
+
+        code
+    }
+}
+
+/// a string literal, or an f-string of the variables of a `template`
+fn string(template: &Template) -> String {
+    if template.is_text() {
+        return quote(&template.texts().collect::<String>());
+    }
+
+    let text = template
+        .0
+        .iter()
+        .map(|part| match part {
+            Part::Text(text) => escape(text).replace('{', "{{").replace('}', "}}"),
+            Part::Variable(name) => format!("{{{}}}", name),
+        })
+        .collect::<String>();
+    format!("f\"{}\"", text)
+}
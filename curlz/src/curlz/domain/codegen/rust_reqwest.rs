@@ -0,0 +1,114 @@
+//! # reqwest
+//! a snippet of the blocking [reqwest](https://docs.rs/reqwest) client, placeholders become `format!` arguments
+use crate::domain::codegen::{quote, CodeBody, CodeGenerator, CodeRequest, Part, Template};
+use crate::domain::http::HttpMethod;
+
+use std::fmt::Write;
+
+/// the blocking client of `reqwest`
+#[derive(Debug, Default)]
+pub struct RustReqwest;
+
+impl CodeGenerator for RustReqwest {
+    fn generate(&self, request: &CodeRequest) -> String {
+        let mut code = String::new();
+        for variable in request.variables.iter() {
+            if variable.secret {
+                let _ = writeln!(
+                    code,
+                    "let {} = std::env::var({})?;",
+                    variable.name,
+                    quote(&variable.env_var())
+                );
+            } else {
+                let _ = writeln!(code, "let {} = {};", variable.name, quote(&variable.value));
+            }
+        }
+        if !request.variables.is_empty() {
+            code.push('\n');
+        }
+        if request.digest_auth.is_some() {
+            code.push_str("// digest authentication is not supported by reqwest\n");
+        }
+
+        code.push_str("let client = reqwest::blocking::Client::new();\n");
+        code.push_str("let response = client\n");
+        let url = string(&request.url);
+        let _ = match request.method {
+            HttpMethod::Get
+            | HttpMethod::Post
+            | HttpMethod::Put
+            | HttpMethod::Delete
+            | HttpMethod::Head
+            | HttpMethod::Patch => {
+                writeln!(code, "    .{}({})", request.method().to_lowercase(), url)
+            }
+            _ => writeln!(
+                code,
+                "    .request(reqwest::Method::{}, {})",
+                request.method(),
+                url
+            ),
+        };
+        for (name, value) in request.headers.iter() {
+            let _ = writeln!(code, "    .header({}, {})", quote(name), string(value));
+        }
+        if let Some((user, password)) = request.basic_auth.as_ref() {
+            let _ = writeln!(
+                code,
+                "    .basic_auth({}, Some({}))",
+                string(user),
+                string(password)
+            );
+        }
+        match request.body.as_ref() {
+            Some(CodeBody::Text(body)) => {
+                let _ = writeln!(code, "    .body({})", string(body));
+            }
+            Some(CodeBody::File(path)) => {
+                let _ = writeln!(
+                    code,
+                    "    .body(std::fs::read({})?)",
+                    quote(&path.display().to_string())
+                );
+            }
+            None => {}
+        }
+        code.push_str("    .send()?;\n");
+        code.push_str("println!(\"{}\", response.text()?);\n");
+
+        code
+    }
+}
+
+/// a string literal, or a `format!` of the variables of a `template`
+fn string(template: &Template) -> String {
+    let text = template
+        .0
+        .iter()
+        .map(|part| match part {
+            Part::Text(text) if template.is_text() => text.clone(),
+            Part::Text(text) => text.replace('{', "{{").replace('}', "}}"),
+            Part::Variable(name) => format!("{{{}}}", name),
+        })
+        .collect::<String>();
+    let literal = if text.contains('"') || text.contains('\n') {
+        let hashes = "#".repeat(hashes_needed(&text));
+        format!("r{}\"{}\"{}", hashes, text, hashes)
+    } else {
+        quote(&text)
+    };
+
+    if template.is_text() {
+        literal
+    } else {
+        format!("format!({})", literal)
+    }
+}
+
+/// the number of `#` a raw string of `text` needs, like 1 for `{"a": 1}`
+fn hashes_needed(text: &str) -> usize {
+    (1..)
+        .find(|n| !text.contains(&format!("\"{}", "#".repeat(*n))))
+        .unwrap_or(1)
+}
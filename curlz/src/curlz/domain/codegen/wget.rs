@@ -0,0 +1,48 @@
+//! # wget
+//! a command line of [wget](https://www.gnu.org/software/wget), placeholders become shell variables
+use crate::domain::codegen::{
+    shell_variables, shell_word, CodeBody, CodeGenerator, CodeRequest, Template,
+};
+use crate::domain::http::HttpMethod;
+
+/// the `wget` command, the response is printed to stdout
+#[derive(Debug, Default)]
+pub struct Wget;
+
+impl CodeGenerator for Wget {
+    fn generate(&self, request: &CodeRequest) -> String {
+        let mut args = vec!["--quiet".to_string(), "--output-document=-".to_string()];
+        if request.method != HttpMethod::Get {
+            args.push(format!("--method={}", request.method()));
+        }
+        for (name, value) in request.headers.iter() {
+            let header = Template::concat(&[&Template::from_text(format!("{}: ", name)), value]);
+            args.push(format!("--header={}", shell_word(&header)));
+        }
+        match request.body.as_ref() {
+            Some(CodeBody::Text(body)) => args.push(format!("--body-data={}", shell_word(body))),
+            Some(CodeBody::File(path)) => {
+                let path = Template::from_text(path.display().to_string());
+                args.push(format!("--body-file={}", shell_word(&path)));
+            }
+            None => {}
+        }
+        if let Some((user, password)) = request.basic_auth.as_ref() {
+            // without it, wget waits for a challenge of the server
+            args.push("--auth-no-challenge".to_string());
+            args.push(format!("--user={}", shell_word(user)));
+            args.push(format!("--password={}", shell_word(password)));
+        }
+        if let Some((user, password)) = request.digest_auth.as_ref() {
+            args.push(format!("--user={}", shell_word(user)));
+            args.push(format!("--password={}", shell_word(password)));
+        }
+        args.push(shell_word(&request.url));
+
+        format!(
+            "{}wget {}\n",
+            shell_variables(request),
+            args.join(" \\\n  ")
+        )
+    }
+}
@@ -0,0 +1,62 @@
+//! the project config in `.curlz/config.yml`
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// the file name of the project config within the workspace folder
+pub const CONFIG_FILE: &str = "config.yml";
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ProjectConfig {
+    #[serde(default)]
+    pub exec: ExecConfig,
+}
+
+/// the config of the `exec()` template function
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ExecConfig {
+    /// the commands templates are allowed to run, like `vault` or `gcloud`
+    #[serde(default)]
+    pub allow: Vec<String>,
+}
+
+impl ProjectConfig {
+    /// loads the config from the yaml `file`, a missing file is the default config
+    pub fn load(file: impl AsRef<Path>) -> crate::Result<Self> {
+        let file = file.as_ref();
+        if !file.is_file() {
+            return Ok(Self::default());
+        }
+
+        let config = std::fs::read_to_string(file)?;
+        serde_yaml::from_str(&config)
+            .with_context(|| format!("invalid project config `{}`", file.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_file;
+
+    #[test]
+    fn should_load_the_allowed_commands() {
+        let tmp = create_file(CONFIG_FILE, "exec:\n  allow:\n    - vault\n    - gcloud\n").unwrap();
+
+        assert_eq!(
+            ProjectConfig::load(tmp.path().join(CONFIG_FILE))
+                .unwrap()
+                .exec
+                .allow,
+            ["vault", "gcloud"]
+        );
+    }
+
+    #[test]
+    fn should_load_the_default_config_without_a_file() {
+        assert_eq!(
+            ProjectConfig::load("not/existing/config.yml").unwrap(),
+            ProjectConfig::default()
+        );
+    }
+}
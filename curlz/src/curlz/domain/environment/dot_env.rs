@@ -1,4 +1,5 @@
 use crate::domain::environment::Environment;
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 /// wraps a `.env` file, used to create an [`Environment`]
@@ -20,13 +21,61 @@ impl TryFrom<DotEnvFile> for Environment {
             .map_err(anyhow::Error::from)?
             .map(|i| i.unwrap())
             .for_each(|(key, value)| {
-                env.as_mut().insert(key, value);
+                env.insert(key, value);
             });
 
         Ok(env)
     }
 }
 
+impl DotEnvFile {
+    /// adds the `variables` to the `.env` file, existing variables of the same name are replaced
+    pub fn merge(&self, variables: &BTreeMap<String, String>) -> crate::Result<()> {
+        let existing = if self.0.is_file() {
+            std::fs::read_to_string(self.0.as_path())?
+        } else {
+            String::new()
+        };
+        let mut replaced = vec![];
+        let mut lines = existing
+            .lines()
+            .map(
+                |line| match variable_of_line(line).and_then(|key| variables.get_key_value(key)) {
+                    Some((key, value)) => {
+                        replaced.push(key);
+                        format!("{}={}", key, quote(value))
+                    }
+                    None => line.to_string(),
+                },
+            )
+            .collect::<Vec<_>>();
+        lines.extend(
+            variables
+                .iter()
+                .filter(|(key, _)| !replaced.contains(key))
+                .map(|(key, value)| format!("{}={}", key, quote(value))),
+        );
+        if let Some(dir) = self.0.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        std::fs::write(self.0.as_path(), lines.join("\n") + "\n").map_err(|e| e.into())
+    }
+}
+
+/// the name of the variable a line defines, like `host` for `export host=localhost`
+fn variable_of_line(line: &str) -> Option<&str> {
+    let (key, _) = line.split_once('=')?;
+    let key = key.trim();
+    let key = key.strip_prefix("export ").unwrap_or(key).trim();
+    (!key.starts_with('#')).then_some(key)
+}
+
+/// quotes a value in single quotes, so templates and `$` are kept as they are
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''").replace('\n', r"'\n'"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,4 +101,31 @@ mod tests {
         );
         assert_eq!(env.get("email").unwrap().as_ref(), "some@user.com");
     }
+
+    #[test]
+    fn should_merge_variables_into_a_dot_env_file() {
+        let tmp = create_file(".env", "# local\nport=8080\nhost=localhost\n").unwrap();
+        let file = DotEnvFile(tmp.path().join(".env"));
+        file.merge(&BTreeMap::from([
+            ("host".to_string(), "example.com".to_string()),
+            ("token".to_string(), "{{ uuid() }}".to_string()),
+            ("note".to_string(), "it's $HOME\nsecond line".to_string()),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join(".env")).unwrap(),
+            indoc! { r#"
+                # local
+                port=8080
+                host='example.com'
+                note='it'\''s $HOME'\n'second line'
+                token='{{ uuid() }}'
+            "#}
+        );
+        let env = Environment::try_from(DotEnvFile(tmp.path().join(".env"))).unwrap();
+        assert_eq!(env.get("host").unwrap().as_ref(), "example.com");
+        assert_eq!(env.get("token").unwrap().as_ref(), "{{ uuid() }}");
+        assert_eq!(env.get("note").unwrap().as_ref(), "it's $HOME\nsecond line");
+    }
 }
@@ -1,29 +1,42 @@
 use crate::template::variables::Placeholder;
-use std::collections::HashMap;
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
 use std::ffi::OsStr;
 use std::path::Path;
 
 use super::dot_env::DotEnvFile;
 use super::yaml_env::YamlEnvFile;
 
+/// the template variables, values can be structured like lists and maps e.g. from a yaml file
 #[derive(Default, Debug)]
-pub struct Environment(pub(crate) HashMap<String, String>);
+pub struct Environment(pub(crate) HashMap<String, Value>);
 
 impl Environment {
-    /// returns the value for a given key
+    /// returns the value for a given key, structured values are returned as json
     pub fn get(&'_ self, key: impl AsRef<str>) -> Option<impl AsRef<str> + '_> {
+        self.0.get(key.as_ref()).map(|value| match value {
+            Value::String(s) => Cow::Borrowed(s.as_str()),
+            other => Cow::Owned(other.to_string()),
+        })
+    }
+
+    /// returns the possibly structured value for a given key
+    pub fn get_value(&self, key: impl AsRef<str>) -> Option<&Value> {
         self.0.get(key.as_ref())
     }
 
     /// inserts a key with it's value, copies the data
     pub fn insert(&mut self, key: impl AsRef<str>, value: impl AsRef<str>) {
-        self.0
-            .insert(key.as_ref().to_string(), value.as_ref().to_string());
+        self.0.insert(
+            key.as_ref().to_string(),
+            Value::String(value.as_ref().to_string()),
+        );
     }
 }
 
-impl AsMut<HashMap<String, String>> for Environment {
-    fn as_mut(&mut self) -> &mut HashMap<String, String> {
+impl AsMut<HashMap<String, Value>> for Environment {
+    fn as_mut(&mut self) -> &mut HashMap<String, Value> {
         &mut self.0
     }
 }
@@ -46,6 +59,17 @@ impl TryFrom<&Path> for Environment {
     }
 }
 
+/// merges the `variables` into a `.env` or a yaml `env_file`, like an [`Environment`] is loaded from it
+pub fn merge_into_env_file(
+    env_file: &Path,
+    variables: &BTreeMap<String, String>,
+) -> crate::Result<()> {
+    match env_file.extension().and_then(OsStr::to_str) {
+        Some("yml" | "yaml") => YamlEnvFile::from(env_file).merge(variables),
+        _ => DotEnvFile::from(env_file).merge(variables),
+    }
+}
+
 impl From<&Environment> for minijinja::value::Value {
     fn from(env: &Environment) -> Self {
         minijinja::value::Value::from_serializable(&env.0)
@@ -58,6 +82,8 @@ impl From<&Environment> for minijinja::value::Value {
 /// ## Fallible
 /// If `env_file` is not a `.env` | `.yaml` | `.yml` file, an error is returned.
 /// If `env_file` is a directory, an error is returned.
+///
+/// Placeholders without value and default are left out, they are prompted for when rendering.
 pub fn create_environment(
     env_file: impl AsRef<Path>,
     placeholders: &[Placeholder],
@@ -65,14 +91,17 @@ pub fn create_environment(
     Environment::try_from(env_file.as_ref()).map(|mut env| {
         placeholders
             .iter()
-            .map(|placeholder| {
+            .filter_map(|placeholder| {
                 let Placeholder {
                     name,
                     value,
                     default,
                     ..
                 } = placeholder;
-                (name, value.as_ref().or(default.as_ref()).unwrap())
+                value
+                    .as_ref()
+                    .or(default.as_ref())
+                    .map(|value| (name, value))
             })
             .for_each(|(k, v)| env.insert(k, v));
         env
@@ -96,6 +125,27 @@ mod tests {
         Environment::try_from(tmp.path().join(".staging.yml").as_path()).unwrap();
     }
 
+    #[test]
+    fn should_leave_out_placeholders_without_value() {
+        let placeholders = [
+            Placeholder::new("id", "42"),
+            Placeholder {
+                name: "region".to_string(),
+                default: Some("eu".to_string()),
+                ..Default::default()
+            },
+            Placeholder {
+                name: "email".to_string(),
+                ..Default::default()
+            },
+        ];
+        let env = create_environment("foo.bar.yml", &placeholders).unwrap();
+
+        assert_eq!(env.get("id").unwrap().as_ref(), "42");
+        assert_eq!(env.get("region").unwrap().as_ref(), "eu");
+        assert!(env.get("email").is_none());
+    }
+
     #[test]
     fn should_gracefully_ignore_not_existing_files() {
         Environment::try_from(Path::new("foo.bar.yml")).unwrap();
@@ -1,5 +1,6 @@
 use super::env::Environment;
 
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::path::{Path, PathBuf};
 
@@ -24,6 +25,25 @@ impl TryFrom<YamlEnvFile> for Environment {
     }
 }
 
+impl YamlEnvFile {
+    /// adds the `variables` to the yaml file, existing variables of the same name are replaced
+    pub fn merge(&self, variables: &BTreeMap<String, String>) -> crate::Result<()> {
+        let mut env: serde_yaml::Mapping = if self.0.is_file() {
+            serde_yaml::from_reader(File::open(self.0.as_path())?)?
+        } else {
+            Default::default()
+        };
+        for (key, value) in variables {
+            env.insert(key.as_str().into(), value.as_str().into());
+        }
+        if let Some(dir) = self.0.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        std::fs::write(self.0.as_path(), serde_yaml::to_string(&env)?).map_err(|e| e.into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -49,4 +69,46 @@ mod tests {
         );
         assert_eq!(env.get("email").unwrap().as_ref(), "some@user.com");
     }
+
+    #[test]
+    fn should_keep_structured_values() {
+        let tmp = create_file(
+            ".staging.yml",
+            indoc! { r#"
+                port: 8080
+                payload:
+                  name: curlz
+                  tags: [cli, http]
+            "#},
+        )
+        .unwrap();
+        let dot_file = YamlEnvFile(tmp.path().join(".staging.yml"));
+
+        let env = Environment::try_from(dot_file).unwrap();
+        assert_eq!(env.get("port").unwrap().as_ref(), "8080");
+        assert_eq!(
+            env.get("payload").unwrap().as_ref(),
+            r#"{"name":"curlz","tags":["cli","http"]}"#
+        );
+        assert_eq!(
+            env.get_value("payload").unwrap()["tags"][1].as_str(),
+            Some("http")
+        );
+    }
+
+    #[test]
+    fn should_merge_variables_into_a_yaml_file() {
+        let tmp = create_file(".env.yml", "port: 8080\nhost: localhost\n").unwrap();
+        let file = YamlEnvFile(tmp.path().join(".env.yml"));
+        file.merge(&BTreeMap::from([
+            ("host".to_string(), "example.com".to_string()),
+            ("token".to_string(), "abc".to_string()),
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(tmp.path().join(".env.yml")).unwrap(),
+            "port: 8080\nhost: example.com\ntoken: abc\n"
+        );
+    }
 }
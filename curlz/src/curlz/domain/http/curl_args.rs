@@ -0,0 +1,685 @@
+//! # Curl Arguments
+//! parses curl command line arguments, like `-X POST -H 'Accept: */*' -d @body.json https://example.com`,
+//! into the parts of a [`HttpRequest`](crate::domain::http::HttpRequest)
+use crate::domain::http::{HttpBody, HttpHeaders, HttpMethod, HttpRequest, HttpUri, HttpVersion};
+use crate::domain::import::{basic_auth, literal};
+use crate::utils::percent_encode;
+
+use anyhow::{anyhow, bail, Context};
+use std::collections::VecDeque;
+use std::path::{Component, Path};
+use std::str::FromStr;
+
+/// the parts of a request given as curl arguments
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct CurlArgs {
+    /// the method of `-X`, or the method implied by `-I`, `-G`, `-F` or `-d`
+    pub method: Option<HttpMethod>,
+    pub url: Option<HttpUri>,
+    pub headers: HttpHeaders,
+    pub body: HttpBody,
+    /// the credentials of `-u user:password`
+    pub user: Option<String>,
+    /// all arguments that are unknown to curlz, they are passed on to curl as they are
+    pub curl_params: Vec<String>,
+}
+
+impl TryFrom<CurlArgs> for HttpRequest {
+    type Error = anyhow::Error;
+
+    /// a request of the curl arguments, `-u user:password` becomes a basic `Authorization` header
+    fn try_from(args: CurlArgs) -> Result<Self, Self::Error> {
+        let CurlArgs {
+            method,
+            url,
+            mut headers,
+            body,
+            user,
+            curl_params,
+        } = args;
+        if let Some(user) = user {
+            let value = match user.split_once(':') {
+                Some((user, password)) => basic_auth(user, password),
+                None => basic_auth(&user, "{{ prompt_password() }}"),
+            };
+            headers.push("Authorization", value);
+        }
+
+        Ok(HttpRequest {
+            url: url.context("the curl arguments contain no url")?,
+            method: method.unwrap_or(HttpMethod::Get),
+            version: HttpVersion::Http11,
+            headers,
+            body,
+            curl_params,
+            placeholders: vec![],
+            auth: None,
+        })
+    }
+}
+
+/// the curl options curlz understands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Opt {
+    Request,
+    Header,
+    Data,
+    DataBinary,
+    DataRaw,
+    DataUrlencode,
+    Json,
+    Form,
+    Get,
+    Head,
+    User,
+    Cookie,
+    UserAgent,
+    Referer,
+    Url,
+}
+
+impl Opt {
+    fn short(c: char) -> Option<Self> {
+        Some(match c {
+            'X' => Self::Request,
+            'H' => Self::Header,
+            'd' => Self::Data,
+            'F' => Self::Form,
+            'G' => Self::Get,
+            'I' => Self::Head,
+            'u' => Self::User,
+            'b' => Self::Cookie,
+            'A' => Self::UserAgent,
+            'e' => Self::Referer,
+            _ => return None,
+        })
+    }
+
+    fn long(name: &str) -> Option<Self> {
+        Some(match name {
+            "request" => Self::Request,
+            "header" => Self::Header,
+            "data" | "data-ascii" => Self::Data,
+            "data-binary" => Self::DataBinary,
+            "data-raw" => Self::DataRaw,
+            "data-urlencode" => Self::DataUrlencode,
+            "json" => Self::Json,
+            "form" | "form-string" => Self::Form,
+            "get" => Self::Get,
+            "head" => Self::Head,
+            "user" => Self::User,
+            "cookie" => Self::Cookie,
+            "user-agent" => Self::UserAgent,
+            "referer" => Self::Referer,
+            "url" => Self::Url,
+            _ => return None,
+        })
+    }
+
+    fn takes_value(self) -> bool {
+        !matches!(self, Self::Get | Self::Head)
+    }
+}
+
+/// short options that are unknown to curlz, but take a value, like `-o file`
+const SHORT_WITH_VALUE: &str = "oTwmxEKcDrCYyzUtQP";
+
+/// long options that are unknown to curlz, but take a value, like `--max-time 5`
+const LONG_WITH_VALUE: &[&str] = &[
+    "output",
+    "output-dir",
+    "upload-file",
+    "write-out",
+    "max-time",
+    "connect-timeout",
+    "proxy",
+    "proxy-user",
+    "proxy-header",
+    "preproxy",
+    "noproxy",
+    "socks5",
+    "socks5-hostname",
+    "cert",
+    "cert-type",
+    "key",
+    "key-type",
+    "pass",
+    "cacert",
+    "capath",
+    "ciphers",
+    "pinnedpubkey",
+    "tls-max",
+    "config",
+    "cookie-jar",
+    "dump-header",
+    "etag-save",
+    "etag-compare",
+    "range",
+    "continue-at",
+    "speed-limit",
+    "speed-time",
+    "limit-rate",
+    "max-filesize",
+    "max-redirs",
+    "retry",
+    "retry-delay",
+    "retry-max-time",
+    "time-cond",
+    "telnet-option",
+    "quote",
+    "ftp-port",
+    "resolve",
+    "connect-to",
+    "interface",
+    "local-port",
+    "dns-servers",
+    "unix-socket",
+    "abstract-unix-socket",
+    "oauth2-bearer",
+    "aws-sigv4",
+    "request-target",
+    "netrc-file",
+    "trace",
+    "trace-ascii",
+    "stderr",
+    "expect100-timeout",
+    "keepalive-time",
+    "happy-eyeballs-timeout-ms",
+    "login-options",
+    "mail-from",
+    "mail-rcpt",
+    "hostpubmd5",
+    "create-file-mode",
+];
+
+/// parses curl arguments, like `-sSL -XPOST --header='Accept: */*' https://example.com`,
+/// combined short flags and the `--flag=value` form included
+pub fn parse_curl_args<S: AsRef<str>>(args: &[S]) -> crate::Result<CurlArgs> {
+    let mut parser = Parser {
+        args: args.iter().map(|arg| arg.as_ref().to_string()).collect(),
+        ..Default::default()
+    };
+    parser.parse()?;
+
+    Ok(parser.finish())
+}
+
+#[derive(Default)]
+struct Parser {
+    args: VecDeque<String>,
+    parsed: CurlArgs,
+    /// the parts of `-d` and its variants, curl joins them with `&`
+    data: Vec<String>,
+    /// whether data of files that curl reads is passed on in the curl params
+    curl_data: bool,
+    get: bool,
+    head: bool,
+    form: bool,
+}
+
+impl Parser {
+    fn parse(&mut self) -> crate::Result<()> {
+        while let Some(arg) = self.args.pop_front() {
+            if arg == "--" {
+                let rest = std::mem::take(&mut self.args);
+                rest.into_iter().for_each(|arg| self.positional(arg));
+            } else if let Some(long) = arg.strip_prefix("--") {
+                self.long(&arg, long)?;
+            } else if arg.len() > 1 && arg.starts_with('-') {
+                self.short_flags(&arg[1..])?;
+            } else {
+                self.positional(arg);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// a long option, like `--header 'Accept: */*'` or `--header='Accept: */*'`
+    fn long(&mut self, arg: &str, long: &str) -> crate::Result<()> {
+        let (name, inline) = match long.split_once('=') {
+            Some((name, value)) => (name, Some(value.to_string())),
+            None => (long, None),
+        };
+
+        match Opt::long(name) {
+            Some(opt) if opt.takes_value() => {
+                let value = self.value(inline, || format!("--{}", name))?;
+                self.apply(opt, value)
+            }
+            Some(opt) => self.apply(opt, String::new()),
+            None if LONG_WITH_VALUE.contains(&name) => {
+                let value = self.value(inline, || format!("--{}", name))?;
+                self.parsed
+                    .curl_params
+                    .extend([format!("--{}", name), value]);
+                Ok(())
+            }
+            None => {
+                self.parsed.curl_params.push(arg.to_string());
+                Ok(())
+            }
+        }
+    }
+
+    /// combined short flags, like `-sSL` or `-XPOST`, a flag with a value ends them
+    fn short_flags(&mut self, flags: &str) -> crate::Result<()> {
+        for (i, flag) in flags.char_indices() {
+            let rest = &flags[i + flag.len_utf8()..];
+            let inline = (!rest.is_empty()).then(|| rest.to_string());
+
+            match Opt::short(flag) {
+                Some(opt) if opt.takes_value() => {
+                    let value = self.value(inline, || format!("-{}", flag))?;
+                    return self.apply(opt, value);
+                }
+                Some(opt) => self.apply(opt, String::new())?,
+                None if SHORT_WITH_VALUE.contains(flag) => {
+                    let value = self.value(inline, || format!("-{}", flag))?;
+                    self.parsed
+                        .curl_params
+                        .extend([format!("-{}", flag), value]);
+                    return Ok(());
+                }
+                None => self.parsed.curl_params.push(format!("-{}", flag)),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// the first positional argument is the url, curl requests any further urls too
+    fn positional(&mut self, arg: String) {
+        if self.parsed.url.is_none() {
+            self.parsed.url = Some(arg.as_str().into());
+        } else {
+            self.parsed.curl_params.push(arg);
+        }
+    }
+
+    /// the `inline` value of an option, or otherwise the next argument
+    fn value(
+        &mut self,
+        inline: Option<String>,
+        option: impl Fn() -> String,
+    ) -> crate::Result<String> {
+        inline
+            .or_else(|| self.args.pop_front())
+            .ok_or_else(|| anyhow!("the curl option `{}` requires a value", option()))
+    }
+
+    fn apply(&mut self, opt: Opt, value: String) -> crate::Result<()> {
+        match opt {
+            Opt::Request => self.parsed.method = Some(HttpMethod::from_str(&value)?),
+            Opt::Header => {
+                let Some((name, value)) = value.split_once(':') else {
+                    bail!(
+                        "the curl header `{}` is invalid, it must look like `Name: value`",
+                        value
+                    )
+                };
+                self.parsed.headers.push(name.trim(), value.trim());
+            }
+            Opt::Data => match value.strip_prefix('@') {
+                // like curl, `-d` strips the newlines of a file
+                Some(file) if is_project_file(file) => self.data.push(format!(
+                    r#"{{{{ file({}) | replace("\r", "") | replace("\n", "") }}}}"#,
+                    literal(file)
+                )),
+                Some(_) => self.curl_data("--data", value),
+                None => self.data.push(value),
+            },
+            // the file might be binary, which the `file()` function cannot read
+            Opt::DataBinary if value.starts_with('@') => self.curl_data("--data-binary", value),
+            Opt::DataBinary | Opt::DataRaw => self.data.push(value),
+            Opt::DataUrlencode => match urlencode(&value) {
+                Some(data) => self.data.push(data),
+                None => self.curl_data("--data-urlencode", value),
+            },
+            Opt::Json => {
+                if self.data.is_empty() && !self.curl_data {
+                    self.parsed.headers.push("Content-Type", "application/json");
+                    self.parsed.headers.push("Accept", "application/json");
+                }
+                match value.strip_prefix('@') {
+                    Some(file) if is_project_file(file) => {
+                        self.data.push(format!("{{{{ file({}) }}}}", literal(file)))
+                    }
+                    // the json headers are set already
+                    Some(_) => self.curl_data("--data-binary", value),
+                    None => self.data.push(value),
+                }
+            }
+            Opt::Form => {
+                // curl builds the multipart body
+                self.form = true;
+                self.parsed.curl_params.extend(["-F".to_string(), value]);
+            }
+            Opt::Get => self.get = true,
+            Opt::Head => self.head = true,
+            Opt::User => self.parsed.user = Some(value),
+            Opt::Cookie if value.contains('=') => self.parsed.headers.push("Cookie", value),
+            // without `=` it is a cookie file
+            Opt::Cookie => self.parsed.curl_params.extend(["-b".to_string(), value]),
+            Opt::UserAgent => self.parsed.headers.push("User-Agent", value),
+            Opt::Referer => self.parsed.headers.push("Referer", value),
+            Opt::Url => self.parsed.url = Some(value.as_str().into()),
+        }
+
+        Ok(())
+    }
+
+    /// data of a file that curl reads itself, like stdin of `-d @-`
+    fn curl_data(&mut self, option: &str, value: String) {
+        self.curl_data = true;
+        self.parsed.curl_params.extend([option.to_string(), value]);
+    }
+
+    fn finish(self) -> CurlArgs {
+        let Self {
+            mut parsed,
+            data,
+            curl_data,
+            get,
+            head,
+            form,
+            ..
+        } = self;
+
+        if get {
+            // `-G` sends the data as query of the url
+            if let (Some(url), false) = (parsed.url.as_ref(), data.is_empty()) {
+                let url = url.as_ref();
+                let separator = if url.contains('?') { '&' } else { '?' };
+                parsed.url = Some(
+                    format!("{}{}{}", url, separator, data.join("&"))
+                        .as_str()
+                        .into(),
+                );
+            }
+        } else if !data.is_empty() {
+            parsed.body = HttpBody::InlineText(data.join("&"));
+        }
+
+        if parsed.method.is_none() {
+            parsed.method = if head {
+                Some(HttpMethod::Head)
+            } else if get {
+                Some(HttpMethod::Get)
+            } else if form || curl_data || !data.is_empty() {
+                Some(HttpMethod::Post)
+            } else {
+                None
+            };
+        }
+
+        parsed
+    }
+}
+
+/// the `--data-urlencode` forms `content`, `=content`, `name=content`, `@file` and `name@file`,
+/// none for files that are left to curl
+fn urlencode(value: &str) -> Option<String> {
+    if let Some((name, content)) = value.split_once('=') {
+        let name = if name.is_empty() {
+            String::new()
+        } else {
+            format!("{}=", name)
+        };
+        return Some(format!("{}{}", name, percent_encode(content)));
+    }
+
+    match value.split_once('@') {
+        Some((name, file)) if is_project_file(file) => {
+            let name = if name.is_empty() {
+                String::new()
+            } else {
+                format!("{}=", name)
+            };
+            Some(format!(
+                "{}{{{{ file({}) | urlencode }}}}",
+                name,
+                literal(file)
+            ))
+        }
+        Some(_) => None,
+        None => Some(percent_encode(value)),
+    }
+}
+
+/// whether the `file()` function can read a data file, that is a relative path inside the project,
+/// curl reads all others, like stdin of `@-` or absolute paths
+fn is_project_file(file: &str) -> bool {
+    file != "-"
+        && Path::new(file)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn parse(args: &[&str]) -> CurlArgs {
+        parse_curl_args(args).unwrap()
+    }
+
+    #[test]
+    fn should_parse_method_headers_and_url() {
+        let args = parse(&[
+            "-vvv",
+            "-H",
+            "foo: bar",
+            "--header",
+            "Accept: application/json",
+            "--request",
+            "PUT",
+            "http://example.com",
+        ]);
+
+        assert_eq!(args.method, Some(HttpMethod::Put));
+        assert_eq!(
+            args.headers.as_ref(),
+            [
+                ("foo".to_string(), "bar".to_string()),
+                ("Accept".to_string(), "application/json".to_string()),
+            ]
+        );
+        assert_eq!(args.url, Some("http://example.com".into()));
+        assert_eq!(args.curl_params, ["-v", "-v", "-v"]);
+    }
+
+    #[rstest]
+    #[case(&["-X", "POST"])]
+    #[case(&["-XPOST"])]
+    #[case(&["--request=POST"])]
+    #[case(&["-k", "-X", "POST"])]
+    #[case(&["-sX", "POST"])]
+    fn should_parse_the_method_at_any_position(#[case] args: &[&str]) {
+        assert_eq!(parse(args).method, Some(HttpMethod::Post));
+    }
+
+    #[test]
+    fn should_split_combined_short_flags() {
+        let args = parse(&["-sSLH", "Accept: */*", "-ofile.txt", "https://example.com"]);
+
+        assert_eq!(args.headers.get("Accept"), Some("*/*"));
+        assert_eq!(args.curl_params, ["-s", "-S", "-L", "-o", "file.txt"]);
+        assert_eq!(args.url, Some("https://example.com".into()));
+    }
+
+    #[test]
+    fn should_keep_unknown_options_with_their_values() {
+        let args = parse(&[
+            "--max-time",
+            "5",
+            "--compressed",
+            "--retry=3",
+            "https://example.com",
+        ]);
+
+        assert_eq!(
+            args.curl_params,
+            ["--max-time", "5", "--compressed", "--retry", "3"]
+        );
+        assert_eq!(args.url, Some("https://example.com".into()));
+    }
+
+    #[test]
+    fn should_join_data_and_imply_post() {
+        let args = parse(&[
+            "-d",
+            "a=1",
+            "--data-raw",
+            "@raw",
+            "-d",
+            "@body.json",
+            "--data-urlencode",
+            "q=rust lang",
+            "--url",
+            "https://example.com",
+        ]);
+
+        assert_eq!(args.method, Some(HttpMethod::Post));
+        assert_eq!(
+            args.body,
+            HttpBody::InlineText(
+                r#"a=1&@raw&{{ file("body.json") | replace("\r", "") | replace("\n", "") }}&q=rust%20lang"#
+                    .into()
+            )
+        );
+        assert_eq!(args.url, Some("https://example.com".into()));
+    }
+
+    #[rstest]
+    #[case(&["-d", "@-"], &["--data", "@-"])]
+    #[case(&["-d", "@/tmp/body.json"], &["--data", "@/tmp/body.json"])]
+    #[case(&["-d", "@../body.json"], &["--data", "@../body.json"])]
+    #[case(&["--data-binary", "@img.png"], &["--data-binary", "@img.png"])]
+    #[case(&["--data-urlencode", "q@-"], &["--data-urlencode", "q@-"])]
+    #[case(&["--json", "@/tmp/body.json"], &["--data-binary", "@/tmp/body.json"])]
+    fn should_leave_files_outside_the_project_to_curl(
+        #[case] data: &[&str],
+        #[case] curl_params: &[&str],
+    ) {
+        let args = parse(&[data, &["https://example.com"]].concat());
+
+        assert_eq!(args.method, Some(HttpMethod::Post));
+        assert_eq!(args.body, HttpBody::None);
+        assert_eq!(args.curl_params, curl_params);
+    }
+
+    #[test]
+    fn should_send_data_as_query_with_get() {
+        let args = parse(&[
+            "-G",
+            "-d",
+            "page=2",
+            "-d",
+            "q=x",
+            "https://example.com/?a=1",
+        ]);
+
+        assert_eq!(args.method, Some(HttpMethod::Get));
+        assert_eq!(args.body, HttpBody::None);
+        assert_eq!(args.url, Some("https://example.com/?a=1&page=2&q=x".into()));
+    }
+
+    #[test]
+    fn should_map_options_to_headers() {
+        let args = parse(&[
+            "-A",
+            "curlz/1.0",
+            "-e",
+            "https://example.com/",
+            "-b",
+            "session=abc",
+            "-b",
+            "cookies.txt",
+            "--json",
+            "{}",
+            "https://example.com",
+        ]);
+
+        assert_eq!(
+            args.headers.as_ref(),
+            [
+                ("User-Agent".to_string(), "curlz/1.0".to_string()),
+                ("Referer".to_string(), "https://example.com/".to_string()),
+                ("Cookie".to_string(), "session=abc".to_string()),
+                ("Content-Type".to_string(), "application/json".to_string()),
+                ("Accept".to_string(), "application/json".to_string()),
+            ]
+        );
+        assert_eq!(args.curl_params, ["-b", "cookies.txt"]);
+        assert_eq!(args.body, HttpBody::InlineText("{}".into()));
+    }
+
+    #[test]
+    fn should_parse_head_form_and_user() {
+        assert_eq!(
+            parse(&["-I", "https://example.com"]).method,
+            Some(HttpMethod::Head)
+        );
+
+        let args = parse(&[
+            "-u",
+            "john:secret",
+            "-F",
+            "file=@a.txt",
+            "https://example.com",
+        ]);
+        assert_eq!(args.method, Some(HttpMethod::Post));
+        assert_eq!(args.user, Some("john:secret".to_string()));
+        assert_eq!(args.curl_params, ["-F", "file=@a.txt"]);
+    }
+
+    #[rstest]
+    #[case("rust lang", "rust%20lang")]
+    #[case("=a&b", "a%26b")]
+    #[case("q=a b", "q=a%20b")]
+    #[case("q@query.txt", r#"q={{ file("query.txt") | urlencode }}"#)]
+    fn should_urlencode_data(#[case] value: &str, #[case] expected: &str) {
+        assert_eq!(urlencode(value).as_deref(), Some(expected));
+    }
+
+    #[test]
+    fn should_convert_into_a_request() {
+        let request =
+            HttpRequest::try_from(parse(&["-u", "john:secret", "https://example.com"])).unwrap();
+
+        assert_eq!(request.method, HttpMethod::Get);
+        assert_eq!(
+            request.headers.get("Authorization"),
+            Some(r#"Basic {{ basic("john", "secret") }}"#)
+        );
+    }
+
+    #[rstest]
+    #[case("john:se:cr\"et", r#"Basic {{ basic("john", "se:cr\"et") }}"#)]
+    #[case("john", r#"Basic {{ basic("john", prompt_password()) }}"#)]
+    fn should_escape_the_basic_auth_of_a_request(#[case] user: &str, #[case] expected: &str) {
+        let request = HttpRequest::try_from(parse(&["-u", user, "https://example.com"])).unwrap();
+
+        assert_eq!(request.headers.get("Authorization"), Some(expected));
+    }
+
+    #[test]
+    #[should_panic(expected = "the curl arguments contain no url")]
+    fn should_throw_on_requests_without_url() {
+        HttpRequest::try_from(parse(&["-X", "POST"])).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "the curl option `-H` requires a value")]
+    fn should_throw_on_missing_values() {
+        parse(&["https://example.com", "-H"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the curl header `Accept` is invalid")]
+    fn should_throw_on_invalid_headers() {
+        parse(&["-H", "Accept"]);
+    }
+}
@@ -0,0 +1,307 @@
+//! # HTTP Digest Authentication
+//! The client side of the challenge-response scheme described in [RFC 7616](https://www.rfc-editor.org/rfc/rfc7616),
+//! it is independent of any http backend: a backend provides the challenge of a `401` response
+//! and gets back the value for the `Authorization` header of the follow-up request.
+
+use anyhow::{anyhow, bail};
+use md5::Md5;
+use sha2::{Digest, Sha256};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// the nonce count, the challenge is answered only once per request
+const NONCE_COUNT: &str = "00000001";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DigestAlgorithm {
+    #[default]
+    Md5,
+    Md5Sess,
+    Sha256,
+    Sha256Sess,
+}
+
+impl DigestAlgorithm {
+    fn hash(&self, data: impl AsRef<[u8]>) -> String {
+        match self {
+            DigestAlgorithm::Md5 | DigestAlgorithm::Md5Sess => hex::encode(Md5::digest(data)),
+            DigestAlgorithm::Sha256 | DigestAlgorithm::Sha256Sess => {
+                hex::encode(Sha256::digest(data))
+            }
+        }
+    }
+
+    fn is_session(&self) -> bool {
+        matches!(self, DigestAlgorithm::Md5Sess | DigestAlgorithm::Sha256Sess)
+    }
+}
+
+impl FromStr for DigestAlgorithm {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "MD5" => Ok(DigestAlgorithm::Md5),
+            "MD5-SESS" => Ok(DigestAlgorithm::Md5Sess),
+            "SHA-256" => Ok(DigestAlgorithm::Sha256),
+            "SHA-256-SESS" => Ok(DigestAlgorithm::Sha256Sess),
+            _ => Err(anyhow!("Unsupported digest algorithm: {}", s)),
+        }
+    }
+}
+
+impl Display for DigestAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            DigestAlgorithm::Md5 => "MD5",
+            DigestAlgorithm::Md5Sess => "MD5-sess",
+            DigestAlgorithm::Sha256 => "SHA-256",
+            DigestAlgorithm::Sha256Sess => "SHA-256-sess",
+        })
+    }
+}
+
+/// the challenge of a `WWW-Authenticate: Digest ..` response header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DigestChallenge {
+    pub realm: String,
+    pub nonce: String,
+    pub opaque: Option<String>,
+    pub algorithm: DigestAlgorithm,
+    pub qop: Vec<String>,
+}
+
+/// the parts of the request that are covered by the digest
+#[derive(Debug)]
+pub struct DigestRequest<'a> {
+    pub method: &'a str,
+    /// the request target, e.g. `/dir/index.html?page=1`
+    pub uri: &'a str,
+    pub body: &'a str,
+}
+
+impl DigestChallenge {
+    /// computes the value of the `Authorization` header that answers this challenge
+    pub fn authorization(
+        &self,
+        user: &str,
+        password: &str,
+        request: &DigestRequest,
+        cnonce: &str,
+    ) -> String {
+        let algorithm = self.algorithm;
+        let mut ha1 = algorithm.hash(format!("{}:{}:{}", user, self.realm, password));
+        if algorithm.is_session() {
+            ha1 = algorithm.hash(format!("{}:{}:{}", ha1, self.nonce, cnonce));
+        }
+
+        let qop = self.preferred_qop();
+        let ha2 = match qop {
+            Some("auth-int") => algorithm.hash(format!(
+                "{}:{}:{}",
+                request.method,
+                request.uri,
+                algorithm.hash(request.body)
+            )),
+            _ => algorithm.hash(format!("{}:{}", request.method, request.uri)),
+        };
+
+        let response = match qop {
+            Some(qop) => algorithm.hash(format!(
+                "{}:{}:{}:{}:{}:{}",
+                ha1, self.nonce, NONCE_COUNT, cnonce, qop, ha2
+            )),
+            None => algorithm.hash(format!("{}:{}:{}", ha1, self.nonce, ha2)),
+        };
+
+        let mut authorization = format!(
+            r#"Digest username="{}", realm="{}", nonce="{}", uri="{}", algorithm={}, response="{}""#,
+            user, self.realm, self.nonce, request.uri, algorithm, response
+        );
+        if let Some(qop) = qop {
+            authorization.push_str(&format!(
+                r#", qop={}, nc={}, cnonce="{}""#,
+                qop, NONCE_COUNT, cnonce
+            ));
+        }
+        if let Some(opaque) = self.opaque.as_ref() {
+            authorization.push_str(&format!(r#", opaque="{}""#, opaque));
+        }
+
+        authorization
+    }
+
+    /// `auth` is preferred over `auth-int`, no qop means the legacy RFC 2069 digest
+    fn preferred_qop(&self) -> Option<&'static str> {
+        ["auth", "auth-int"]
+            .into_iter()
+            .find(|qop| self.qop.iter().any(|offered| offered.eq(qop)))
+    }
+}
+
+impl FromStr for DigestChallenge {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let params = match s.split_once(char::is_whitespace) {
+            Some((scheme, params)) if scheme.eq_ignore_ascii_case("digest") => params,
+            _ => bail!("Not a digest challenge: {}", s),
+        };
+
+        let mut realm = None;
+        let mut nonce = None;
+        let mut challenge = DigestChallenge {
+            realm: Default::default(),
+            nonce: Default::default(),
+            opaque: None,
+            algorithm: Default::default(),
+            qop: vec![],
+        };
+        for (key, value) in parse_auth_params(params) {
+            match key.to_lowercase().as_str() {
+                "realm" => realm = Some(value),
+                "nonce" => nonce = Some(value),
+                "opaque" => challenge.opaque = Some(value),
+                "algorithm" => challenge.algorithm = value.parse()?,
+                "qop" => {
+                    challenge.qop = value
+                        .split(',')
+                        .map(|qop| qop.trim().to_lowercase())
+                        .collect()
+                }
+                _ => {}
+            }
+        }
+        challenge.realm = realm.ok_or_else(|| anyhow!("Digest challenge without realm"))?;
+        challenge.nonce = nonce.ok_or_else(|| anyhow!("Digest challenge without nonce"))?;
+
+        Ok(challenge)
+    }
+}
+
+/// parses comma separated `key=value` or `key="quoted, value"` pairs
+fn parse_auth_params(params: &str) -> Vec<(String, String)> {
+    let mut pairs = vec![];
+    let mut chars = params.chars().peekable();
+
+    loop {
+        while matches!(chars.peek(), Some(c) if c.is_whitespace() || *c == ',') {
+            chars.next();
+        }
+        let key: String = chars.by_ref().take_while(|c| *c != '=').collect();
+        if key.trim().is_empty() {
+            break;
+        }
+
+        let mut value = String::new();
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => value.extend(chars.next()),
+                    '"' => break,
+                    c => value.push(c),
+                }
+            }
+        } else {
+            value = chars.by_ref().take_while(|c| *c != ',').collect();
+        }
+        pairs.push((key.trim().to_string(), value.trim().to_string()));
+    }
+
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const RFC_7616_CHALLENGE: &str = r#"Digest realm="http-auth@example.org", qop="auth, auth-int", algorithm=SHA-256, nonce="7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v", opaque="FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS""#;
+    const RFC_7616_CNONCE: &str = "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ";
+
+    fn get_index_html() -> DigestRequest<'static> {
+        DigestRequest {
+            method: "GET",
+            uri: "/dir/index.html",
+            body: "",
+        }
+    }
+
+    #[test]
+    fn should_parse_a_challenge() {
+        let challenge: DigestChallenge = RFC_7616_CHALLENGE.parse().unwrap();
+
+        assert_eq!(
+            challenge,
+            DigestChallenge {
+                realm: "http-auth@example.org".to_string(),
+                nonce: "7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v".to_string(),
+                opaque: Some("FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS".to_string()),
+                algorithm: DigestAlgorithm::Sha256,
+                qop: vec!["auth".to_string(), "auth-int".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn should_answer_a_sha256_challenge() {
+        let challenge: DigestChallenge = RFC_7616_CHALLENGE.parse().unwrap();
+        let authorization = challenge.authorization(
+            "Mufasa",
+            "Circle of Life",
+            &get_index_html(),
+            RFC_7616_CNONCE,
+        );
+
+        assert!(authorization.contains(
+            r#"response="753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1""#
+        ));
+        assert!(
+            authorization.starts_with(r#"Digest username="Mufasa", realm="http-auth@example.org""#)
+        );
+        assert!(authorization.contains("qop=auth, nc=00000001"));
+    }
+
+    #[test]
+    fn should_answer_a_md5_challenge() {
+        let challenge: DigestChallenge = RFC_7616_CHALLENGE
+            .replace("SHA-256", "MD5")
+            .parse()
+            .unwrap();
+        let authorization = challenge.authorization(
+            "Mufasa",
+            "Circle of Life",
+            &get_index_html(),
+            RFC_7616_CNONCE,
+        );
+
+        assert!(authorization.contains(r#"response="8ca523f5e9506fed4657c9700eebdbec""#));
+    }
+
+    #[test]
+    fn should_answer_a_legacy_challenge_without_qop() {
+        let challenge: DigestChallenge =
+            r#"Digest realm="testrealm@host.com", nonce="dcd98b7102dd2f0e8b11d0f600bfb0c093""#
+                .parse()
+                .unwrap();
+        let authorization =
+            challenge.authorization("Mufasa", "Circle Of Life", &get_index_html(), "0a4f113b");
+
+        assert!(authorization.contains(r#"response="670fd8c2df070c60b045671b8b24ff02""#));
+        assert!(!authorization.contains("qop="));
+    }
+
+    #[test]
+    #[should_panic(expected = "Not a digest challenge: Basic realm=\"foo\"")]
+    fn should_throw_on_other_schemes() {
+        DigestChallenge::from_str(r#"Basic realm="foo""#).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "Digest challenge without nonce")]
+    fn should_throw_on_incomplete_challenges() {
+        DigestChallenge::from_str(r#"Digest realm="foo""#).unwrap();
+    }
+}
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+/// authentication schemes that cannot be expressed as a static header,
+/// because they require a challenge-response round trip with the server
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum HttpAuth {
+    /// HTTP Digest authentication, `user` and `password` may contain template placeholders
+    Digest { user: String, password: String },
+}
@@ -2,20 +2,15 @@ use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum HttpBody {
     InlineText(String),
     InlineBinary(Vec<u8>),
     Extern(PathBuf),
+    #[default]
     None,
 }
 
-impl Default for HttpBody {
-    fn default() -> Self {
-        Self::None
-    }
-}
-
 impl HttpBody {
     // todo: not in sync with `.as_bytes()`
     pub fn contents(&self) -> std::io::Result<Option<String>> {
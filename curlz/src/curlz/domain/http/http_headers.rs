@@ -57,7 +57,7 @@ mod tests {
 
     #[test]
     fn should_parse_the_header_arg_flag_away() {
-        let args = vec![
+        let args = [
             "-H",
             "foo: bar",
             "--header",
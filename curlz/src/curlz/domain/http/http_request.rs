@@ -1,7 +1,10 @@
 use serde::{Deserialize, Serialize};
 
-use crate::domain::http::{HttpBody, HttpHeaders, HttpMethod, HttpUri, HttpVersion};
-use crate::template::variables::Placeholder;
+use crate::domain::http::{HttpAuth, HttpBody, HttpHeaders, HttpMethod, HttpUri, HttpVersion};
+use crate::template::variables::{discover_placeholders, Placeholder};
+
+/// the already rendered url, method and body are accessible by this name, e.g. in headers
+pub const REQUEST_VARIABLE: &str = "request";
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct HttpRequest {
@@ -12,6 +15,8 @@ pub struct HttpRequest {
     pub body: HttpBody,
     pub curl_params: Vec<String>,
     pub placeholders: Vec<Placeholder>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<HttpAuth>,
 }
 
 impl HttpRequest {
@@ -21,4 +26,30 @@ impl HttpRequest {
 
         req
     }
+
+    /// all parts of the request that are templates: the url, header values, body, curl params and credentials
+    pub fn templates(&self) -> Vec<&str> {
+        let mut templates = vec![self.url.as_ref()];
+        templates.extend(
+            self.headers
+                .as_ref()
+                .iter()
+                .map(|(_, value)| value.as_str()),
+        );
+        if let HttpBody::InlineText(body) = &self.body {
+            templates.push(body);
+        }
+        templates.extend(self.curl_params.iter().map(String::as_str));
+        if let Some(HttpAuth::Digest { user, password }) = &self.auth {
+            templates.extend([user.as_str(), password.as_str()]);
+        }
+
+        templates
+    }
+
+    /// the placeholders of all variables the templates of the request refer to,
+    /// the current placeholders provide the defaults
+    pub fn discover_placeholders(&self) -> crate::Result<Vec<Placeholder>> {
+        discover_placeholders(self.templates(), &[REQUEST_VARIABLE], &self.placeholders)
+    }
 }
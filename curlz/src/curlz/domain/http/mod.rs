@@ -1,3 +1,6 @@
+mod curl_args;
+mod digest_auth;
+mod http_auth;
 mod http_body;
 mod http_headers;
 mod http_method;
@@ -5,6 +8,9 @@ mod http_request;
 mod http_uri;
 mod http_version;
 
+pub use curl_args::*;
+pub use digest_auth::*;
+pub use http_auth::*;
 pub use http_body::*;
 pub use http_headers::*;
 pub use http_method::*;
@@ -31,6 +31,8 @@ pub fn parse_request_file(req_file: impl AsRef<str>) -> Result<Vec<Bookmark>, an
             Rule::request => {
                 requests.push(Bookmark {
                     slug: delimiter.to_owned(),
+                    description: None,
+                    tags: vec![],
                     request: HttpRequest::try_from(line)?,
                 });
             }
@@ -97,6 +99,7 @@ impl TryFrom<Pair<'_, Rule>> for HttpRequest {
                     body,
                     curl_params: Default::default(),
                     placeholders: Default::default(),
+                    auth: None,
                 })
             }
             _ => Err(anyhow!("The parsing result is not a valid `request`")),
@@ -167,6 +170,8 @@ mod tests {
         "#},
         Bookmark {
             slug: "### GET gitignore template for rustlang".into(),
+            description: None,
+            tags: vec![],
             request: HttpRequest {
                 url: "https://api.github.com/gitignore/templates/Rust".into(),
                 method: HttpMethod::Get,
@@ -175,6 +180,7 @@ mod tests {
                 body: HttpBody::default(),
                 curl_params: Default::default(),
                 placeholders: Default::default(),
+                auth: None,
             }
         }
     )]
@@ -185,6 +191,8 @@ mod tests {
         "#},
         Bookmark {
             slug: "### GET request with environment variables".into(),
+            description: None,
+            tags: vec![],
             request: HttpRequest {
                 url: "https://api.github.com/gitignore/templates/Rust".into(),
                 method: HttpMethod::Get,
@@ -193,6 +201,7 @@ mod tests {
                 body: HttpBody::default(),
                 curl_params: Default::default(),
                 placeholders: Default::default(),
+                auth: None,
             }
         }
     )]
@@ -210,6 +219,8 @@ mod tests {
         "#},
         Bookmark {
             slug: "### this is a POST request with a body".into(),
+            description: None,
+            tags: vec![],
             request: HttpRequest {
                 url: "https://httpbin.org/anything".into(),
                 method: HttpMethod::Post,
@@ -226,6 +237,7 @@ mod tests {
                 "#}.to_owned()),
                 curl_params: Default::default(),
                 placeholders: Default::default(),
+                auth: None,
             }
         }
     )]
@@ -243,6 +255,8 @@ mod tests {
         "#},
         Bookmark {
             slug: "### this is a POST request with a body".into(),
+            description: None,
+            tags: vec![],
             request: HttpRequest {
                 url: "https://httpbin.org/anything".into(),
                 method: HttpMethod::Post,
@@ -259,6 +273,7 @@ mod tests {
                 "#}.to_owned()),
                 curl_params: Default::default(),
                 placeholders: Default::default(),
+                auth: None,
             }
         }
     )]
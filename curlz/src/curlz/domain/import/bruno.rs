@@ -0,0 +1,470 @@
+//! # Bruno
+//! imports a [Bruno](https://www.usebruno.com) collection folder of `.bru` files,
+//! its `{{var}}` variables are already templates
+use crate::domain::http::{HttpAuth, HttpBody, HttpHeaders, HttpMethod, HttpRequest, HttpVersion};
+use crate::domain::import::{
+    basic_auth, dynamic_variables, read_file, slugify, ImportFormat, ImportedCollection,
+};
+
+use anyhow::{anyhow, bail, Context};
+use log::warn;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const ENVIRONMENTS_FOLDER: &str = "environments";
+/// the `.bru` files of folder and collection settings, they hold no request
+const SETTINGS: &[&str] = &["collection.bru", "folder.bru"];
+
+/// a Bruno collection folder, with the variables of an optional `environment` of its `environments/` folder
+#[derive(Debug, Default)]
+pub struct BrunoFormat {
+    pub environment: Option<String>,
+}
+
+impl ImportFormat for BrunoFormat {
+    fn import(&self, path: &Path) -> crate::Result<ImportedCollection> {
+        if !path.is_dir() {
+            bail!(
+                "a Bruno collection is a folder, `{}` is none",
+                path.display()
+            );
+        }
+        let mut imported = ImportedCollection::default();
+        if let Some(environment) = self.environment.as_ref() {
+            let file = path
+                .join(ENVIRONMENTS_FOLDER)
+                .join(format!("{}.bru", environment));
+            if !file.is_file() {
+                bail!("the environment `{}` does not exist", file.display());
+            }
+            imported.variables = import_bruno_environment(&read_file(&file)?)?
+                .into_iter()
+                .collect();
+        }
+
+        let mut files = vec![];
+        find_requests(path, &mut files)?;
+        let mut requests = vec![];
+        for file in files {
+            let folders = file
+                .strip_prefix(path)
+                .unwrap_or(&file)
+                .parent()
+                .map(|dir| {
+                    dir.iter()
+                        .map(|name| slugify(&name.to_string_lossy()))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            let request = parse_bru(&read_file(&file)?)
+                .with_context(|| format!("invalid Bruno request `{}`", file.display()))?;
+            requests.push((folders, request));
+        }
+        // like Bruno does, requests are ordered by their sequence within a folder
+        requests.sort_by(|(a, a_request), (b, b_request)| {
+            a.cmp(b).then(a_request.seq.cmp(&b_request.seq))
+        });
+
+        for (mut folders, bru) in requests {
+            let Some(request) = bru.request()? else {
+                continue;
+            };
+            folders.push(slugify(&bru.name));
+            imported.push(folders.join("/"), request);
+        }
+
+        Ok(imported)
+    }
+}
+
+fn find_requests(dir: &Path, files: &mut Vec<PathBuf>) -> crate::Result<()> {
+    let mut entries = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .collect::<Vec<_>>();
+    entries.sort();
+    for path in entries {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if path.is_dir() {
+            if name != ENVIRONMENTS_FOLDER && !name.starts_with('.') {
+                find_requests(&path, files)?;
+            }
+        } else if name.ends_with(".bru") && !SETTINGS.contains(&name.as_ref()) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// a block of a `.bru` file, like `headers { .. }`
+#[derive(Debug, PartialEq, Eq)]
+enum Block {
+    /// `key: value` pairs, disabled pairs start with `~`
+    Dictionary(Vec<(String, String)>),
+    /// a list like `vars:secret [ .. ]`
+    List(Vec<String>),
+    /// like a body or a script
+    Text(String),
+}
+
+/// the blocks that hold text instead of `key: value` pairs
+const TEXT_BLOCKS: &[&str] = &["body", "script", "tests", "docs"];
+
+/// parses the blocks of a `.bru` file, in their order
+fn parse_blocks(bru: &str) -> crate::Result<Vec<(String, Block)>> {
+    let mut blocks = vec![];
+    let mut lines = bru.lines();
+
+    while let Some(line) = lines.next() {
+        let line = line.trim_end();
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (name, close) = if let Some(name) = line.strip_suffix(" {") {
+            (name.trim(), "}")
+        } else if let Some(name) = line.strip_suffix(" [") {
+            (name.trim(), "]")
+        } else {
+            bail!("the line `{}` starts no block", line);
+        };
+
+        let mut content = vec![];
+        loop {
+            match lines.next() {
+                Some(line) if line.trim_end() == close => break,
+                // the content of a block is indented by 2 spaces
+                Some(line) => content.push(line.strip_prefix("  ").unwrap_or(line)),
+                None => bail!("the block `{}` is not closed", name),
+            }
+        }
+
+        let kind = name.split(':').next().unwrap_or(name);
+        let block = if close == "]" {
+            Block::List(
+                content
+                    .iter()
+                    .map(|l| l.trim().trim_end_matches(',').to_string())
+                    .filter(|l| !l.is_empty())
+                    .collect(),
+            )
+        } else if TEXT_BLOCKS.contains(&kind)
+            && name != "body:form-urlencoded"
+            && name != "body:multipart-form"
+        {
+            Block::Text(content.join("\n"))
+        } else {
+            Block::Dictionary(
+                content
+                    .iter()
+                    .filter(|l| !l.trim().is_empty())
+                    .filter_map(|l| l.trim().split_once(':'))
+                    .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+                    .collect(),
+            )
+        };
+        blocks.push((name.to_string(), block));
+    }
+
+    Ok(blocks)
+}
+
+/// a request of a `.bru` file
+#[derive(Debug)]
+struct Bru {
+    name: String,
+    seq: u32,
+    blocks: Vec<(String, Block)>,
+}
+
+fn parse_bru(bru: &str) -> crate::Result<Bru> {
+    let blocks = parse_blocks(bru)?;
+    let meta = dictionary(&blocks, "meta");
+    let value = |key: &str| meta.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone());
+
+    Ok(Bru {
+        name: value("name").ok_or_else(|| anyhow!("the request has no name"))?,
+        seq: value("seq")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or_default(),
+        blocks,
+    })
+}
+
+/// the enabled pairs of a dictionary block
+fn dictionary(blocks: &[(String, Block)], name: &str) -> Vec<(String, String)> {
+    blocks
+        .iter()
+        .find_map(|(n, block)| match block {
+            Block::Dictionary(pairs) if n == name => Some(
+                pairs
+                    .iter()
+                    .filter(|(key, _)| !key.starts_with('~'))
+                    .cloned()
+                    .collect(),
+            ),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+impl Bru {
+    /// the http request, other requests like GraphQL ones have none
+    fn request(&self) -> crate::Result<Option<HttpRequest>> {
+        let Some((method, block)) = self.blocks.iter().find(|(name, _)| {
+            HttpMethod::from_str(name).is_ok() && name.chars().all(|c| c.is_ascii_lowercase())
+        }) else {
+            warn!("the Bruno request `{}` is no http request", self.name);
+            return Ok(None);
+        };
+        let Block::Dictionary(settings) = block else {
+            bail!("the block `{}` has no settings", method);
+        };
+        let setting = |key: &str| {
+            settings
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+                .unwrap_or("none")
+        };
+
+        let mut url = dynamic_variables(setting("url"));
+        let query = dictionary(&self.blocks, "params:query")
+            .into_iter()
+            .map(|(key, value)| format!("{}={}", key, dynamic_variables(&value)))
+            .collect::<Vec<_>>();
+        // the url of a `.bru` file contains the query already, unless it has further params
+        if !query.is_empty() && !url.contains('?') {
+            url = format!("{}?{}", url, query.join("&"));
+        }
+        for (key, value) in dictionary(&self.blocks, "params:path") {
+            url = url.replace(&format!(":{}", key), &dynamic_variables(&value));
+        }
+
+        let mut headers = HttpHeaders::default();
+        for (key, value) in dictionary(&self.blocks, "headers") {
+            headers.push(key, dynamic_variables(&value));
+        }
+        let mut request = HttpRequest {
+            url: url.as_str().into(),
+            method: HttpMethod::from_str(method)?,
+            version: HttpVersion::Http11,
+            headers,
+            body: HttpBody::None,
+            curl_params: vec![],
+            placeholders: vec![],
+            auth: None,
+        };
+
+        match setting("body") {
+            "none" => {}
+            "formUrlEncoded" => {
+                let form = dictionary(&self.blocks, "body:form-urlencoded")
+                    .into_iter()
+                    .map(|(key, value)| format!("{}={}", key, dynamic_variables(&value)))
+                    .collect::<Vec<_>>();
+                push_content_type(&mut request, "application/x-www-form-urlencoded");
+                request.body = HttpBody::InlineText(form.join("&"));
+            }
+            "multipartForm" => {
+                for (key, value) in dictionary(&self.blocks, "body:multipart-form") {
+                    request.curl_params.extend([
+                        "-F".to_string(),
+                        format!("{}={}", key, dynamic_variables(&value)),
+                    ]);
+                }
+            }
+            kind => {
+                let name = format!("body:{}", kind);
+                let text = self.blocks.iter().find_map(|(n, block)| match block {
+                    Block::Text(text) if *n == name => Some(text),
+                    _ => None,
+                });
+                match text {
+                    Some(text) => {
+                        match kind {
+                            "json" => push_content_type(&mut request, "application/json"),
+                            "xml" => push_content_type(&mut request, "application/xml"),
+                            _ => {}
+                        }
+                        request.body = HttpBody::InlineText(dynamic_variables(text));
+                    }
+                    None => warn!("the Bruno body `{}` is not supported", kind),
+                }
+            }
+        }
+
+        let auth = setting("auth");
+        let auth_settings = dictionary(&self.blocks, &format!("auth:{}", auth));
+        let param = |key: &str| {
+            auth_settings
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| dynamic_variables(v))
+                .unwrap_or_default()
+        };
+        match auth {
+            "none" | "inherit" => {}
+            "bearer" => request
+                .headers
+                .push("Authorization", format!("Bearer {}", param("token"))),
+            "basic" => request.headers.push(
+                "Authorization",
+                basic_auth(&param("username"), &param("password")),
+            ),
+            "digest" => {
+                request.auth = Some(HttpAuth::Digest {
+                    user: param("username"),
+                    password: param("password"),
+                })
+            }
+            other => warn!("the Bruno auth `{}` is not supported", other),
+        }
+
+        Ok(Some(request))
+    }
+}
+
+fn push_content_type(request: &mut HttpRequest, content_type: &str) {
+    if request.headers.get("Content-Type").is_none() {
+        request.headers.push("Content-Type", content_type);
+    }
+}
+
+/// the enabled `vars` of a Bruno environment, secrets have no value in the file
+fn import_bruno_environment(environment: &str) -> crate::Result<Vec<(String, String)>> {
+    let blocks = parse_blocks(environment)?;
+
+    Ok(dictionary(&blocks, "vars"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const GET_USER: &str = indoc! {r#"
+        meta {
+          name: Get User
+          type: http
+          seq: 2
+        }
+
+        get {
+          url: {{baseUrl}}/users/:id?page=2
+          body: none
+          auth: bearer
+        }
+
+        params:query {
+          page: 2
+          ~debug: 1
+        }
+
+        params:path {
+          id: {{userId}}
+        }
+
+        headers {
+          Accept: application/json
+          ~X-Debug: 1
+        }
+
+        auth:bearer {
+          token: {{token}}
+        }
+    "#};
+
+    const CREATE_USER: &str = indoc! {r#"
+        meta {
+          name: Create User
+          type: http
+          seq: 1
+        }
+
+        post {
+          url: {{baseUrl}}/users
+          body: json
+          auth: none
+        }
+
+        body:json {
+          {
+            "id": "{{$guid}}",
+            "name": "john"
+          }
+        }
+    "#};
+
+    #[test]
+    fn should_parse_the_blocks_of_bru_files() {
+        let blocks = parse_blocks(CREATE_USER).unwrap();
+
+        assert_eq!(
+            blocks[2],
+            (
+                "body:json".to_string(),
+                Block::Text("{\n  \"id\": \"{{$guid}}\",\n  \"name\": \"john\"\n}".to_string())
+            )
+        );
+        assert_eq!(
+            import_bruno_environment("vars {\n  baseUrl: https://api.example.com\n  ~old: x\n}\nvars:secret [\n  token\n]\n")
+                .unwrap(),
+            [("baseUrl".to_string(), "https://api.example.com".to_string())]
+        );
+    }
+
+    #[test]
+    fn should_import_a_collection_folder() {
+        let collection = tempfile::tempdir().unwrap();
+        let users = collection.path().join("Users");
+        std::fs::create_dir_all(&users).unwrap();
+        std::fs::create_dir_all(collection.path().join(ENVIRONMENTS_FOLDER)).unwrap();
+        std::fs::write(collection.path().join("bruno.json"), "{}").unwrap();
+        std::fs::write(users.join("Get User.bru"), GET_USER).unwrap();
+        std::fs::write(users.join("Create User.bru"), CREATE_USER).unwrap();
+        std::fs::write(
+            collection
+                .path()
+                .join(ENVIRONMENTS_FOLDER)
+                .join("local.bru"),
+            "vars {\n  baseUrl: http://localhost:8080\n}\n",
+        )
+        .unwrap();
+
+        let imported = BrunoFormat {
+            environment: Some("local".to_string()),
+        }
+        .import(collection.path())
+        .unwrap();
+
+        assert_eq!(
+            imported
+                .bookmarks
+                .iter()
+                .map(|b| b.slug.as_str())
+                .collect::<Vec<_>>(),
+            ["users/create-user", "users/get-user"]
+        );
+        let create = &imported.bookmarks[0].request;
+        assert_eq!(create.headers.get("Content-Type"), Some("application/json"));
+        assert_eq!(
+            create.body,
+            HttpBody::InlineText(
+                "{\n  \"id\": \"{{ uuid() }}\",\n  \"name\": \"john\"\n}".to_string()
+            )
+        );
+        let get = &imported.bookmarks[1].request;
+        assert_eq!(get.url.as_ref(), "{{baseUrl}}/users/{{userId}}?page=2");
+        assert_eq!(
+            get.headers.as_ref(),
+            [
+                ("Accept".to_string(), "application/json".to_string()),
+                ("Authorization".to_string(), "Bearer {{token}}".to_string()),
+            ]
+        );
+        assert_eq!(
+            imported.variables.get("baseUrl").map(String::as_str),
+            Some("http://localhost:8080")
+        );
+    }
+}
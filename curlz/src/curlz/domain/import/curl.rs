@@ -0,0 +1,51 @@
+use crate::domain::http::{parse_curl_args, HttpRequest};
+use crate::domain::import::split_shell_words;
+
+use anyhow::{bail, Context};
+
+/// imports a curl command line, like the "Copy as cURL" of browsers, as request
+pub fn import_curl(command: &str) -> crate::Result<HttpRequest> {
+    let words = split_shell_words(command.trim())?;
+    let Some((curl, args)) = words.split_first() else {
+        bail!("the curl command is empty")
+    };
+    if curl != "curl" {
+        bail!("the command `{}` is not a curl command", curl);
+    }
+
+    HttpRequest::try_from(parse_curl_args(args)?).context("cannot import the curl command")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::http::{HttpBody, HttpMethod};
+    use indoc::indoc;
+
+    #[test]
+    fn should_import_a_copied_curl_command() {
+        let request = import_curl(indoc! {r#"
+            curl 'https://api.example.com/users/42' \
+              -H 'accept: application/json' \
+              -H 'authorization: Bearer abc' \
+              --data-raw '{"name":"john"}' \
+              --compressed
+        "#})
+        .unwrap();
+
+        assert_eq!(request.url.as_ref(), "https://api.example.com/users/42");
+        assert_eq!(request.method, HttpMethod::Post);
+        assert_eq!(request.headers.get("authorization"), Some("Bearer abc"));
+        assert_eq!(
+            request.body,
+            HttpBody::InlineText(r#"{"name":"john"}"#.to_string())
+        );
+        assert_eq!(request.curl_params, ["--compressed"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "the command `wget` is not a curl command")]
+    fn should_throw_on_other_commands() {
+        import_curl("wget https://example.com").unwrap();
+    }
+}
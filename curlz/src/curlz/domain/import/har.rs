@@ -0,0 +1,252 @@
+//! # HAR
+//! imports the [HTTP Archive](http://www.softwareishard.com/blog/har-12-spec/) format
+//! of browser devtools and proxies like Charles
+use crate::domain::http::{HttpBody, HttpHeaders, HttpMethod, HttpRequest, HttpVersion};
+use crate::domain::import::{read_file, slug_of_url, ImportFormat, ImportedCollection};
+
+use anyhow::Context;
+use serde::Deserialize;
+use std::path::Path;
+use std::str::FromStr;
+
+/// headers that curl sets by itself
+const SKIPPED_HEADERS: &[&str] = &["host", "content-length", "connection"];
+
+#[derive(Debug, Deserialize)]
+struct Har {
+    log: Log,
+}
+
+#[derive(Debug, Deserialize)]
+struct Log {
+    #[serde(default)]
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    #[serde(default)]
+    started_date_time: String,
+    request: Request,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Request {
+    method: String,
+    url: String,
+    #[serde(default)]
+    http_version: String,
+    #[serde(default)]
+    headers: Vec<NameValue>,
+    #[serde(default)]
+    post_data: Option<PostData>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PostData {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    params: Vec<NameValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NameValue {
+    name: String,
+    #[serde(default)]
+    value: String,
+}
+
+/// a HAR file, optionally only the requests to the host `filter` and its subdomains
+#[derive(Debug, Default)]
+pub struct HarFormat {
+    pub filter: Option<String>,
+}
+
+impl ImportFormat for HarFormat {
+    fn import(&self, path: &Path) -> crate::Result<ImportedCollection> {
+        import_har(&read_file(path)?, self.filter.as_deref())
+    }
+}
+
+/// imports the requests of a `har` file as bookmarks, only those to the host `filter` and its subdomains if given,
+/// the slugs are made of the url path, like `api-users` for `/api/users?page=2`
+pub fn import_har(har: &str, filter: Option<&str>) -> crate::Result<ImportedCollection> {
+    let har: Har = serde_json::from_str(har).context("invalid HAR file")?;
+    let mut imported = ImportedCollection::default();
+
+    for entry in har.log.entries {
+        let request = entry.request;
+        let Some(host) = host_of(&request.url) else {
+            continue;
+        };
+        if filter.is_some_and(|filter| host != filter && !host.ends_with(&format!(".{}", filter))) {
+            continue;
+        }
+        let slug = slug_of_url(&request.url);
+        let request = import_request(request).with_context(|| {
+            format!("cannot import the HAR entry `{}`", entry.started_date_time)
+        })?;
+
+        // repeated requests, like polling, are imported once
+        if imported.bookmarks.iter().any(|b| b.request == request) {
+            continue;
+        }
+        imported.push(slug, request);
+    }
+
+    Ok(imported)
+}
+
+fn import_request(request: Request) -> crate::Result<HttpRequest> {
+    let mut headers = HttpHeaders::default();
+    for header in request.headers.iter() {
+        let name = header.name.to_lowercase();
+        // like the `:authority` pseudo header of HTTP/2
+        if name.starts_with(':') || SKIPPED_HEADERS.contains(&name.as_str()) {
+            continue;
+        }
+        headers.push(&header.name, &header.value);
+    }
+
+    let body = match request.post_data {
+        Some(PostData {
+            text: Some(text), ..
+        }) if !text.is_empty() => HttpBody::InlineText(text),
+        Some(PostData { params, .. }) if !params.is_empty() => HttpBody::InlineText(
+            params
+                .iter()
+                .map(|p| format!("{}={}", p.name, p.value))
+                .collect::<Vec<_>>()
+                .join("&"),
+        ),
+        _ => HttpBody::None,
+    };
+    let version = match request.http_version.to_lowercase().as_str() {
+        "h2" | "http/2" | "http/2.0" => HttpVersion::Http2,
+        "h3" | "http/3" | "http/3.0" => HttpVersion::Http3,
+        _ => HttpVersion::Http11,
+    };
+
+    Ok(HttpRequest {
+        url: request.url.as_str().into(),
+        method: HttpMethod::from_str(&request.method)?,
+        version,
+        headers,
+        body,
+        curl_params: vec![],
+        placeholders: vec![],
+        auth: None,
+    })
+}
+
+/// the host of an http url, other urls like `data:` have none
+fn host_of(url: &str) -> Option<&str> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+
+    host.split(':').next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const HAR: &str = indoc! {r#"
+        {
+          "log": {
+            "version": "1.2",
+            "creator": { "name": "Firefox", "version": "112.0" },
+            "entries": [
+              {
+                "startedDateTime": "2023-05-01T10:00:00.000Z",
+                "request": {
+                  "method": "GET",
+                  "url": "https://api.example.com/users?page=2",
+                  "httpVersion": "HTTP/2",
+                  "headers": [
+                    { "name": ":authority", "value": "api.example.com" },
+                    { "name": "Host", "value": "api.example.com" },
+                    { "name": "Accept", "value": "application/json" }
+                  ]
+                },
+                "response": { "status": 200 }
+              },
+              {
+                "startedDateTime": "2023-05-01T10:00:01.000Z",
+                "request": { "method": "GET", "url": "https://cdn.example.org/app.js", "headers": [] }
+              },
+              {
+                "startedDateTime": "2023-05-01T10:00:02.000Z",
+                "request": {
+                  "method": "POST",
+                  "url": "https://auth.api.example.com/login",
+                  "headers": [{ "name": "Content-Type", "value": "application/x-www-form-urlencoded" }],
+                  "postData": {
+                    "mimeType": "application/x-www-form-urlencoded",
+                    "params": [{ "name": "user", "value": "john" }, { "name": "pass", "value": "s3cr3t" }]
+                  }
+                }
+              },
+              {
+                "startedDateTime": "2023-05-01T10:00:03.000Z",
+                "request": {
+                  "method": "GET",
+                  "url": "https://api.example.com/users?page=2",
+                  "httpVersion": "HTTP/2",
+                  "headers": [{ "name": "Accept", "value": "application/json" }]
+                }
+              },
+              {
+                "startedDateTime": "2023-05-01T10:00:04.000Z",
+                "request": { "method": "GET", "url": "https://api.example.com/users?page=3", "headers": [] }
+              }
+            ]
+          }
+        }
+    "#};
+
+    #[test]
+    fn should_import_the_entries_of_a_host() {
+        let imported = import_har(HAR, Some("api.example.com")).unwrap();
+
+        assert_eq!(
+            imported
+                .bookmarks
+                .iter()
+                .map(|b| (b.slug.as_str(), b.request.url.as_ref()))
+                .collect::<Vec<_>>(),
+            [
+                ("users", "https://api.example.com/users?page=2"),
+                ("login", "https://auth.api.example.com/login"),
+                ("users-2", "https://api.example.com/users?page=3"),
+            ]
+        );
+
+        let users = &imported.bookmarks[0].request;
+        assert_eq!(users.version, HttpVersion::Http2);
+        assert_eq!(
+            users.headers.as_ref(),
+            [("Accept".to_string(), "application/json".to_string())]
+        );
+        assert_eq!(
+            imported.bookmarks[1].request.body,
+            HttpBody::InlineText("user=john&pass=s3cr3t".to_string())
+        );
+    }
+
+    #[test]
+    fn should_import_all_entries_without_filter() {
+        let imported = import_har(HAR, None).unwrap();
+
+        assert_eq!(imported.bookmarks.len(), 4);
+        assert_eq!(imported.bookmarks[1].slug, "app-js");
+    }
+}
@@ -0,0 +1,328 @@
+//! # Hurl
+//! imports the requests of a [Hurl](https://hurl.dev) file, their responses and asserts are left out,
+//! its `{{var}}` variables are already templates
+use crate::domain::http::{HttpBody, HttpHeaders, HttpMethod, HttpRequest, HttpVersion};
+use crate::domain::import::{basic_auth, read_file, slug_of_url, ImportFormat, ImportedCollection};
+use crate::utils::parse_pairs;
+
+use anyhow::{bail, Context};
+use log::warn;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// a Hurl file, with the variables of an optional `variables` file of `name=value` lines,
+/// like for `hurl --variables-file`
+#[derive(Debug, Default)]
+pub struct HurlFormat {
+    pub variables: Option<PathBuf>,
+}
+
+impl ImportFormat for HurlFormat {
+    fn import(&self, path: &Path) -> crate::Result<ImportedCollection> {
+        let mut imported = import_hurl(&read_file(path)?)?;
+        if let Some(variables) = self.variables.as_deref() {
+            for line in read_file(variables)?.lines() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                match parse_pairs(line, '=') {
+                    Some((name, value)) => {
+                        imported
+                            .variables
+                            .insert(name.to_string(), value.to_string());
+                    }
+                    None => bail!("the variable `{}` is no `name=value` pair", line),
+                }
+            }
+        }
+
+        Ok(imported)
+    }
+}
+
+/// the parts of a request entry
+#[derive(Debug, PartialEq, Eq)]
+enum Section {
+    Headers,
+    Query,
+    Form,
+    Multipart,
+    Cookies,
+    BasicAuth,
+    /// like `[Options]`, they are left out
+    Other,
+}
+
+/// imports the requests of a `hurl` file as bookmarks, named after their url path
+pub fn import_hurl(hurl: &str) -> crate::Result<ImportedCollection> {
+    let request_line = Regex::new(r"^([A-Z]+)\s+(\S.*)$").unwrap();
+    let response_line = Regex::new(r"^HTTP(/[\d.]+)?\s+(\d{3}|\*)").unwrap();
+    let mut imported = ImportedCollection::default();
+    let mut entry: Option<Entry> = None;
+    let mut in_response = false;
+    let mut lines = hurl.lines().enumerate().peekable();
+
+    while let Some((number, line)) = lines.next() {
+        let trimmed = line.trim();
+        if let Some(captures) = request_line
+            .captures(trimmed)
+            .filter(|c| HttpMethod::from_str(&c[1]).is_ok())
+        {
+            if let Some(entry) = entry.take() {
+                entry.push_into(&mut imported);
+            }
+            entry = Some(Entry::new(&captures[1], captures[2].trim())?);
+            in_response = false;
+            continue;
+        }
+        if response_line.is_match(trimmed) {
+            in_response = true;
+            continue;
+        }
+        let Some(entry) = entry.as_mut().filter(|_| !in_response) else {
+            continue;
+        };
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if trimmed.starts_with('[') && trimmed.ends_with(']') && !trimmed.contains(' ') {
+            entry.section = match &trimmed[1..trimmed.len() - 1] {
+                "QueryStringParams" | "Query" => Section::Query,
+                "FormParams" | "Form" => Section::Form,
+                "MultipartFormData" | "Multipart" => Section::Multipart,
+                "Cookies" => Section::Cookies,
+                "BasicAuth" => Section::BasicAuth,
+                other => {
+                    warn!("the Hurl section `[{}]` is left out", other);
+                    Section::Other
+                }
+            };
+            continue;
+        }
+        if let Some(body) = trimmed.strip_prefix("```") {
+            // a multiline body, the first line may name its language like ```json
+            let mut text = vec![];
+            for (_, line) in lines.by_ref() {
+                if line.trim() == "```" {
+                    break;
+                }
+                text.push(line);
+            }
+            if body.trim() == "json" {
+                entry.content_type("application/json");
+            }
+            entry.body = Some(text.join("\n"));
+            continue;
+        }
+        if let Some(body) = trimmed.strip_prefix('`').and_then(|b| b.strip_suffix('`')) {
+            entry.body = Some(body.to_string());
+            continue;
+        }
+        if let Some(file) = trimmed
+            .strip_prefix("file,")
+            .and_then(|f| f.strip_suffix(';'))
+        {
+            entry.body = Some(format!(r#"{{{{ file("{}") }}}}"#, file.trim()));
+            continue;
+        }
+        if trimmed.starts_with('{') || trimmed.starts_with('[') || trimmed.starts_with('<') {
+            // a json or xml body, it ends with the response or the next request
+            let mut text = vec![line];
+            while let Some((_, next)) = lines.peek() {
+                let next = next.trim();
+                if response_line.is_match(next) || request_line.is_match(next) {
+                    break;
+                }
+                text.push(lines.next().map(|(_, l)| l).unwrap_or_default());
+            }
+            let text = text.join("\n").trim_end().to_string();
+            if trimmed.starts_with('<') {
+                entry.content_type("application/xml");
+            } else {
+                entry.content_type("application/json");
+            }
+            entry.body = Some(text);
+            continue;
+        }
+
+        let Some((key, value)) = trimmed.split_once(':') else {
+            bail!("the line {} `{}` is not supported", number + 1, trimmed);
+        };
+        let (key, value) = (key.trim(), value.trim());
+        match entry.section {
+            Section::Headers => entry.headers.push(key, value),
+            Section::Query => entry.query.push(format!("{}={}", key, value)),
+            Section::Form => entry.form.push(format!("{}={}", key, value)),
+            Section::Multipart => {
+                let value = match value.strip_prefix("file,") {
+                    Some(file) => format!("@{}", file.split(';').next().unwrap_or_default().trim()),
+                    None => value.to_string(),
+                };
+                entry.multipart.push(format!("{}={}", key, value));
+            }
+            Section::Cookies => entry.cookies.push(format!("{}={}", key, value)),
+            Section::BasicAuth => entry.headers.push("Authorization", basic_auth(key, value)),
+            Section::Other => {}
+        }
+    }
+    if let Some(entry) = entry {
+        entry.push_into(&mut imported);
+    }
+
+    Ok(imported)
+}
+
+/// a request entry, while it is parsed
+struct Entry {
+    method: HttpMethod,
+    url: String,
+    section: Section,
+    headers: HttpHeaders,
+    query: Vec<String>,
+    form: Vec<String>,
+    multipart: Vec<String>,
+    cookies: Vec<String>,
+    body: Option<String>,
+}
+
+impl Entry {
+    fn new(method: &str, url: &str) -> crate::Result<Self> {
+        Ok(Self {
+            method: HttpMethod::from_str(method)
+                .with_context(|| format!("invalid Hurl request `{} {}`", method, url))?,
+            url: url.to_string(),
+            section: Section::Headers,
+            headers: HttpHeaders::default(),
+            query: vec![],
+            form: vec![],
+            multipart: vec![],
+            cookies: vec![],
+            body: None,
+        })
+    }
+
+    fn content_type(&mut self, content_type: &str) {
+        if self.headers.get("Content-Type").is_none() {
+            self.headers.push("Content-Type", content_type);
+        }
+    }
+
+    fn push_into(mut self, imported: &mut ImportedCollection) {
+        let mut url = std::mem::take(&mut self.url);
+        if !self.query.is_empty() {
+            let separator = if url.contains('?') { '&' } else { '?' };
+            url = format!("{}{}{}", url, separator, self.query.join("&"));
+        }
+        if !self.cookies.is_empty() {
+            self.headers.push("Cookie", self.cookies.join("; "));
+        }
+        if !self.form.is_empty() {
+            self.content_type("application/x-www-form-urlencoded");
+            self.body = Some(self.form.join("&"));
+        }
+
+        let request = HttpRequest {
+            url: url.as_str().into(),
+            method: self.method,
+            version: HttpVersion::Http11,
+            headers: self.headers,
+            body: self
+                .body
+                .map(HttpBody::InlineText)
+                .unwrap_or(HttpBody::None),
+            curl_params: self
+                .multipart
+                .into_iter()
+                .flat_map(|field| ["-F".to_string(), field])
+                .collect(),
+            placeholders: vec![],
+            auth: None,
+        };
+        imported.push(slug_of_url(&url), request);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const HURL: &str = indoc! {r#"
+        # the users of a page
+        GET {{base_url}}/users
+        Accept: application/json
+        [QueryStringParams]
+        page: 2
+
+        HTTP 200
+        [Asserts]
+        jsonpath "$.users" count > 0
+
+        POST {{base_url}}/users
+        [BasicAuth]
+        admin: {{password}}
+        {
+          "name": "john"
+        }
+
+        HTTP/1.1 201
+
+        POST {{base_url}}/login
+        [FormParams]
+        user: john
+        pass: {{password}}
+    "#};
+
+    #[test]
+    fn should_import_the_requests_of_a_hurl_file() {
+        let imported = import_hurl(HURL).unwrap();
+
+        assert_eq!(
+            imported
+                .bookmarks
+                .iter()
+                .map(|b| (b.slug.as_str(), b.request.url.as_ref()))
+                .collect::<Vec<_>>(),
+            [
+                ("users", "{{base_url}}/users?page=2"),
+                ("users", "{{base_url}}/users"),
+                ("login", "{{base_url}}/login"),
+            ]
+        );
+        let create = &imported.bookmarks[1].request;
+        assert_eq!(create.method, HttpMethod::Post);
+        assert_eq!(
+            create.headers.as_ref(),
+            [
+                (
+                    "Authorization".to_string(),
+                    r#"Basic {{ basic("admin", password) }}"#.to_string()
+                ),
+                ("Content-Type".to_string(), "application/json".to_string()),
+            ]
+        );
+        assert_eq!(
+            create.body,
+            HttpBody::InlineText("{\n  \"name\": \"john\"\n}".to_string())
+        );
+        assert_eq!(
+            imported.bookmarks[2].request.body,
+            HttpBody::InlineText("user=john&pass={{password}}".to_string())
+        );
+    }
+
+    #[test]
+    fn should_import_multiline_bodies() {
+        let imported =
+            import_hurl("PUT https://example.com/notes/1\n```\nhello\nworld\n```\n").unwrap();
+
+        assert_eq!(imported.bookmarks[0].slug, "notes-1");
+        assert_eq!(
+            imported.bookmarks[0].request.body,
+            HttpBody::InlineText("hello\nworld".to_string())
+        );
+    }
+}
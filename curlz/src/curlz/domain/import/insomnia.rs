@@ -0,0 +1,423 @@
+//! # Insomnia
+//! imports the export format v4 of [Insomnia](https://insomnia.rest),
+//! its `{{ _.var }}` variables and some template tags become plain templates
+use crate::domain::http::{HttpAuth, HttpBody, HttpHeaders, HttpMethod, HttpRequest, HttpVersion};
+use crate::domain::import::{basic_auth, read_file, slugify, ImportFormat, ImportedCollection};
+
+use anyhow::{anyhow, bail, Context};
+use log::warn;
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::str::FromStr;
+
+/// an Insomnia v4 export, with the variables of the base environment and optionally of a sub `environment`
+#[derive(Debug, Default)]
+pub struct InsomniaFormat {
+    pub environment: Option<String>,
+}
+
+impl ImportFormat for InsomniaFormat {
+    fn import(&self, path: &Path) -> crate::Result<ImportedCollection> {
+        import_insomnia(&read_file(path)?, self.environment.as_deref())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Export {
+    #[serde(rename = "__export_format")]
+    format: u8,
+    #[serde(default)]
+    resources: Vec<Resource>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Resource {
+    #[serde(rename = "_id")]
+    id: String,
+    #[serde(rename = "_type")]
+    kind: String,
+    parent_id: Option<String>,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    method: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    headers: Vec<Pair>,
+    #[serde(default)]
+    parameters: Vec<Pair>,
+    #[serde(default)]
+    body: Body,
+    #[serde(default)]
+    authentication: Authentication,
+    /// the variables of an environment
+    #[serde(default)]
+    data: serde_json::Map<String, Value>,
+    /// the variables of a folder
+    #[serde(default)]
+    environment: serde_json::Map<String, Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Body {
+    mime_type: Option<String>,
+    text: Option<String>,
+    #[serde(default)]
+    params: Vec<Pair>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Authentication {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    #[serde(default)]
+    disabled: bool,
+    token: Option<String>,
+    prefix: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    key: Option<String>,
+    value: Option<String>,
+    add_to: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Pair {
+    name: String,
+    #[serde(default)]
+    value: String,
+    #[serde(default)]
+    disabled: bool,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    file_name: Option<String>,
+}
+
+/// imports the requests of an Insomnia v4 `export` as bookmarks, slugs follow the folders like `users/get-user`,
+/// the base environment, the folder environments and the sub `environment` become variables
+pub fn import_insomnia(
+    export: &str,
+    environment: Option<&str>,
+) -> crate::Result<ImportedCollection> {
+    let export: Export = serde_json::from_str(export).context("invalid Insomnia export")?;
+    if export.format != 4 {
+        bail!(
+            "the Insomnia export format `{}` is not supported, only version 4 is",
+            export.format
+        );
+    }
+    let resources: HashMap<&str, &Resource> = export
+        .resources
+        .iter()
+        .map(|r| (r.id.as_str(), r))
+        .collect();
+    let mut imported = ImportedCollection::default();
+
+    // the base environments belong to the workspace, sub environments to a base environment
+    let is_base = |env: &Resource| {
+        env.parent_id
+            .as_deref()
+            .and_then(|id| resources.get(id))
+            .is_some_and(|parent| parent.kind == "workspace")
+    };
+    let environments = export.resources.iter().filter(|r| r.kind == "environment");
+    for base in environments.clone().filter(|env| is_base(env)) {
+        insert_variables(&mut imported, &base.data);
+    }
+    for folder in export
+        .resources
+        .iter()
+        .filter(|r| r.kind == "request_group")
+    {
+        insert_variables(&mut imported, &folder.environment);
+    }
+    if let Some(name) = environment {
+        let Some(sub) = environments
+            .clone()
+            .find(|env| !is_base(env) && env.name == name)
+        else {
+            let names = environments
+                .filter(|env| !is_base(env))
+                .map(|env| format!("`{}`", env.name))
+                .collect::<Vec<_>>();
+            bail!(
+                "the environment `{}` does not exist, there are: {}",
+                name,
+                names.join(", ")
+            );
+        };
+        insert_variables(&mut imported, &sub.data);
+    }
+
+    for request in export.resources.iter().filter(|r| r.kind == "request") {
+        let mut names = vec![slugify(&request.name)];
+        let mut parent = request
+            .parent_id
+            .as_deref()
+            .and_then(|id| resources.get(id));
+        while let Some(folder) = parent.filter(|p| p.kind == "request_group") {
+            names.insert(0, slugify(&folder.name));
+            parent = folder.parent_id.as_deref().and_then(|id| resources.get(id));
+        }
+        let request = import_request(request)
+            .with_context(|| format!("cannot import the Insomnia request `{}`", request.name))?;
+        imported.push(names.join("/"), request);
+    }
+
+    Ok(imported)
+}
+
+fn insert_variables(imported: &mut ImportedCollection, data: &serde_json::Map<String, Value>) {
+    for (name, value) in data {
+        let value = match value {
+            Value::String(text) => templates(text),
+            Value::Number(_) | Value::Bool(_) => value.to_string(),
+            _ => {
+                warn!(
+                    "the structured Insomnia variable `{}` is not supported",
+                    name
+                );
+                continue;
+            }
+        };
+        imported.variables.insert(name.clone(), value);
+    }
+}
+
+fn import_request(resource: &Resource) -> crate::Result<HttpRequest> {
+    let mut url = templates(&resource.url);
+    let query = resource
+        .parameters
+        .iter()
+        .filter(|p| !p.disabled)
+        .map(|p| format!("{}={}", templates(&p.name), templates(&p.value)))
+        .collect::<Vec<_>>();
+    if !query.is_empty() {
+        let separator = if url.contains('?') { '&' } else { '?' };
+        url = format!("{}{}{}", url, separator, query.join("&"));
+    }
+
+    let body = &resource.body;
+    // curl builds a multipart body and its content type
+    let multipart = body.mime_type.as_deref() == Some("multipart/form-data");
+    let mut headers = HttpHeaders::default();
+    for header in resource.headers.iter().filter(|h| !h.disabled) {
+        if multipart && header.name.eq_ignore_ascii_case("Content-Type") {
+            continue;
+        }
+        headers.push(&header.name, templates(&header.value));
+    }
+    let mut request = HttpRequest {
+        url: "".into(),
+        method: HttpMethod::from_str(&resource.method)
+            .map_err(|_| anyhow!("the method `{}` is not supported", resource.method))?,
+        version: HttpVersion::Http11,
+        headers,
+        body: HttpBody::None,
+        curl_params: vec![],
+        placeholders: vec![],
+        auth: None,
+    };
+
+    match body.mime_type.as_deref() {
+        Some("application/x-www-form-urlencoded") => {
+            let form = body
+                .params
+                .iter()
+                .filter(|p| !p.disabled)
+                .map(|p| format!("{}={}", templates(&p.name), templates(&p.value)))
+                .collect::<Vec<_>>();
+            request.body = HttpBody::InlineText(form.join("&"));
+        }
+        Some("multipart/form-data") => {
+            for param in body.params.iter().filter(|p| !p.disabled) {
+                let value = match (param.kind.as_deref(), param.file_name.as_ref()) {
+                    (Some("file"), Some(file)) => format!("@{}", file),
+                    _ => templates(&param.value),
+                };
+                request
+                    .curl_params
+                    .extend(["-F".to_string(), format!("{}={}", param.name, value)]);
+            }
+        }
+        _ => {
+            if let Some(text) = body.text.as_ref().filter(|t| !t.is_empty()) {
+                request.body = HttpBody::InlineText(templates(text));
+            }
+        }
+    }
+
+    let auth = &resource.authentication;
+    let param = |value: &Option<String>| templates(value.as_deref().unwrap_or_default());
+    match auth.kind.as_deref() {
+        _ if auth.disabled => {}
+        None | Some("none") => {}
+        Some("bearer") => request.headers.push(
+            "Authorization",
+            format!(
+                "{} {}",
+                auth.prefix
+                    .as_deref()
+                    .filter(|p| !p.is_empty())
+                    .unwrap_or("Bearer"),
+                param(&auth.token)
+            ),
+        ),
+        Some("basic") => request.headers.push(
+            "Authorization",
+            basic_auth(&param(&auth.username), &param(&auth.password)),
+        ),
+        Some("digest") => {
+            request.auth = Some(HttpAuth::Digest {
+                user: param(&auth.username),
+                password: param(&auth.password),
+            })
+        }
+        Some("apikey") => {
+            if auth.add_to.as_deref() == Some("queryParams") {
+                let separator = if url.contains('?') { '&' } else { '?' };
+                url = format!(
+                    "{}{}{}={}",
+                    url,
+                    separator,
+                    param(&auth.key),
+                    param(&auth.value)
+                );
+            } else {
+                request.headers.push(param(&auth.key), param(&auth.value));
+            }
+        }
+        Some(other) => warn!("the Insomnia authentication `{}` is not supported", other),
+    }
+    request.url = url.as_str().into();
+
+    Ok(request)
+}
+
+/// turns the variables of Insomnia, like `{{ _.base_url }}`, into plain variables and
+/// its template tags, like `{% uuid 'v4' %}`, into template functions
+fn templates(text: &str) -> String {
+    let variable = Regex::new(r"\{\{\s*_\.([\w.]+)\s*\}\}").unwrap();
+    let tag = Regex::new(r"\{%\s*(\w+)([^%]*)%\}").unwrap();
+
+    let text = variable.replace_all(text, "{{ $1 }}");
+    tag.replace_all(&text, |captures: &Captures| {
+        let args = captures[2].trim();
+        match &captures[1] {
+            "uuid" => "{{ uuid() }}".to_string(),
+            "now" if args.contains("millis") => "{{ timestamp_ms() }}".to_string(),
+            "now" if args.contains("unix") => "{{ timestamp() }}".to_string(),
+            "now" => "{{ now() }}".to_string(),
+            "base64" if args.starts_with("'encode'") => {
+                let value = args.split(',').nth(2).unwrap_or_default().trim();
+                format!(r#"{{{{ {} | base64encode }}}}"#, value.replace('\'', "\""))
+            }
+            other => {
+                warn!("the Insomnia template tag `{}` is not supported", other);
+                captures[0].to_string()
+            }
+        }
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const EXPORT: &str = indoc! {r#"
+        {
+          "_type": "export",
+          "__export_format": 4,
+          "resources": [
+            { "_id": "wrk_1", "parentId": null, "_type": "workspace", "name": "Users API" },
+            { "_id": "env_base", "parentId": "wrk_1", "_type": "environment", "name": "Base Environment",
+              "data": { "base_url": "https://api.example.com", "page_size": 20 } },
+            { "_id": "env_stg", "parentId": "env_base", "_type": "environment", "name": "Staging",
+              "data": { "base_url": "https://staging.example.com" } },
+            { "_id": "fld_1", "parentId": "wrk_1", "_type": "request_group", "name": "Users",
+              "environment": { "user_id": "42" } },
+            { "_id": "req_1", "parentId": "fld_1", "_type": "request", "name": "Get User", "method": "GET",
+              "url": "{{ _.base_url }}/users/{{ _.user_id }}",
+              "parameters": [{ "name": "size", "value": "{{ _.page_size }}" }, { "name": "debug", "value": "1", "disabled": true }],
+              "headers": [{ "name": "X-Request-Id", "value": "{% uuid 'v4' %}" }],
+              "authentication": { "type": "bearer", "token": "{{ _.token }}" },
+              "body": {} },
+            { "_id": "req_2", "parentId": "wrk_1", "_type": "request", "name": "Login", "method": "POST",
+              "url": "{{ _.base_url }}/login", "headers": [],
+              "authentication": { "type": "basic", "username": "john", "password": "{{ _.password }}" },
+              "body": { "mimeType": "application/json", "text": "{\"at\": {% now 'millis', '' %}}" } }
+          ]
+        }
+    "#};
+
+    #[test]
+    fn should_import_requests_in_folders() {
+        let imported = import_insomnia(EXPORT, None).unwrap();
+
+        let user = &imported.bookmarks[0];
+        assert_eq!(user.slug, "users/get-user");
+        assert_eq!(
+            user.request.url.as_ref(),
+            "{{ base_url }}/users/{{ user_id }}?size={{ page_size }}"
+        );
+        assert_eq!(
+            user.request.headers.as_ref(),
+            [
+                ("X-Request-Id".to_string(), "{{ uuid() }}".to_string()),
+                (
+                    "Authorization".to_string(),
+                    "Bearer {{ token }}".to_string()
+                ),
+            ]
+        );
+
+        let login = &imported.bookmarks[1];
+        assert_eq!(login.slug, "login");
+        assert_eq!(
+            login.request.headers.get("Authorization"),
+            Some(r#"Basic {{ basic("john", password) }}"#)
+        );
+        assert_eq!(
+            login.request.body,
+            HttpBody::InlineText(r#"{"at": {{ timestamp_ms() }}}"#.to_string())
+        );
+    }
+
+    #[test]
+    fn should_import_the_variables_of_environments() {
+        let base = import_insomnia(EXPORT, None).unwrap();
+        assert_eq!(
+            base.variables.into_iter().collect::<Vec<_>>(),
+            [
+                (
+                    "base_url".to_string(),
+                    "https://api.example.com".to_string()
+                ),
+                ("page_size".to_string(), "20".to_string()),
+                ("user_id".to_string(), "42".to_string()),
+            ]
+        );
+
+        let staging = import_insomnia(EXPORT, Some("Staging")).unwrap();
+        assert_eq!(
+            staging.variables.get("base_url").map(String::as_str),
+            Some("https://staging.example.com")
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the environment `Production` does not exist, there are: `Staging`")]
+    fn should_throw_on_unknown_environments() {
+        import_insomnia(EXPORT, Some("Production")).unwrap();
+    }
+}
@@ -0,0 +1,314 @@
+//! imports requests from other tools, like a curl command line, a postman collection, an OpenAPI spec or a HAR file
+//!
+//! each format of a file or folder implements [`ImportFormat`], it turns it into an [`ImportedCollection`]
+mod bruno;
+mod curl;
+mod har;
+mod hurl;
+mod insomnia;
+mod openapi;
+mod placeholders;
+mod postman;
+mod shell;
+
+pub use bruno::*;
+pub use curl::*;
+pub use har::*;
+pub use hurl::*;
+pub use insomnia::*;
+pub use openapi::*;
+pub use placeholders::*;
+pub use postman::*;
+pub use shell::*;
+
+use crate::domain::bookmark::Bookmark;
+use crate::domain::http::HttpRequest;
+
+use anyhow::Context;
+use convert_case::{Case, Casing};
+use log::warn;
+use regex::{Captures, Regex};
+use std::collections::BTreeMap;
+use std::iter::Peekable;
+use std::path::Path;
+use std::str::Chars;
+
+/// a format of another tool, like a postman collection
+pub trait ImportFormat {
+    /// imports the file or folder at `path`
+    fn import(&self, path: &Path) -> crate::Result<ImportedCollection>;
+}
+
+/// the bookmarks and variables of an imported collection, like of postman
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ImportedCollection {
+    pub bookmarks: Vec<Bookmark>,
+    /// the variables, like of an environment, they become a yaml environment file
+    pub variables: BTreeMap<String, String>,
+}
+
+impl ImportedCollection {
+    /// adds a bookmark, its slug gets a number, like `users-2`, when it is taken by a request of the same method
+    pub fn push(&mut self, slug: impl Into<String>, request: HttpRequest) -> &mut Bookmark {
+        let slug = slug.into();
+        let taken = |slug: &str| {
+            self.bookmarks
+                .iter()
+                .any(|b| b.slug == slug && b.request.method == request.method)
+        };
+        let mut numbered = slug.clone();
+        for i in 2.. {
+            if !taken(&numbered) {
+                break;
+            }
+            numbered = format!("{}-{}", slug, i);
+        }
+
+        self.bookmarks.push(Bookmark {
+            slug: numbered,
+            description: None,
+            tags: vec![],
+            request,
+        });
+
+        self.bookmarks.last_mut().unwrap()
+    }
+}
+
+/// reads the file of an import
+pub(crate) fn read_file(path: &Path) -> crate::Result<String> {
+    std::fs::read_to_string(path).with_context(|| format!("cannot read `{}`", path.display()))
+}
+
+/// a slug of a name, like `get-user` for `Get User`
+pub(crate) fn slugify(name: &str) -> String {
+    name.replace(['/', '\\'], " ").to_case(Case::Kebab)
+}
+
+/// a slug of the path of an url, like `api-users` for `https://example.com/api/users?page=2`
+/// or `users` for `{{ base_url }}/users`
+pub(crate) fn slug_of_url(url: &str) -> String {
+    let path = match (url.find("://"), url.strip_prefix("{{")) {
+        (Some(i), _) => {
+            let rest = &url[i + 3..];
+            &rest[rest.find('/').unwrap_or(rest.len())..]
+        }
+        (None, Some(rest)) => &rest[rest.find("}}").map(|i| i + 2).unwrap_or(0)..],
+        (None, None) => url,
+    };
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+
+    match path.replace(['/', '.', '{', '}'], " ").to_case(Case::Kebab) {
+        slug if slug.is_empty() => "index".to_string(),
+        slug => slug,
+    }
+}
+
+/// turns the dynamic variables of other tools, like `{{$guid}}`, into template functions
+pub(crate) fn dynamic_variables(text: &str) -> String {
+    let dynamic = Regex::new(r"\{\{\s*\$(\w+)\s*\}\}").unwrap();
+
+    dynamic
+        .replace_all(text, |captures: &Captures| match &captures[1] {
+            "guid" | "uuid" | "randomUUID" => "{{ uuid() }}".to_string(),
+            "timestamp" => "{{ timestamp() }}".to_string(),
+            "randomInt" => "{{ random_int(0, 1000) }}".to_string(),
+            other => {
+                warn!("the dynamic variable `{{{{${}}}}}` is not supported", other);
+                captures[0].to_string()
+            }
+        })
+        .to_string()
+}
+
+/// the value of a basic auth header, the `user` and `password` may contain templates like `{{password}}`,
+/// like `Basic {{ basic("john", password) }}`
+pub(crate) fn basic_auth(user: &str, password: &str) -> String {
+    format!(
+        "Basic {{{{ basic({}, {}) }}}}",
+        expression(user),
+        expression(password)
+    )
+}
+
+/// the user and password of a basic auth header, the reverse of [`basic_auth`],
+/// like `john` and `{{ password }}` for `Basic {{ basic("john", password) }}`,
+/// none when the arguments are more than strings and variables, like `prompt_password()`
+pub(crate) fn parse_basic_auth(value: &str) -> Option<(String, String)> {
+    let basic = Regex::new(r"^Basic \{\{\s*basic\((.*)\)\s*\}\}$").unwrap();
+    let captures = basic.captures(value.trim())?;
+    let mut chars = captures.get(1)?.as_str().chars().peekable();
+    let skip_whitespace = |chars: &mut Peekable<Chars>| {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+    };
+
+    let mut args = vec![];
+    let mut arg = String::new();
+    loop {
+        skip_whitespace(&mut chars);
+        match chars.next()? {
+            '"' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next()? {
+                        '\\' => text.push(chars.next()?),
+                        '"' => break,
+                        c => text.push(c),
+                    }
+                }
+                arg.push_str(&text);
+            }
+            // a variable, also in parentheses like `(id)`
+            c if c == '(' || c == '_' || c.is_ascii_alphabetic() => {
+                let parenthesized = c == '(';
+                let mut name = String::new();
+                if parenthesized {
+                    skip_whitespace(&mut chars);
+                } else {
+                    name.push(c);
+                }
+                while let Some(c) = chars.next_if(|c| *c == '_' || c.is_ascii_alphanumeric()) {
+                    name.push(c);
+                }
+                skip_whitespace(&mut chars);
+                if name.is_empty() || chars.next_if_eq(&'(').is_some() {
+                    // a function call, like `prompt_password()`
+                    return None;
+                }
+                if parenthesized && chars.next_if_eq(&')').is_none() {
+                    return None;
+                }
+                arg.push_str(&format!("{{{{ {} }}}}", name));
+            }
+            _ => return None,
+        }
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            Some('~') => {}
+            Some(',') => args.push(std::mem::take(&mut arg)),
+            None => {
+                args.push(arg);
+                break;
+            }
+            Some(_) => return None,
+        }
+    }
+
+    match <[String; 2]>::try_from(args) {
+        Ok([user, password]) => Some((user, password)),
+        Err(_) => None,
+    }
+}
+
+/// the template string literal of a `text`, like `"say \"hi\""` for `say "hi"`
+pub(crate) fn literal(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// the template expression of a `text`, like `password` for `{{password}}`,
+/// `"john"` for `john` or `"user-" ~ (id)` for `user-{{id}}`
+fn expression(text: &str) -> String {
+    let variable = Regex::new(r"\{\{\s*(.*?)\s*\}\}").unwrap();
+
+    let mut parts = vec![];
+    let mut last = 0;
+    for captures in variable.captures_iter(text) {
+        let matched = captures.get(0).unwrap();
+        if matched.start() > last {
+            parts.push(literal(&text[last..matched.start()]));
+        }
+        parts.push(format!("({})", &captures[1]));
+        last = matched.end();
+    }
+    if last < text.len() || parts.is_empty() {
+        parts.push(literal(&text[last..]));
+    }
+
+    match parts.as_slice() {
+        [part] => part
+            .strip_prefix('(')
+            .and_then(|part| part.strip_suffix(')'))
+            .unwrap_or(part)
+            .to_string(),
+        _ => parts.join(" ~ "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::http::HttpMethod;
+    use crate::test_utils;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("https://api.example.com/api/users?page=2", "api-users")]
+    #[case("{{ base_url }}/users/{{ id }}", "users-id")]
+    #[case("{{baseUrl}}", "index")]
+    #[case("/app.js", "app-js")]
+    fn should_slug_urls(#[case] url: &str, #[case] expected: &str) {
+        assert_eq!(slug_of_url(url), expected);
+    }
+
+    #[test]
+    fn should_number_taken_slugs() {
+        let request = |method: HttpMethod| test_utils::request("https://example.com", method);
+        let mut imported = ImportedCollection::default();
+        imported.push("users", request(HttpMethod::Get));
+        imported.push("users", request(HttpMethod::Post));
+        imported.push("users", request(HttpMethod::Get));
+
+        assert_eq!(
+            imported
+                .bookmarks
+                .iter()
+                .map(|b| b.slug.as_str())
+                .collect::<Vec<_>>(),
+            ["users", "users", "users-2"]
+        );
+    }
+
+    #[rstest]
+    #[case("john", "secret", r#"Basic {{ basic("john", "secret") }}"#)]
+    #[case("john", "{{password}}", r#"Basic {{ basic("john", password) }}"#)]
+    #[case("{{ user }}", "{{ uuid() }}", r#"Basic {{ basic(user, uuid()) }}"#)]
+    #[case("user-{{id}}", "", r#"Basic {{ basic("user-" ~ (id), "") }}"#)]
+    #[case(r#"jo"hn"#, r"se\cret", r#"Basic {{ basic("jo\"hn", "se\\cret") }}"#)]
+    fn should_build_basic_auth(#[case] user: &str, #[case] password: &str, #[case] expected: &str) {
+        assert_eq!(basic_auth(user, password), expected);
+    }
+
+    #[rstest]
+    #[case(r#"Basic {{ basic("john", "se\"cret") }}"#, "john", r#"se"cret"#)]
+    #[case(
+        r#"Basic {{ basic(user, "pre-" ~ (id) ~ "-post") }}"#,
+        "{{ user }}",
+        "pre-{{ id }}-post"
+    )]
+    fn should_parse_basic_auth_of_strings_and_variables(
+        #[case] value: &str,
+        #[case] user: &str,
+        #[case] password: &str,
+    ) {
+        assert_eq!(
+            parse_basic_auth(value),
+            Some((user.to_string(), password.to_string()))
+        );
+    }
+
+    #[rstest]
+    #[case(r#"Basic {{ basic("john", prompt_password()) }}"#)]
+    #[case(r#"Basic {{ basic("john") }}"#)]
+    #[case(r#"Basic {{ basic("john", "secret") | upper }}"#)]
+    fn should_not_parse_basic_auth_of_expressions(#[case] value: &str) {
+        assert_eq!(parse_basic_auth(value), None);
+    }
+
+    #[test]
+    fn should_turn_dynamic_variables_into_functions() {
+        assert_eq!(
+            dynamic_variables(r#"{"id": "{{$guid}}", "at": {{ $timestamp }}, "x": "{{$foo}}"}"#),
+            r#"{"id": "{{ uuid() }}", "at": {{ timestamp() }}, "x": "{{$foo}}"}"#
+        );
+    }
+}
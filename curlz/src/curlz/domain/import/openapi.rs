@@ -0,0 +1,509 @@
+//! # OpenAPI
+//! generates a bookmark per operation of an [OpenAPI 3](https://spec.openapis.org/oas/v3.0.3) spec,
+//! the parameters become placeholders and the server url the `base_url` variable
+use crate::domain::http::{HttpBody, HttpHeaders, HttpMethod, HttpRequest, HttpVersion};
+use crate::domain::import::{read_file, slug_of_url, ImportFormat, ImportedCollection};
+use crate::template::variables::{Placeholder, PlaceholderType};
+
+use anyhow::{bail, Context};
+use convert_case::{Case, Casing};
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+const BASE_URL: &str = "base_url";
+const METHODS: &[(&str, HttpMethod)] = &[
+    ("get", HttpMethod::Get),
+    ("post", HttpMethod::Post),
+    ("put", HttpMethod::Put),
+    ("patch", HttpMethod::Patch),
+    ("delete", HttpMethod::Delete),
+    ("head", HttpMethod::Head),
+    ("options", HttpMethod::Options),
+];
+/// schemas are nested only this deep into example bodies, recursive schemas end here
+const MAX_EXAMPLE_DEPTH: usize = 8;
+
+#[derive(Debug, Deserialize)]
+struct Spec {
+    openapi: String,
+    #[serde(default)]
+    servers: Vec<Server>,
+    #[serde(default)]
+    paths: Mapping,
+    #[serde(default)]
+    components: Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct Server {
+    url: String,
+    #[serde(default)]
+    variables: BTreeMap<String, ServerVariable>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ServerVariable {
+    default: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Operation {
+    operation_id: Option<String>,
+    summary: Option<String>,
+    description: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    parameters: Vec<Value>,
+    request_body: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Parameter {
+    name: String,
+    #[serde(rename = "in")]
+    location: String,
+    description: Option<String>,
+    #[serde(default)]
+    required: bool,
+    #[serde(default)]
+    schema: Value,
+    example: Option<Value>,
+}
+
+/// an OpenAPI 3 spec file
+#[derive(Debug, Default)]
+pub struct OpenApiFormat;
+
+impl ImportFormat for OpenApiFormat {
+    fn import(&self, path: &Path) -> crate::Result<ImportedCollection> {
+        import_openapi(&read_file(path)?)
+    }
+}
+
+/// imports an OpenAPI 3 `spec`, in yaml or json, as one bookmark per operation
+pub fn import_openapi(spec: &str) -> crate::Result<ImportedCollection> {
+    let spec: Spec = serde_yaml::from_str(spec).context("invalid OpenAPI spec")?;
+    if !spec.openapi.starts_with('3') {
+        bail!(
+            "the OpenAPI version `{}` is not supported, only version 3 is",
+            spec.openapi
+        );
+    }
+    let mut imported = ImportedCollection::default();
+    if let Some(server) = spec.servers.first() {
+        let url = server
+            .variables
+            .iter()
+            .fold(server.url.clone(), |url, (name, variable)| {
+                url.replace(&format!("{{{}}}", name), &variable.default)
+            });
+        imported
+            .variables
+            .insert(BASE_URL.to_string(), url.trim_end_matches('/').to_string());
+    }
+
+    for (path, item) in spec.paths.iter() {
+        let path = path.as_str().unwrap_or_default();
+        let shared = item
+            .get("parameters")
+            .and_then(Value::as_sequence)
+            .cloned()
+            .unwrap_or_default();
+        for (key, method) in METHODS {
+            let Some(operation) = item.get(key) else {
+                continue;
+            };
+            let operation: Operation = serde_yaml::from_value(operation.clone())
+                .with_context(|| format!("invalid operation `{} {}`", key, path))?;
+            let slug = operation
+                .operation_id
+                .as_deref()
+                .map(|id| id.to_case(Case::Kebab))
+                .unwrap_or_else(|| slug_of_url(path));
+            let request = import_operation(
+                &spec,
+                imported.variables.get(BASE_URL),
+                path,
+                method.clone(),
+                &shared,
+                &operation,
+            )
+            .with_context(|| format!("cannot import the operation `{} {}`", key, path))?;
+            let bookmark = imported.push(slug, request);
+            bookmark.description = operation.summary.or(operation.description);
+            bookmark.tags = operation.tags;
+        }
+    }
+
+    Ok(imported)
+}
+
+fn import_operation(
+    spec: &Spec,
+    base_url: Option<&String>,
+    path: &str,
+    method: HttpMethod,
+    shared: &[Value],
+    operation: &Operation,
+) -> crate::Result<HttpRequest> {
+    let mut url = format!("{{{{ {} }}}}{}", BASE_URL, path);
+    let mut query = vec![];
+    let mut headers = HttpHeaders::default();
+    let mut placeholders = vec![];
+    if let Some(base_url) = base_url {
+        placeholders.push(Placeholder {
+            name: BASE_URL.to_string(),
+            default: Some(base_url.to_string()),
+            ..Default::default()
+        });
+    }
+
+    // the parameters of an operation override those of its path
+    let mut parameters: Vec<Parameter> = vec![];
+    for parameter in shared.iter().chain(operation.parameters.iter()) {
+        let parameter: Parameter = serde_yaml::from_value(resolve(spec, parameter)?.clone())
+            .context("invalid parameter")?;
+        parameters.retain(|p| !(p.name == parameter.name && p.location == parameter.location));
+        parameters.push(parameter);
+    }
+    for parameter in parameters {
+        let name = parameter.name.to_case(Case::Snake);
+        let template = format!("{{{{ {} }}}}", name);
+        let default = parameter
+            .schema
+            .get("default")
+            .or(parameter.example.as_ref())
+            .or(parameter.schema.get("example"))
+            .map(text);
+        match parameter.location.as_str() {
+            "path" => url = url.replace(&format!("{{{}}}", parameter.name), &template),
+            "query" if parameter.required || default.is_some() => {
+                query.push(format!("{}={}", parameter.name, template))
+            }
+            "header" if parameter.required => headers.push(&parameter.name, &template),
+            _ => continue,
+        }
+        placeholders.push(Placeholder {
+            name,
+            default,
+            prompt: parameter.description.clone(),
+            kind: match parameter.schema.get("type").and_then(Value::as_str) {
+                Some("integer" | "number") => PlaceholderType::Number,
+                Some("boolean") => PlaceholderType::Bool,
+                _ => PlaceholderType::String,
+            },
+            choices: parameter
+                .schema
+                .get("enum")
+                .and_then(Value::as_sequence)
+                .map(|choices| choices.iter().map(text).collect())
+                .unwrap_or_default(),
+            ..Default::default()
+        });
+    }
+    if !query.is_empty() {
+        url = format!("{}?{}", url, query.join("&"));
+    }
+
+    let mut body = HttpBody::None;
+    if let Some(request_body) = operation.request_body.as_ref() {
+        let content = resolve(spec, request_body)?
+            .get("content")
+            .and_then(Value::as_mapping)
+            .cloned()
+            .unwrap_or_default();
+        let media = content
+            .iter()
+            .find(|(content_type, _)| content_type.as_str().is_some_and(|c| c.contains("json")))
+            .or_else(|| content.iter().next());
+        if let Some((content_type, media)) = media {
+            let content_type = content_type.as_str().unwrap_or_default();
+            if let Some(example) = example_of(spec, media)? {
+                body = HttpBody::InlineText(if content_type.contains("json") {
+                    serde_json::to_string_pretty(&example)?
+                } else {
+                    text(&example)
+                });
+                headers.push("Content-Type", content_type);
+            }
+        }
+    }
+
+    Ok(HttpRequest {
+        url: url.as_str().into(),
+        method,
+        version: HttpVersion::Http11,
+        headers,
+        body,
+        curl_params: vec![],
+        placeholders,
+        auth: None,
+    })
+}
+
+/// the example of a media type, either given or made up from its schema
+fn example_of(spec: &Spec, media: &Value) -> crate::Result<Option<Value>> {
+    if let Some(example) = media.get("example") {
+        return Ok(Some(example.clone()));
+    }
+    let example = media
+        .get("examples")
+        .and_then(Value::as_mapping)
+        .and_then(|examples| examples.values().next());
+    if let Some(example) = example {
+        return Ok(resolve(spec, example)?.get("value").cloned());
+    }
+    match media.get("schema") {
+        Some(schema) => example_of_schema(spec, schema, 0).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// makes up an example of a `schema`, from its examples, defaults and types
+fn example_of_schema(spec: &Spec, schema: &Value, depth: usize) -> crate::Result<Value> {
+    let schema = resolve(spec, schema)?;
+    if let Some(example) = schema.get("example").or(schema.get("default")) {
+        return Ok(example.clone());
+    }
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(Value::as_sequence)
+        .and_then(|e| e.first())
+    {
+        return Ok(first.clone());
+    }
+    if depth > MAX_EXAMPLE_DEPTH {
+        return Ok(Value::Null);
+    }
+    let one_of = ["allOf", "oneOf", "anyOf"]
+        .iter()
+        .find_map(|key| schema.get(key).and_then(Value::as_sequence));
+    if let Some(schemas) = one_of {
+        let mut merged = Mapping::new();
+        for schema in schemas {
+            match example_of_schema(spec, schema, depth + 1)? {
+                Value::Mapping(example) => merged.extend(example),
+                example if schemas.len() == 1 || schema.get("properties").is_none() => {
+                    return Ok(example)
+                }
+                _ => {}
+            }
+        }
+        return Ok(Value::Mapping(merged));
+    }
+
+    let kind = schema.get("type").and_then(Value::as_str);
+    Ok(match kind {
+        Some("object") | None if schema.get("properties").is_some() => {
+            let mut example = Mapping::new();
+            if let Some(properties) = schema.get("properties").and_then(Value::as_mapping) {
+                for (name, property) in properties {
+                    example.insert(name.clone(), example_of_schema(spec, property, depth + 1)?);
+                }
+            }
+            Value::Mapping(example)
+        }
+        Some("array") => match schema.get("items") {
+            Some(items) => Value::Sequence(vec![example_of_schema(spec, items, depth + 1)?]),
+            None => Value::Sequence(vec![]),
+        },
+        Some("integer" | "number") => Value::from(0),
+        Some("boolean") => Value::from(false),
+        Some("string") => Value::from(""),
+        _ => Value::Mapping(Mapping::new()),
+    })
+}
+
+/// follows a local `$ref`, like `#/components/schemas/User`
+fn resolve<'a>(spec: &'a Spec, value: &'a Value) -> crate::Result<&'a Value> {
+    let mut value = value;
+    for _ in 0..MAX_EXAMPLE_DEPTH {
+        let Some(reference) = value.get("$ref").and_then(Value::as_str) else {
+            return Ok(value);
+        };
+        let Some(path) = reference.strip_prefix("#/components/") else {
+            bail!("the reference `{}` is not supported", reference);
+        };
+        value = path
+            .split('/')
+            .try_fold(&spec.components, |value, key| value.get(key))
+            .with_context(|| format!("the reference `{}` does not exist", reference))?;
+    }
+
+    bail!("the references starting from `{:?}` are circular", value)
+}
+
+/// a plain text of a yaml value, like `42` or `available`
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => String::new(),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const SPEC: &str = indoc! {r#"
+        openapi: 3.0.3
+        info:
+          title: Pet Store
+          version: 1.0.0
+        servers:
+          - url: https://petstore.example.com/v1/
+        paths:
+          /pets:
+            get:
+              operationId: listPets
+              parameters:
+                - name: status
+                  in: query
+                  description: the status of the pets
+                  schema:
+                    type: string
+                    enum: [available, sold]
+                    default: available
+                - name: limit
+                  in: query
+                  schema:
+                    type: integer
+            post:
+              operationId: createPet
+              requestBody:
+                content:
+                  application/json:
+                    schema:
+                      $ref: '#/components/schemas/Pet'
+          /pets/{petId}:
+            parameters:
+              - $ref: '#/components/parameters/PetId'
+            delete:
+              parameters:
+                - name: X-Request-Id
+                  in: header
+                  required: true
+                  schema:
+                    type: string
+              responses:
+                204:
+                  description: deleted
+        components:
+          parameters:
+            PetId:
+              name: petId
+              in: path
+              required: true
+              description: the id of the pet
+              schema:
+                type: integer
+                example: 42
+          schemas:
+            Pet:
+              type: object
+              properties:
+                name:
+                  type: string
+                  example: Rex
+                tags:
+                  type: array
+                  items:
+                    type: string
+                vaccinated:
+                  type: boolean
+    "#};
+
+    #[test]
+    fn should_import_a_bookmark_per_operation() {
+        let imported = import_openapi(SPEC).unwrap();
+
+        assert_eq!(
+            imported
+                .bookmarks
+                .iter()
+                .map(|b| (b.slug.as_str(), b.request.method.clone()))
+                .collect::<Vec<_>>(),
+            [
+                ("list-pets", HttpMethod::Get),
+                ("create-pet", HttpMethod::Post),
+                ("pets-pet-id", HttpMethod::Delete),
+            ]
+        );
+        assert_eq!(
+            imported.variables.get("base_url").map(String::as_str),
+            Some("https://petstore.example.com/v1")
+        );
+    }
+
+    #[test]
+    fn should_turn_parameters_into_placeholders() {
+        let imported = import_openapi(SPEC).unwrap();
+
+        let list = &imported.bookmarks[0].request;
+        assert_eq!(list.url.as_ref(), "{{ base_url }}/pets?status={{ status }}");
+        assert_eq!(
+            list.placeholders[1],
+            Placeholder {
+                name: "status".to_string(),
+                default: Some("available".to_string()),
+                prompt: Some("the status of the pets".to_string()),
+                choices: vec!["available".to_string(), "sold".to_string()],
+                ..Default::default()
+            }
+        );
+
+        let delete = &imported.bookmarks[2].request;
+        assert_eq!(delete.url.as_ref(), "{{ base_url }}/pets/{{ pet_id }}");
+        assert_eq!(
+            delete.headers.get("X-Request-Id"),
+            Some("{{ x_request_id }}")
+        );
+        assert_eq!(
+            delete.placeholders[1],
+            Placeholder {
+                name: "pet_id".to_string(),
+                default: Some("42".to_string()),
+                prompt: Some("the id of the pet".to_string()),
+                kind: PlaceholderType::Number,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn should_make_up_example_bodies_from_schemas() {
+        let imported = import_openapi(SPEC).unwrap();
+
+        let create = &imported.bookmarks[1].request;
+        assert_eq!(create.headers.get("Content-Type"), Some("application/json"));
+        assert_eq!(
+            create.body,
+            HttpBody::InlineText(
+                indoc! {r#"
+                    {
+                      "name": "Rex",
+                      "tags": [
+                        ""
+                      ],
+                      "vaccinated": false
+                    }"#}
+                .to_string()
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the OpenAPI version `2.0` is not supported")]
+    fn should_throw_on_swagger_2() {
+        import_openapi("openapi: '2.0'\npaths: {}").unwrap();
+    }
+}
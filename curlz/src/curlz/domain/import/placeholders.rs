@@ -0,0 +1,222 @@
+use crate::domain::http::{HttpHeaders, HttpRequest};
+use crate::template::variables::Placeholder;
+
+/// a value of an imported request that can be replaced by a placeholder,
+/// like the host by `{{ base_url }}` or a bearer token by `{{ token }}`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replacement {
+    /// the name of the placeholder
+    pub name: String,
+    /// the value that is replaced, it becomes the default of the placeholder
+    pub value: String,
+    target: Target,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Target {
+    /// the scheme, host and port of the url
+    Host,
+    /// a segment of the url path, like an id, by its index
+    PathSegment(usize),
+    /// the token of a bearer `Authorization` header
+    BearerToken,
+}
+
+const AUTHORIZATION: &str = "Authorization";
+const BEARER: &str = "Bearer ";
+
+impl Replacement {
+    /// the template that replaces the value, like `{{ base_url }}`
+    pub fn template(&self) -> String {
+        format!("{{{{ {} }}}}", self.name)
+    }
+
+    /// replaces the value by the placeholder, the value becomes its default
+    pub fn apply(&self, request: &mut HttpRequest) {
+        let template = self.template();
+        match self.target {
+            Target::Host => {
+                let url = request.url.as_ref();
+                if let Some(path) = url.strip_prefix(self.value.as_str()) {
+                    request.url = format!("{}{}", template, path).as_str().into();
+                }
+            }
+            Target::PathSegment(index) => {
+                let (origin, path, rest) = split_url(request.url.as_ref());
+                let path = path
+                    .split('/')
+                    .enumerate()
+                    .map(|(i, segment)| {
+                        if i == index {
+                            template.as_str()
+                        } else {
+                            segment
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("/");
+                request.url = format!("{}{}{}", origin, path, rest).as_str().into();
+            }
+            Target::BearerToken => {
+                let mut headers = HttpHeaders::default();
+                for (name, value) in request.headers.as_ref() {
+                    if name.eq_ignore_ascii_case(AUTHORIZATION) && value.starts_with(BEARER) {
+                        headers.push(name, format!("{}{}", BEARER, template));
+                    } else {
+                        headers.push(name, value);
+                    }
+                }
+                request.headers = headers;
+            }
+        }
+        request.placeholders.retain(|p| p.name != self.name);
+        request
+            .placeholders
+            .push(Placeholder::new(&self.name, &self.value));
+    }
+}
+
+/// the values of the `request` that look like they should be placeholders:
+/// the host, ids in the url path and bearer tokens
+pub fn detect_replacements(request: &HttpRequest) -> Vec<Replacement> {
+    let mut replacements = vec![];
+    let (origin, path, _) = split_url(request.url.as_ref());
+    if !origin.is_empty() && !origin.contains("{{") {
+        replacements.push(Replacement {
+            name: "base_url".to_string(),
+            value: origin.to_string(),
+            target: Target::Host,
+        });
+    }
+
+    let segments = path.split('/').collect::<Vec<_>>();
+    for (i, segment) in segments.iter().enumerate() {
+        if !is_id(segment) {
+            continue;
+        }
+        let name = match i.checked_sub(1).map(|i| segments[i]) {
+            Some(previous) if !previous.is_empty() && !is_id(previous) => {
+                let previous = previous.strip_suffix('s').unwrap_or(previous);
+                format!("{}_id", previous.replace(['-', '.'], "_"))
+            }
+            _ => "id".to_string(),
+        };
+        if replacements.iter().any(|r: &Replacement| r.name == name) {
+            continue;
+        }
+        replacements.push(Replacement {
+            name,
+            value: segment.to_string(),
+            target: Target::PathSegment(i),
+        });
+    }
+
+    if let Some(token) = request
+        .headers
+        .as_ref()
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(AUTHORIZATION))
+        .and_then(|(_, value)| value.strip_prefix(BEARER))
+        .filter(|token| !token.contains("{{"))
+    {
+        replacements.push(Replacement {
+            name: "token".to_string(),
+            value: token.to_string(),
+            target: Target::BearerToken,
+        });
+    }
+
+    replacements
+}
+
+/// splits an url into its origin, like `https://example.com`, its path and the query or fragment
+fn split_url(url: &str) -> (&str, &str, &str) {
+    let origin_len = url
+        .find("://")
+        .map(|i| {
+            let after = &url[i + 3..];
+            i + 3 + after.find(['/', '?', '#']).unwrap_or(after.len())
+        })
+        .unwrap_or(0);
+    let (origin, rest) = url.split_at(origin_len);
+    let path_len = rest.find(['?', '#']).unwrap_or(rest.len());
+    let (path, rest) = rest.split_at(path_len);
+
+    (origin, path, rest)
+}
+
+/// numbers and uuids look like ids
+fn is_id(segment: &str) -> bool {
+    let is_number = !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit());
+    let is_uuid = segment.len() == 36
+        && segment.chars().enumerate().all(|(i, c)| match i {
+            8 | 13 | 18 | 23 => c == '-',
+            _ => c.is_ascii_hexdigit(),
+        });
+
+    is_number || is_uuid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::http::HttpMethod;
+    use crate::test_utils;
+
+    fn request(url: &str) -> HttpRequest {
+        let mut headers = HttpHeaders::default();
+        headers.push("Accept", "application/json");
+        headers.push("Authorization", "Bearer eyJhbGciOi");
+        HttpRequest {
+            headers,
+            ..test_utils::request(url, HttpMethod::Get)
+        }
+    }
+
+    #[test]
+    fn should_detect_host_ids_and_bearer_tokens() {
+        let request =
+            request("https://api.example.com:8443/users/42/orders/0b8b2f4e-5d5a-4a53-9f2a-8d3c5b1d2e3f?page=2");
+
+        assert_eq!(
+            detect_replacements(&request)
+                .into_iter()
+                .map(|r| (r.name, r.value))
+                .collect::<Vec<_>>(),
+            [
+                ("base_url".into(), "https://api.example.com:8443".into()),
+                ("user_id".into(), "42".into()),
+                (
+                    "order_id".into(),
+                    "0b8b2f4e-5d5a-4a53-9f2a-8d3c5b1d2e3f".into()
+                ),
+                ("token".into(), "eyJhbGciOi".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_replace_values_by_placeholders() {
+        let mut request = request("https://api.example.com/users/42?page=2");
+        for replacement in detect_replacements(&request) {
+            replacement.apply(&mut request);
+        }
+
+        assert_eq!(
+            request.url.as_ref(),
+            "{{ base_url }}/users/{{ user_id }}?page=2"
+        );
+        assert_eq!(
+            request.headers.get("Authorization"),
+            Some("Bearer {{ token }}")
+        );
+        assert_eq!(
+            request.placeholders,
+            [
+                Placeholder::new("base_url", "https://api.example.com"),
+                Placeholder::new("user_id", "42"),
+                Placeholder::new("token", "eyJhbGciOi"),
+            ]
+        );
+    }
+}
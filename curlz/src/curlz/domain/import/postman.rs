@@ -0,0 +1,839 @@
+//! # Postman
+//! imports and exports [postman collections v2.1](https://schema.getpostman.com/json/collection/v2.1.0/collection.json),
+//! their `{{var}}` variables are already templates
+use crate::domain::bookmark::Bookmark;
+use crate::domain::http::{HttpAuth, HttpBody, HttpHeaders, HttpMethod, HttpRequest, HttpVersion};
+use crate::domain::import::{
+    basic_auth, dynamic_variables, parse_basic_auth, read_file, slugify, ImportFormat,
+    ImportedCollection,
+};
+use crate::template::variables::Placeholder;
+
+use anyhow::{anyhow, Context};
+use log::warn;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+const SCHEMA: &str = "https://schema.getpostman.com/json/collection/v2.1.0/collection.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Collection {
+    info: Info,
+    #[serde(default)]
+    item: Vec<Item>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auth: Option<Auth>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    variable: Vec<KeyValue>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Info {
+    name: String,
+    #[serde(default)]
+    schema: String,
+}
+
+/// either a folder with items, or a request
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Item {
+    name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    item: Vec<Item>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request: Option<Request>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auth: Option<Auth>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Request {
+    Url(String),
+    Full(Box<FullRequest>),
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FullRequest {
+    #[serde(default = "default_method")]
+    method: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    header: Vec<KeyValue>,
+    url: Url,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<Body>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    auth: Option<Auth>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Url {
+    Raw(String),
+    Parsed {
+        #[serde(default)]
+        raw: String,
+    },
+}
+
+impl Default for Url {
+    fn default() -> Self {
+        Self::Raw(String::new())
+    }
+}
+
+impl Url {
+    fn raw(&self) -> &str {
+        match self {
+            Self::Raw(raw) | Self::Parsed { raw } => raw,
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Body {
+    mode: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    raw: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    urlencoded: Vec<KeyValue>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    formdata: Vec<KeyValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    file: Option<FileBody>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    graphql: Option<Value>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct FileBody {
+    #[serde(default)]
+    src: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Auth {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    bearer: Vec<KeyValue>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    basic: Vec<KeyValue>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    digest: Vec<KeyValue>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    apikey: Vec<KeyValue>,
+}
+
+/// a header, variable, form field or auth parameter
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct KeyValue {
+    key: String,
+    #[serde(default)]
+    value: Value,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    disabled: bool,
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    kind: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    src: Option<Value>,
+}
+
+impl KeyValue {
+    fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: Value::String(value.into()),
+            ..Default::default()
+        }
+    }
+
+    fn text(&self) -> String {
+        match &self.value {
+            Value::String(s) => s.clone(),
+            Value::Null => String::new(),
+            other => other.to_string(),
+        }
+    }
+}
+
+/// the values of a postman environment export
+#[derive(Debug, Deserialize)]
+struct PostmanEnvironment {
+    #[serde(default)]
+    values: Vec<EnvironmentValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EnvironmentValue {
+    key: String,
+    #[serde(default)]
+    value: Value,
+    #[serde(default = "enabled")]
+    enabled: bool,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+/// a postman collection v2.1 file, with the values of an optional postman `environment` file
+#[derive(Debug, Default)]
+pub struct PostmanFormat {
+    pub environment: Option<PathBuf>,
+}
+
+impl ImportFormat for PostmanFormat {
+    fn import(&self, path: &Path) -> crate::Result<ImportedCollection> {
+        let environment = self.environment.as_deref().map(read_file).transpose()?;
+
+        import_postman(&read_file(path)?, environment.as_deref())
+    }
+}
+
+/// imports the requests of a postman `collection` as bookmarks, slugs follow the folders like `users/get-user`,
+/// the collection variables and the values of the postman `environment` become variables
+pub fn import_postman(
+    collection: &str,
+    environment: Option<&str>,
+) -> crate::Result<ImportedCollection> {
+    let collection: Collection =
+        serde_json::from_str(collection).context("invalid postman collection")?;
+    let mut imported = ImportedCollection::default();
+
+    imported.variables.extend(
+        collection
+            .variable
+            .iter()
+            .filter(|v| !v.disabled)
+            .map(|v| (variable_name(&v.key), v.text())),
+    );
+    if let Some(environment) = environment {
+        let environment: PostmanEnvironment =
+            serde_json::from_str(environment).context("invalid postman environment")?;
+        imported.variables.extend(
+            environment
+                .values
+                .into_iter()
+                .filter(|v| v.enabled)
+                .map(|v| {
+                    let value = KeyValue {
+                        value: v.value,
+                        ..Default::default()
+                    };
+                    (variable_name(&v.key), value.text())
+                }),
+        );
+    }
+
+    import_items(
+        &collection.item,
+        &[],
+        collection.auth.as_ref(),
+        &mut imported,
+    )?;
+
+    Ok(imported)
+}
+
+fn import_items(
+    items: &[Item],
+    folders: &[String],
+    auth: Option<&Auth>,
+    imported: &mut ImportedCollection,
+) -> crate::Result<()> {
+    for item in items {
+        let slug = slugify(&item.name);
+        let auth = item.auth.as_ref().or(auth);
+        match item.request.as_ref() {
+            None => {
+                let folders = [folders, &[slug]].concat();
+                import_items(&item.item, &folders, auth, imported)?;
+            }
+            Some(request) => {
+                let slug = [folders, &[slug]].concat().join("/");
+                let mut request = import_request(request, auth).with_context(|| {
+                    format!("cannot import the postman request `{}`", item.name)
+                })?;
+                request.placeholders = imported
+                    .variables
+                    .iter()
+                    .map(|(name, value)| Placeholder::new(name, value))
+                    .collect();
+                imported.push(slug, request);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn import_request(request: &Request, inherited: Option<&Auth>) -> crate::Result<HttpRequest> {
+    let request = match request {
+        Request::Url(url) => return Ok(http_request(HttpMethod::Get, url, HttpHeaders::default())),
+        Request::Full(request) => request,
+    };
+    if request.url.raw().is_empty() {
+        return Err(anyhow!("the request has no url"));
+    }
+
+    let mut headers = HttpHeaders::default();
+    for header in request.header.iter().filter(|h| !h.disabled) {
+        headers.push(&header.key, template(&header.text()));
+    }
+    let mut url = template(request.url.raw());
+    let mut http = http_request(HttpMethod::from_str(&request.method)?, "", headers);
+
+    if let Some(body) = request.body.as_ref() {
+        import_body(body, &mut http)?;
+    }
+    if let Some(auth) = request.auth.as_ref().or(inherited) {
+        import_auth(auth, &mut http, &mut url);
+    }
+    http.url = url.as_str().into();
+
+    Ok(http)
+}
+
+/// a postman text as template, its dynamic variables become template functions
+/// and its variables valid template variables, like `{{my_var}}` for `{{my-var}}`
+fn template(text: &str) -> String {
+    let variable = Regex::new(r"\{\{(\s*)([A-Za-z_][\w.-]*)(\s*)\}\}").unwrap();
+
+    variable
+        .replace_all(&dynamic_variables(text), |captures: &Captures| {
+            format!(
+                "{{{{{}{}{}}}}}",
+                &captures[1],
+                variable_name(&captures[2]),
+                &captures[3]
+            )
+        })
+        .to_string()
+}
+
+/// the name of a postman variable as template variable, `-` and `.` become `_`, like `my_var` for `my-var`
+fn variable_name(name: &str) -> String {
+    name.replace(['-', '.'], "_")
+}
+
+fn http_request(method: HttpMethod, url: &str, headers: HttpHeaders) -> HttpRequest {
+    HttpRequest {
+        url: url.into(),
+        method,
+        version: HttpVersion::Http11,
+        headers,
+        body: HttpBody::None,
+        curl_params: vec![],
+        placeholders: vec![],
+        auth: None,
+    }
+}
+
+fn import_body(body: &Body, request: &mut HttpRequest) -> crate::Result<()> {
+    let fields = |fields: &[KeyValue]| {
+        fields
+            .iter()
+            .filter(|f| !f.disabled)
+            .map(|f| (template(&f.key), template(&f.text())))
+            .collect::<Vec<_>>()
+    };
+
+    match body.mode.as_str() {
+        "raw" => {
+            request.body = HttpBody::InlineText(template(body.raw.as_deref().unwrap_or_default()))
+        }
+        "urlencoded" => {
+            let form = fields(&body.urlencoded)
+                .into_iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect::<Vec<_>>()
+                .join("&");
+            if request.headers.get("Content-Type").is_none() {
+                request
+                    .headers
+                    .push("Content-Type", "application/x-www-form-urlencoded");
+            }
+            request.body = HttpBody::InlineText(form);
+        }
+        "formdata" => {
+            // curl builds the multipart body
+            for field in body.formdata.iter().filter(|f| !f.disabled) {
+                let value = match (field.kind.as_deref(), field.src.as_ref()) {
+                    (Some("file"), Some(Value::String(src))) => format!("@{}", src),
+                    _ => template(&field.text()),
+                };
+                request
+                    .curl_params
+                    .extend(["-F".to_string(), format!("{}={}", field.key, value)]);
+            }
+            if request.method == HttpMethod::Get {
+                request.method = HttpMethod::Post;
+            }
+        }
+        "file" => {
+            let src = body
+                .file
+                .as_ref()
+                .map(|f| f.src.as_str())
+                .unwrap_or_default();
+            request.body = HttpBody::InlineText(format!(r#"{{{{ file("{}") }}}}"#, src));
+        }
+        "graphql" => {
+            let graphql = body.graphql.clone().unwrap_or_default();
+            let variables = match graphql.get("variables") {
+                Some(Value::String(variables)) if !variables.trim().is_empty() => {
+                    serde_json::from_str(variables).unwrap_or(Value::Null)
+                }
+                _ => Value::Null,
+            };
+            let query =
+                serde_json::json!({ "query": graphql.get("query"), "variables": variables });
+            if request.headers.get("Content-Type").is_none() {
+                request.headers.push("Content-Type", "application/json");
+            }
+            request.body = HttpBody::InlineText(template(&query.to_string()));
+        }
+        other => warn!("the postman body mode `{}` is not supported", other),
+    }
+
+    Ok(())
+}
+
+fn import_auth(auth: &Auth, request: &mut HttpRequest, url: &mut String) {
+    let param = |params: &[KeyValue], key: &str| {
+        params
+            .iter()
+            .find(|p| p.key == key)
+            .map(|p| template(&p.text()))
+            .unwrap_or_default()
+    };
+
+    match auth.kind.as_str() {
+        "noauth" => {}
+        "bearer" => request.headers.push(
+            "Authorization",
+            format!("Bearer {}", param(&auth.bearer, "token")),
+        ),
+        "basic" => request.headers.push(
+            "Authorization",
+            basic_auth(
+                &param(&auth.basic, "username"),
+                &param(&auth.basic, "password"),
+            ),
+        ),
+        "digest" => {
+            request.auth = Some(HttpAuth::Digest {
+                user: param(&auth.digest, "username"),
+                password: param(&auth.digest, "password"),
+            })
+        }
+        "apikey" => {
+            let key = param(&auth.apikey, "key");
+            let value = param(&auth.apikey, "value");
+            if param(&auth.apikey, "in") == "query" {
+                let separator = if url.contains('?') { '&' } else { '?' };
+                url.push_str(&format!("{}{}={}", separator, key, value));
+            } else {
+                request.headers.push(key, value);
+            }
+        }
+        other => warn!("the postman auth type `{}` is not supported", other),
+    }
+}
+
+/// exports the `bookmarks` as postman collection, the folders follow the slugs,
+/// the defaults of placeholders become collection variables
+pub fn export_postman(name: &str, bookmarks: &[Bookmark]) -> crate::Result<String> {
+    let mut collection = Collection {
+        info: Info {
+            name: name.to_string(),
+            schema: SCHEMA.to_string(),
+        },
+        ..Default::default()
+    };
+
+    for bookmark in bookmarks {
+        let request = &bookmark.request;
+        if !request.curl_params.is_empty() {
+            warn!(
+                "the curl params `{}` of the bookmark `{}` cannot be exported",
+                request.curl_params.join(" "),
+                bookmark.slug
+            );
+        }
+        for placeholder in &request.placeholders {
+            let value = placeholder.value.as_ref().or(placeholder.default.as_ref());
+            if let Some(value) = value {
+                if !collection
+                    .variable
+                    .iter()
+                    .any(|v| v.key == placeholder.name)
+                {
+                    collection
+                        .variable
+                        .push(KeyValue::new(&placeholder.name, value));
+                }
+            }
+        }
+
+        let mut segments = bookmark
+            .slug
+            .split('/')
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>();
+        let name = segments.pop().unwrap_or(bookmark.slug.as_str());
+        let mut items = &mut collection.item;
+        for folder in segments {
+            let index = match items
+                .iter()
+                .position(|i| i.request.is_none() && i.name == folder)
+            {
+                Some(index) => index,
+                None => {
+                    items.push(Item {
+                        name: folder.to_string(),
+                        ..Default::default()
+                    });
+                    items.len() - 1
+                }
+            };
+            items = &mut items[index].item;
+        }
+        items.push(Item {
+            name: name.to_string(),
+            request: Some(Request::Full(Box::new(export_request(
+                request,
+                &bookmark.slug,
+            )))),
+            ..Default::default()
+        });
+    }
+
+    serde_json::to_string_pretty(&collection).map_err(|e| e.into())
+}
+
+/// the postman request of the bookmark `slug`, a basic auth header becomes a postman basic auth,
+/// templates that postman does not know, like prompts, are exported as they are with a warning
+fn export_request(request: &HttpRequest, slug: &str) -> FullRequest {
+    let export = |template: &str| {
+        let exported = variables(template);
+        for unsupported in unsupported_templates(&exported) {
+            warn!(
+                "the template `{}` of the bookmark `{}` is unknown to postman, it is exported as it is",
+                unsupported, slug
+            );
+        }
+        exported
+    };
+
+    let mut auth = request
+        .auth
+        .as_ref()
+        .map(|HttpAuth::Digest { user, password }| Auth {
+            kind: "digest".to_string(),
+            digest: vec![
+                KeyValue::new("username", export(user)),
+                KeyValue::new("password", export(password)),
+            ],
+            ..Default::default()
+        });
+    let mut header = vec![];
+    for (key, value) in request.headers.as_ref() {
+        match parse_basic_auth(value) {
+            Some((user, password))
+                if auth.is_none() && key.eq_ignore_ascii_case("Authorization") =>
+            {
+                auth = Some(Auth {
+                    kind: "basic".to_string(),
+                    basic: vec![
+                        KeyValue::new("username", export(&user)),
+                        KeyValue::new("password", export(&password)),
+                    ],
+                    ..Default::default()
+                })
+            }
+            _ => header.push(KeyValue::new(key, export(value))),
+        }
+    }
+
+    FullRequest {
+        method: (&request.method).into(),
+        header,
+        url: Url::Raw(export(request.url.as_ref())),
+        body: match &request.body {
+            HttpBody::InlineText(text) => Some(Body {
+                mode: "raw".to_string(),
+                raw: Some(export(text)),
+                ..Default::default()
+            }),
+            _ => None,
+        },
+        auth,
+    }
+}
+
+/// turns plain variables, like `{{ base_url }}`, into postman variables like `{{base_url}}`,
+/// and template functions into their postman dynamic variables, like `{{ uuid() }}` into `{{$guid}}`,
+/// the reverse of [`dynamic_variables`]
+fn variables(template: &str) -> String {
+    let variable = Regex::new(r"\{\{\s*([A-Za-z_][\w.-]*)\s*\}\}").unwrap();
+    let function =
+        Regex::new(r"\{\{\s*(uuid\(\)|timestamp\(\)|random_int\(0, 1000\))\s*\}\}").unwrap();
+
+    let template = function.replace_all(template, |captures: &Captures| match &captures[1] {
+        "uuid()" => "{{$guid}}",
+        "timestamp()" => "{{$timestamp}}",
+        _ => "{{$randomInt}}",
+    });
+    variable.replace_all(&template, "{{$1}}").to_string()
+}
+
+/// the templates of an exported `text` that are neither variables nor dynamic variables, like `{{ prompt("id") }}`
+fn unsupported_templates(text: &str) -> Vec<&str> {
+    let template = Regex::new(r"\{\{.*?\}\}|\{%.*?%\}").unwrap();
+    let postman = Regex::new(r"^\{\{\$?[\w.-]+\}\}$").unwrap();
+
+    template
+        .find_iter(text)
+        .map(|matched| matched.as_str())
+        .filter(|matched| !postman.is_match(matched))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::environment::Environment;
+    use crate::template::Renderer;
+    use crate::test_utils;
+    use indoc::indoc;
+
+    const COLLECTION: &str = indoc! {r#"
+        {
+          "info": { "name": "Users API", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json" },
+          "auth": { "type": "bearer", "bearer": [{ "key": "token", "value": "{{token}}", "type": "string" }] },
+          "variable": [{ "key": "base_url", "value": "https://api.example.com" }],
+          "item": [
+            {
+              "name": "Users",
+              "item": [
+                {
+                  "name": "Get User",
+                  "request": {
+                    "method": "GET",
+                    "header": [
+                      { "key": "Accept", "value": "application/json" },
+                      { "key": "X-Debug", "value": "1", "disabled": true }
+                    ],
+                    "url": { "raw": "{{base_url}}/users/{{user_id}}", "host": ["{{base_url}}"] }
+                  }
+                },
+                {
+                  "name": "Create User",
+                  "request": {
+                    "method": "POST",
+                    "auth": { "type": "basic", "basic": [{ "key": "username", "value": "john" }, { "key": "password", "value": "{{password}}" }] },
+                    "url": "{{base_url}}/users",
+                    "body": { "mode": "raw", "raw": "{\"id\": \"{{$guid}}\"}" }
+                  }
+                }
+              ]
+            },
+            {
+              "name": "Login",
+              "request": {
+                "method": "POST",
+                "auth": { "type": "noauth" },
+                "url": "{{base_url}}/login",
+                "body": { "mode": "urlencoded", "urlencoded": [{ "key": "user", "value": "john" }, { "key": "pass", "value": "{{password}}" }] }
+              }
+            }
+          ]
+        }
+    "#};
+
+    const ENVIRONMENT: &str = indoc! {r#"
+        {
+          "name": "staging",
+          "values": [
+            { "key": "base_url", "value": "https://staging.example.com", "enabled": true },
+            { "key": "user_id", "value": "42", "enabled": true },
+            { "key": "unused", "value": "x", "enabled": false }
+          ]
+        }
+    "#};
+
+    #[test]
+    fn should_import_folders_requests_auth_and_bodies() {
+        let imported = import_postman(COLLECTION, None).unwrap();
+        let slugs = imported
+            .bookmarks
+            .iter()
+            .map(|b| b.slug.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(slugs, ["users/get-user", "users/create-user", "login"]);
+
+        let get = &imported.bookmarks[0].request;
+        assert_eq!(get.url.as_ref(), "{{base_url}}/users/{{user_id}}");
+        assert_eq!(
+            get.headers.as_ref(),
+            [
+                ("Accept".to_string(), "application/json".to_string()),
+                ("Authorization".to_string(), "Bearer {{token}}".to_string()),
+            ]
+        );
+        assert_eq!(
+            get.placeholders,
+            [Placeholder::new("base_url", "https://api.example.com")]
+        );
+
+        let create = &imported.bookmarks[1].request;
+        assert_eq!(create.method, HttpMethod::Post);
+        let mut env = Environment::default();
+        env.insert("password", "secret");
+        assert_eq!(
+            Renderer::new(&env)
+                .render(
+                    create.headers.get("Authorization").unwrap(),
+                    "authorization"
+                )
+                .unwrap(),
+            "Basic am9objpzZWNyZXQ="
+        );
+        assert_eq!(
+            create.body,
+            HttpBody::InlineText(r#"{"id": "{{ uuid() }}"}"#.to_string())
+        );
+
+        let login = &imported.bookmarks[2].request;
+        assert_eq!(login.headers.get("Authorization"), None);
+        assert_eq!(
+            login.body,
+            HttpBody::InlineText("user=john&pass={{password}}".to_string())
+        );
+    }
+
+    #[test]
+    fn should_import_the_variables_of_an_environment() {
+        let imported = import_postman(COLLECTION, Some(ENVIRONMENT)).unwrap();
+
+        assert_eq!(
+            imported.variables.into_iter().collect::<Vec<_>>(),
+            [
+                (
+                    "base_url".to_string(),
+                    "https://staging.example.com".to_string()
+                ),
+                ("user_id".to_string(), "42".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_turn_variable_names_into_valid_template_variables() {
+        let collection = indoc! {r#"
+            {
+              "info": { "name": "Keys" },
+              "variable": [{ "key": "api-key", "value": "abc" }],
+              "item": [{
+                "name": "Get",
+                "request": {
+                  "header": [{ "key": "X-Api-Key", "value": "{{api-key}}" }],
+                  "url": "https://example.com/users/{{user.id}}"
+                }
+              }]
+            }
+        "#};
+        let environment = r#"{ "values": [{ "key": "user.id", "value": "42" }] }"#;
+        let imported = import_postman(collection, Some(environment)).unwrap();
+
+        let request = &imported.bookmarks[0].request;
+        assert_eq!(
+            request.url.as_ref(),
+            "https://example.com/users/{{user_id}}"
+        );
+        assert_eq!(request.headers.get("X-Api-Key"), Some("{{api_key}}"));
+        assert_eq!(
+            imported.variables.into_iter().collect::<Vec<_>>(),
+            [
+                ("api_key".to_string(), "abc".to_string()),
+                ("user_id".to_string(), "42".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_export_bookmarks_into_folders() {
+        let imported = import_postman(COLLECTION, None).unwrap();
+        let exported = export_postman("Users API", &imported.bookmarks).unwrap();
+        let reimported = import_postman(&exported, None).unwrap();
+
+        let slugs = reimported
+            .bookmarks
+            .iter()
+            .map(|b| b.slug.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(slugs, ["users/get-user", "users/create-user", "login"]);
+        assert_eq!(
+            reimported.bookmarks[0].request.url,
+            imported.bookmarks[0].request.url
+        );
+        assert_eq!(
+            reimported.variables.get("base_url").map(String::as_str),
+            Some("https://api.example.com")
+        );
+    }
+
+    #[test]
+    fn should_export_plain_variables_in_postman_syntax() {
+        assert_eq!(
+            variables(r#"{{ base_url }}/users/{{id}}?ts={{ timestamp() }}&id={{uuid()}}"#),
+            "{{base_url}}/users/{{id}}?ts={{$timestamp}}&id={{$guid}}"
+        );
+    }
+
+    #[test]
+    fn should_find_templates_unknown_to_postman() {
+        assert_eq!(
+            unsupported_templates(r#"{{base_url}}/{{$guid}}/{{ prompt("id") }}{% if x %}"#),
+            [r#"{{ prompt("id") }}"#, "{% if x %}"]
+        );
+    }
+
+    #[test]
+    fn should_export_basic_auth_as_postman_auth() {
+        let request = export_request(
+            &HttpRequest {
+                headers: {
+                    let mut headers = HttpHeaders::default();
+                    headers.push("Authorization", basic_auth("john", "{{ password }}"));
+                    headers
+                },
+                ..test_utils::request("{{ base_url }}/users", HttpMethod::Get)
+            },
+            "users",
+        );
+
+        assert!(request.header.is_empty());
+        let auth = request.auth.unwrap();
+        assert_eq!(auth.kind, "basic");
+        assert_eq!(
+            auth.basic
+                .iter()
+                .map(|p| (p.key.as_str(), p.text()))
+                .collect::<Vec<_>>(),
+            [
+                ("username", "john".to_string()),
+                ("password", "{{password}}".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,103 @@
+use anyhow::bail;
+
+/// splits a shell command line into its words, like bash does,
+/// with `'single'`, `"double"` and `$'ansi-c'` quotes and `\` line continuations
+pub fn split_shell_words(line: &str) -> crate::Result<Vec<String>> {
+    let mut words = vec![];
+    let mut word: Option<String> = None;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                // a line continuation
+                Some('\n') => {}
+                Some('\r') if chars.peek() == Some(&'\n') => {
+                    chars.next();
+                }
+                Some(c) => word.get_or_insert_with(String::new).push(c),
+                None => bail!("the command ends with an escape `\\`"),
+            },
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => bail!("the command has an unclosed quote `'`"),
+                    }
+                }
+            }
+            '"' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some('\n') => {}
+                            Some(c @ ('"' | '\\' | '$' | '`')) => word.push(c),
+                            Some(c) => word.extend(['\\', c]),
+                            None => bail!("the command has an unclosed quote `\"`"),
+                        },
+                        Some(c) => word.push(c),
+                        None => bail!("the command has an unclosed quote `\"`"),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some('\\') => match chars.next() {
+                            Some('n') => word.push('\n'),
+                            Some('r') => word.push('\r'),
+                            Some('t') => word.push('\t'),
+                            Some(c) => word.push(c),
+                            None => bail!("the command has an unclosed quote `$'`"),
+                        },
+                        Some(c) => word.push(c),
+                        None => bail!("the command has an unclosed quote `$'`"),
+                    }
+                }
+            }
+            c if c.is_whitespace() => words.extend(word.take()),
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_split_quoted_words_and_line_continuations() {
+        let command = "curl 'https://example.com/a b' \\\n  -H \"Accept: \\\"json\\\"\" \\\r\n  --data-raw $'{\"a\":\\'1\\'}\\n' -d x\\ y ''";
+
+        assert_eq!(
+            split_shell_words(command).unwrap(),
+            [
+                "curl",
+                "https://example.com/a b",
+                "-H",
+                r#"Accept: "json""#,
+                "--data-raw",
+                "{\"a\":'1'}\n",
+                "-d",
+                "x y",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the command has an unclosed quote `'`")]
+    fn should_throw_on_unclosed_quotes() {
+        split_shell_words("curl 'https://example.com").unwrap();
+    }
+}
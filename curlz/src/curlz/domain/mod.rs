@@ -1,6 +1,9 @@
 pub mod bookmark;
+pub mod codegen;
+pub mod config;
 pub mod environment;
 pub mod http;
+pub mod import;
 pub mod request;
 
 #[cfg(feature = "x-http-lang")]
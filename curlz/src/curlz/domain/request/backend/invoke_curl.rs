@@ -1,65 +1,317 @@
 use self::curl_arg_conversions::IntoCurlArguments;
 use super::HttpBackend;
-use crate::domain::http::HttpBody;
+use crate::domain::http::{
+    DigestChallenge, DigestRequest, HttpAuth, HttpBody, HttpMethod, REQUEST_VARIABLE,
+};
 use crate::domain::request::{IssueRequest, RequestContext, Verbosity};
+use crate::template::Renderer;
 use crate::Result;
 
-use anyhow::Context;
+use anyhow::{anyhow, bail, ensure, Context};
 use log::debug;
+use minijinja::value::Value;
+use serde::Serialize;
+use std::io::Write;
 use std::process::{Command, Stdio};
 
+#[cfg(windows)]
+const NULL_DEVICE: &str = "NUL";
+#[cfg(not(windows))]
+const NULL_DEVICE: &str = "/dev/null";
+
+/// the curl params of a body or an output, they are left out of the digest challenge request
+const BODY_AND_OUTPUT_PARAMS: [&str; 8] = [
+    "--data",
+    "--data-binary",
+    "--data-urlencode",
+    "-F",
+    "-o",
+    "--output",
+    "-D",
+    "--dump-header",
+];
+
 #[derive(Default)]
 pub struct InvokeCurlBackend;
 
+/// the already rendered parts of a request,
+/// accessible as `request` by the templates that are rendered afterwards, e.g. headers
+#[derive(Serialize)]
+struct RenderedRequest<'a> {
+    method: &'a str,
+    url: &'a str,
+    body: &'a str,
+}
+
+/// all parts of a request, rendered by the templates
+struct RenderedParts {
+    url: String,
+    body: Option<String>,
+    curl_params: Vec<String>,
+    headers: Vec<String>,
+    /// the credentials of a digest challenge, as user and password
+    digest: Option<(String, String)>,
+}
+
 /// It knows haw to issue a `HttpRequest`
 impl HttpBackend for InvokeCurlBackend {
     fn issue(&self, req: &IssueRequest, context: &RequestContext) -> Result<()> {
         let request = req.request;
-        let mut renderer = context.renderer_with_placeholders(&request.placeholders);
-
-        let url = renderer.render(request.url.as_ref(), "url")?;
-        let _method: String = (&request.method).into();
+        let rendered = render_parts(req, context);
+        // unanswered prompts render as undefined, so they are reported first
+        context.ensure_answered()?;
+        let RenderedParts {
+            url,
+            body,
+            curl_params,
+            headers,
+            digest,
+        } = rendered?;
 
         let mut cmd = Command::new("curl");
         if req.verbosity.eq(&Verbosity::Silent) {
             cmd.arg("-s");
         }
-        let payload = if request.body.ne(&HttpBody::None) {
-            vec![
-                "--data".to_string(),
-                match &request.body {
-                    HttpBody::InlineText(s) => renderer.render(s.as_str(), "body")?,
-                    HttpBody::InlineBinary(_) => todo!("inline binary data not impl yet"),
-                    HttpBody::Extern(_) => todo!("external file data loading impl yet"),
-                    HttpBody::None => "".to_string(),
-                },
-            ]
-        } else {
-            vec![]
+        // the answer of a digest challenge goes to curl as config on stdin, so it is not on the command line
+        let config = match digest {
+            Some((user, password)) => {
+                ensure!(
+                    !curl_params.iter().any(|param| param.ends_with("@-")),
+                    "digest authentication needs stdin, it cannot be combined with data of stdin like `-d @-`"
+                );
+                let challenge =
+                    request_digest_challenge(&request.method, &url, &curl_params, &headers)?;
+                let method: String = (&request.method).into();
+                let authorization = challenge.authorization(
+                    &user,
+                    &password,
+                    &DigestRequest {
+                        method: method.as_str(),
+                        uri: &request_target(&url),
+                        body: body.as_deref().unwrap_or_default(),
+                    },
+                    &cnonce(),
+                );
+                cmd.args(["-K", "-"]);
+                Some(format!(
+                    "header = \"Authorization: {}\"\n",
+                    authorization.replace('\\', "\\\\").replace('"', "\\\"")
+                ))
+            }
+            None => None,
         };
+        // the body is sent as it is, curl neither reads a file of a leading `@` nor strips newlines
+        let payload = body
+            .map(|body| vec!["--data-raw".to_string(), body])
+            .unwrap_or_default();
 
         cmd.args(request.method.as_curl_parameter())
-            .args(
-                &request
-                    .curl_params
-                    .iter()
-                    .map(|s| renderer.render(s, "param"))
-                    .collect::<Result<Vec<_>>>()?,
-            )
-            .args(request.headers.as_ref().iter().flat_map(|(k, v)| {
-                let value = renderer.render(v, k).unwrap();
-                vec!["-H".to_string(), format!("{}: {}", k, value)]
-            }))
+            .args(&curl_params)
+            .args(headers.iter().flat_map(|header| ["-H", header.as_str()]))
             .arg(&url)
             .args(&payload);
 
         debug!("curl cmd: \n  {:?}", &cmd);
 
-        cmd.stdout(Stdio::inherit())
-            .stderr(Stdio::inherit())
-            .output()
-            .map(|_output| ())
-            .context("error when starting curl")
+        // otherwise stdin is left to curl, like for `-d @-`
+        cmd.stdin(if config.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::inherit()
+        })
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit());
+        let mut child = cmd.spawn().context("error when starting curl")?;
+        if let (Some(config), Some(mut stdin)) = (config, child.stdin.take()) {
+            stdin
+                .write_all(config.as_bytes())
+                .context("error when passing the config to curl")?;
+        }
+        child
+            .wait()
+            .map(|_status| ())
+            .context("error when running curl")
+    }
+}
+
+/// issues the request once without body and credentials, to get the digest challenge of the `401` response
+fn request_digest_challenge(
+    method: &HttpMethod,
+    url: &str,
+    curl_params: &[String],
+    headers: &[String],
+) -> Result<DigestChallenge> {
+    let mut params = curl_params.iter();
+    let mut preflight_params = vec![];
+    while let Some(param) = params.next() {
+        if BODY_AND_OUTPUT_PARAMS.contains(&param.as_str()) {
+            params.next();
+        } else {
+            preflight_params.push(param);
+        }
+    }
+
+    let mut cmd = Command::new("curl");
+    cmd.args(["-s", "-S", "-o", NULL_DEVICE, "-D", "-"])
+        .args(method.as_curl_parameter())
+        .args(preflight_params)
+        .args(headers.iter().flat_map(|header| ["-H", header.as_str()]))
+        .arg(url)
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit());
+    debug!("curl digest challenge cmd: \n  {:?}", &cmd);
+
+    let output = cmd.output().context("error when starting curl")?;
+    ensure!(
+        output.status.success(),
+        "the request of the digest challenge failed, curl exited with {}",
+        output.status
+    );
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .filter(|(name, _)| name.trim().eq_ignore_ascii_case("www-authenticate"))
+        .map(|(_, value)| value.trim())
+        .find(|value| value.to_lowercase().starts_with("digest "))
+        .ok_or_else(|| anyhow!("the server did not ask for digest authentication"))?
+        .parse()
+        .map_err(|e| anyhow!("invalid digest challenge from the server: {}", e))
+}
+
+/// the path and query of an url, e.g. `/dir/index.html?page=1` for `https://example.com/dir/index.html?page=1`
+fn request_target(url: &str) -> String {
+    let without_scheme = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    match without_scheme.find(['/', '?']) {
+        Some(i) if without_scheme[i..].starts_with('/') => without_scheme[i..].to_string(),
+        Some(i) => format!("/{}", &without_scheme[i..]),
+        None => "/".to_string(),
+    }
+}
+
+/// a random client nonce of a digest challenge
+fn cnonce() -> String {
+    hex::encode(rand::random::<[u8; 8]>())
+}
+
+/// renders all parts of the request, before anything is sent
+fn render_parts(req: &IssueRequest, context: &RequestContext) -> Result<RenderedParts> {
+    let request = req.request;
+    let mut parts = PartRenderer {
+        renderer: context.renderer_with_placeholders(&request.placeholders)?,
+        strict: context.is_strict(),
+        origin: req.origin.as_deref(),
+        undefined: vec![],
+    };
+
+    let url = parts.render(request.url.as_ref(), "url", || "url".to_string())?;
+    let method: String = (&request.method).into();
+    let body = match &request.body {
+        HttpBody::InlineText(s) => Some(parts.render(s.as_str(), "body", || "body".to_string())?),
+        HttpBody::InlineBinary(_) => bail!("inline binary bodies are not supported yet"),
+        HttpBody::Extern(_) => bail!("bodies from external files are not supported yet"),
+        HttpBody::None => None,
+    };
+    parts.renderer.inject_object(
+        REQUEST_VARIABLE,
+        Value::from_serializable(&RenderedRequest {
+            method: method.as_str(),
+            url: url.as_str(),
+            body: body.as_deref().unwrap_or_default(),
+        }),
+    );
+
+    let curl_params = request
+        .curl_params
+        .iter()
+        .map(|s| parts.render(s, "param", || format!("curl param `{}`", s)))
+        .collect::<Result<Vec<_>>>()?;
+    let headers = request
+        .headers
+        .as_ref()
+        .iter()
+        .map(|(k, v)| {
+            parts
+                .render(v, k, || format!("header `{}`", k))
+                .map(|value| format!("{}: {}", k, value))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let digest = match request.auth.as_ref() {
+        Some(HttpAuth::Digest { user, password }) => Some((
+            parts.render(user, "user", || "digest user".to_string())?,
+            parts.render(password, "password", || "digest password".to_string())?,
+        )),
+        None => None,
+    };
+    parts.ensure_defined()?;
+
+    Ok(RenderedParts {
+        url,
+        body,
+        curl_params,
+        headers,
+        digest,
+    })
+}
+
+/// renders the parts of a request one after another,
+/// in strict mode all undefined variables of all parts are reported at once
+struct PartRenderer<'source> {
+    renderer: Renderer<'source>,
+    strict: bool,
+    /// where the request comes from, like a bookmark or a http file
+    origin: Option<&'source str>,
+    /// the undefined variables by the part of the request they appear in
+    undefined: Vec<(String, Vec<String>)>,
+}
+
+impl<'source> PartRenderer<'source> {
+    /// a part with undefined variables is not rendered in strict mode, it stays empty
+    fn render(&mut self, template: &str, name: &str, part: impl Fn() -> String) -> Result<String> {
+        let origin = self.origin;
+        let context = || match origin {
+            Some(origin) => format!("cannot render the {} of {}", part(), origin),
+            None => format!("cannot render the {}", part()),
+        };
+
+        if self.strict {
+            let undefined = self
+                .renderer
+                .undefined_variables(template, name)
+                .with_context(context)?;
+            if !undefined.is_empty() {
+                self.undefined.push((part(), undefined));
+                return Ok(String::new());
+            }
+        }
+
+        self.renderer.render(template, name).with_context(context)
+    }
+
+    fn ensure_defined(&self) -> Result<()> {
+        if self.undefined.is_empty() {
+            return Ok(());
+        }
+
+        let parts = self
+            .undefined
+            .iter()
+            .map(|(part, variables)| {
+                let variables = variables
+                    .iter()
+                    .map(|v| format!("`{}`", v))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("  - {}: {}", part, variables)
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        bail!(
+            "the request refers to undefined variables, define them with `--define`, \
+             the env file or use `--lenient` to render them empty:\n{}",
+            parts
+        )
     }
 }
 
@@ -79,8 +331,30 @@ mod curl_arg_conversions {
     /// todo: not yet sure if that abstraction is really helpful or stands in the way
     impl IntoCurlArguments<Vec<String>, String> for HttpMethod {
         fn as_curl_parameter(&self) -> Vec<String> {
-            let method: String = self.into();
-            vec!["-X".to_string(), method]
+            match self {
+                // with `-X HEAD` curl waits for a body that never comes
+                HttpMethod::Head => vec!["-I".to_string()],
+                method => {
+                    let method: String = method.into();
+                    vec!["-X".to_string(), method]
+                }
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_extract_the_request_target() {
+        assert_eq!(
+            request_target("https://example.com/dir/index.html?page=1"),
+            "/dir/index.html?page=1"
+        );
+        assert_eq!(request_target("http://localhost:8080"), "/");
+        assert_eq!(request_target("http://localhost:8080?page=1"), "/?page=1");
+        assert_eq!(request_target("/relative"), "/relative");
+    }
+}
@@ -1,43 +1,58 @@
 use crate::domain::environment::Environment;
+use crate::template::session::RenderSession;
 use crate::template::variables::Placeholder;
 use crate::template::Renderer;
 
 /// processes all commands and keeps the application state
 pub struct RequestContext<'a> {
     environment: &'a Environment,
+    session: RenderSession,
 }
 
 impl<'a> RequestContext<'a> {
     pub fn new(environment: &'a Environment) -> Self {
-        Self { environment }
+        Self {
+            environment,
+            session: RenderSession::default(),
+        }
+    }
+
+    /// shares the given `session` with all renderers of this context
+    pub fn with_session(mut self, session: RenderSession) -> Self {
+        self.session = session;
+
+        self
     }
 
     pub fn environment(&self) -> &Environment {
         self.environment
     }
 
+    /// fails for prompts that could not be answered by the renderers so far,
+    /// so that a non-interactive run never issues a request with missing answers
+    pub fn ensure_answered(&self) -> crate::Result<()> {
+        self.session.answers().ensure_answered()
+    }
+
+    /// undefined variables are errors, unless the run is lenient
+    pub fn is_strict(&self) -> bool {
+        self.session.is_strict()
+    }
+
     /// creates a new renderer based on the inner ['Environment`]
-    pub fn renderer(&self) -> Renderer {
-        self.environment.into()
+    pub fn renderer(&self) -> Renderer<'_> {
+        Renderer::with_session(self.environment, &self.session)
     }
 
     /// creates a new renderer based on the inner [`Environment`]
-    /// and the provided `placeholders`
+    /// and the provided `placeholders`, placeholders without value are prompted for
     pub fn renderer_with_placeholders<'source>(
         &'source self,
         placeholders: &'source [Placeholder],
-    ) -> Renderer<'source> {
+    ) -> crate::Result<Renderer<'source>> {
         let mut r = self.renderer();
+        r.inject_placeholders(placeholders)?;
 
-        placeholders.iter().for_each(|placeholder| {
-            let value = placeholder
-                .value
-                .as_ref()
-                .unwrap_or_else(|| placeholder.default.as_ref().unwrap());
-
-            r.inject_variable(&placeholder.name, value.to_string());
-        });
-
-        r
+        Ok(r)
     }
 }
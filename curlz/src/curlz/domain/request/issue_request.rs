@@ -1,5 +1,4 @@
 use super::backend::*;
-use crate::domain::environment::Environment;
 use crate::domain::http::HttpRequest;
 use crate::domain::request::RequestContext;
 
@@ -12,11 +11,23 @@ pub enum Verbosity {
 pub struct IssueRequest<'r> {
     pub request: &'r HttpRequest,
     pub verbosity: Verbosity,
+    /// where the request comes from, like ``bookmark `get-users` ``, errors name it
+    pub origin: Option<String>,
 }
 
 impl<'r> IssueRequest<'r> {
     pub fn new(request: &'r HttpRequest, verbosity: Verbosity) -> Self {
-        Self { request, verbosity }
+        Self {
+            request,
+            verbosity,
+            origin: None,
+        }
+    }
+
+    pub fn with_origin(mut self, origin: impl Into<String>) -> Self {
+        self.origin = Some(origin.into());
+
+        self
     }
 }
 
@@ -24,23 +35,22 @@ impl<'r> IssueRequest<'r> {
 pub fn issue_request(
     req: IssueRequest<'_>,
     backend: &impl HttpBackend,
-    env: &Environment,
+    ctx: &RequestContext,
 ) -> crate::Result<()> {
-    let ctx = RequestContext::new(env);
-
-    backend.issue(&req, &ctx)
+    backend.issue(&req, ctx)
 }
 
 /// issues a request with the via curl
-pub fn issue_request_with_curl(req: IssueRequest<'_>, env: &Environment) -> crate::Result<()> {
-    let backend = InvokeCurlBackend::default();
+pub fn issue_request_with_curl(req: IssueRequest<'_>, ctx: &RequestContext) -> crate::Result<()> {
+    let backend = InvokeCurlBackend;
 
-    issue_request(req, &backend, env)
+    issue_request(req, &backend, ctx)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::environment::Environment;
     use crate::test_utils::sample_requests;
     use log::debug;
 
@@ -58,8 +68,8 @@ mod tests {
         let env = Environment::default();
         let req = sample_requests::post_request();
         let req = IssueRequest::new(&req, Verbosity::Verbose);
-        let backend = MockBackend::default();
-        let res = issue_request(req, &backend, &env);
+        let backend = MockBackend;
+        let res = issue_request(req, &backend, &RequestContext::new(&env));
 
         assert!(res.is_ok())
     }
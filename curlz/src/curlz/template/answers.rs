@@ -0,0 +1,229 @@
+use anyhow::bail;
+use log::warn;
+use minijinja::value::Value;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// the file name of remembered answers within the workspace folder
+pub const ANSWERS_FILE: &str = "answers.yml";
+
+/// the answers of prompts, by their label
+///
+/// an answer is given only once per run, opt-in answers are also remembered in a yaml file,
+/// like `.curlz/answers.yml`, and offered as default the next time
+#[derive(Clone, Default)]
+pub struct Answers {
+    given: Arc<Mutex<BTreeMap<String, Value>>>,
+    unanswered: Arc<Mutex<Vec<String>>>,
+    file: Option<PathBuf>,
+}
+
+impl Answers {
+    /// remembers answers in the given yaml `file`
+    pub fn with_file(mut self, file: impl Into<PathBuf>) -> Self {
+        self.file = Some(file.into());
+
+        self
+    }
+
+    /// the answer that was already given in this run
+    pub fn given(&self, label: &str) -> Option<Value> {
+        self.given.lock().unwrap().get(label).cloned()
+    }
+
+    /// the answer that was remembered in a previous run
+    pub fn remembered(&self, label: &str) -> Option<Value> {
+        let file = self.file.as_ref()?;
+
+        load(file)
+            .remove(label)
+            .map(|v| Value::from_serializable(&v))
+    }
+
+    /// records the answer of a prompt for the rest of this run,
+    /// with `remember` it is stored for the next runs too
+    pub fn give(&self, label: &str, answer: Value, remember: bool) -> crate::Result<()> {
+        self.given
+            .lock()
+            .unwrap()
+            .insert(label.to_string(), answer.clone());
+
+        match self.file.as_ref() {
+            Some(file) if remember => {
+                let mut answers = load(file);
+                answers.insert(label.to_string(), serde_yaml::to_value(&answer)?);
+                if let Some(dir) = file.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                std::fs::write(file, serde_yaml::to_string(&answers)?)?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// records a prompt that could not be answered, because the run is not interactive
+    pub fn unanswered(&self, label: &str) {
+        let mut unanswered = self.unanswered.lock().unwrap();
+        if !unanswered.iter().any(|l| l == label) {
+            unanswered.push(label.to_string());
+        }
+    }
+
+    /// the labels of all prompts that could not be answered so far, they are forgotten afterwards
+    pub fn take_unanswered(&self) -> Vec<String> {
+        std::mem::take(&mut *self.unanswered.lock().unwrap())
+    }
+
+    /// an error listing all prompts that could not be answered, because the run is not interactive
+    pub fn ensure_answered(&self) -> crate::Result<()> {
+        let unanswered = self.take_unanswered();
+        if unanswered.is_empty() {
+            return Ok(());
+        }
+
+        let prompts = unanswered
+            .iter()
+            .map(|label| {
+                let names = variable_names(label)
+                    .iter()
+                    .map(|name| format!("`{name}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!(
+                    "  - `{label}`: define one of {names} or the env var `{}`",
+                    env_var_name(label)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        bail!(
+            "the following prompts cannot be asked in a non-interactive run, \
+             answer them with `--define`, the env file or env vars:\n{prompts}"
+        )
+    }
+}
+
+/// the names of variables that can answer a prompt, e.g. `GitHub Username`, `github_username`
+/// and `GITHUB_USERNAME` for the label `GitHub Username`
+pub fn variable_names(label: &str) -> Vec<String> {
+    let snake_case = label
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("_");
+    let mut names = vec![
+        label.to_string(),
+        snake_case.clone(),
+        snake_case.to_uppercase(),
+    ];
+    names.dedup();
+    names.retain(|name| !name.is_empty());
+
+    names
+}
+
+/// the process env var that answers a prompt of the `label`, like `CURLZ_API_TOKEN` for `api_token`
+pub fn env_var_name(label: &str) -> String {
+    format!(
+        "CURLZ_{}",
+        label
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_uppercase)
+            .collect::<Vec<_>>()
+            .join("_")
+    )
+}
+
+fn load(file: &Path) -> BTreeMap<String, serde_yaml::Value> {
+    if !file.exists() {
+        return BTreeMap::default();
+    }
+
+    std::fs::read_to_string(file)
+        .map_err(anyhow::Error::from)
+        .and_then(|yaml| serde_yaml::from_str(&yaml).map_err(Into::into))
+        .unwrap_or_else(|e| {
+            warn!("ignoring remembered answers of {}: {}", file.display(), e);
+            BTreeMap::default()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Tenant", &["Tenant", "tenant", "TENANT"])]
+    #[case("GitHub Username", &["GitHub Username", "github_username", "GITHUB_USERNAME"])]
+    #[case("api_key", &["api_key", "API_KEY"])]
+    fn should_name_variables_after_the_label(#[case] label: &str, #[case] expected: &[&str]) {
+        assert_eq!(variable_names(label), expected);
+    }
+
+    #[test]
+    fn should_give_answers_once_per_run() {
+        let answers = Answers::default();
+        answers.give("Tenant", Value::from("acme"), false).unwrap();
+
+        assert_eq!(answers.given("Tenant"), Some(Value::from("acme")));
+        assert_eq!(answers.clone().given("Tenant"), Some(Value::from("acme")));
+        assert_eq!(answers.given("Region"), None);
+        assert_eq!(answers.remembered("Tenant"), None);
+    }
+
+    #[test]
+    fn should_take_unanswered_prompts_once() {
+        let answers = Answers::default();
+        answers.unanswered("Tenant");
+        answers.unanswered("Region");
+        answers.unanswered("Tenant");
+
+        assert_eq!(answers.take_unanswered(), ["Tenant", "Region"]);
+        assert!(answers.take_unanswered().is_empty());
+    }
+
+    #[test]
+    fn should_list_unanswered_prompts_with_their_variables() {
+        let answers = Answers::default();
+        answers.ensure_answered().unwrap();
+        answers.unanswered("Tenant");
+        answers.unanswered("api_key");
+
+        assert_eq!(
+            answers.ensure_answered().unwrap_err().to_string(),
+            "the following prompts cannot be asked in a non-interactive run, \
+             answer them with `--define`, the env file or env vars:\n  \
+             - `Tenant`: define one of `Tenant`, `tenant`, `TENANT` or the env var `CURLZ_TENANT`\n  \
+             - `api_key`: define one of `api_key`, `API_KEY` or the env var `CURLZ_API_KEY`"
+        );
+        answers.ensure_answered().unwrap();
+    }
+
+    #[test]
+    fn should_remember_answers_for_the_next_run() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join(".curlz").join("answers.yml");
+
+        let answers = Answers::default().with_file(&file);
+        answers.give("Tenant", Value::from("acme"), true).unwrap();
+        answers.give("Port", Value::from(8080), true).unwrap();
+        answers.give("Token", Value::from("secret"), false).unwrap();
+
+        let next_run = Answers::default().with_file(&file);
+        assert_eq!(next_run.given("Tenant"), None);
+        assert_eq!(next_run.remembered("Tenant"), Some(Value::from("acme")));
+        assert_eq!(next_run.remembered("Port"), Some(Value::from(8080)));
+        assert_eq!(next_run.remembered("Token"), None);
+        assert_eq!(
+            std::fs::read_to_string(file).unwrap(),
+            "Port: 8080\nTenant: acme\n"
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use anyhow::{anyhow, bail};
+use std::collections::HashMap;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+/// the shell commands templates may run by `exec()`
+///
+/// only allowlisted commands run, and each command runs only once per run,
+/// no matter how many headers or requests use its output
+#[derive(Clone, Default)]
+pub struct Commands {
+    allowed: Vec<String>,
+    outputs: Arc<Mutex<HashMap<Vec<String>, String>>>,
+}
+
+impl Commands {
+    /// allows templates to run the commands `allowed`, like `vault` or `gcloud`
+    pub fn with_allowed(mut self, allowed: Vec<String>) -> Self {
+        self.allowed = allowed;
+
+        self
+    }
+
+    /// runs `command` with `args` and returns its trimmed stdout
+    pub fn run(&self, command: &str, args: &[String]) -> crate::Result<String> {
+        if !self.allowed.iter().any(|allowed| allowed == command) {
+            bail!(
+                "the command `{command}` is not allowed, add it to `exec.allow` of the project config `.curlz/config.yml`"
+            );
+        }
+
+        let key = std::iter::once(command.to_string())
+            .chain(args.iter().cloned())
+            .collect::<Vec<_>>();
+        if let Some(output) = self.outputs.lock().unwrap().get(&key) {
+            return Ok(output.clone());
+        }
+
+        let line = key.join(" ");
+        let output = Command::new(command)
+            .args(args)
+            .output()
+            .map_err(|e| anyhow!("the command `{line}` cannot be run: {e}"))?;
+        if !output.status.success() {
+            bail!(
+                "the command `{line}` failed with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        self.outputs.lock().unwrap().insert(key, stdout.clone());
+
+        Ok(stdout)
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn sh(script: &str) -> Vec<String> {
+        vec!["-c".to_string(), script.to_string()]
+    }
+
+    #[test]
+    fn should_return_the_trimmed_stdout() {
+        let commands = Commands::default().with_allowed(vec!["echo".into()]);
+
+        assert_eq!(
+            commands.run("echo", &["  s3cr3t ".into()]).unwrap(),
+            "s3cr3t"
+        );
+    }
+
+    #[test]
+    fn should_run_a_command_only_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        let counter = tmp.path().join("counter");
+        let script = sh(&format!("echo run >> {0}; wc -l < {0}", counter.display()));
+        let commands = Commands::default().with_allowed(vec!["sh".into()]);
+
+        assert_eq!(commands.run("sh", &script).unwrap(), "1");
+        assert_eq!(commands.clone().run("sh", &script).unwrap(), "1");
+    }
+
+    #[test]
+    #[should_panic(expected = "the command `echo` is not allowed, add it to `exec.allow`")]
+    fn should_throw_on_commands_that_are_not_allowed() {
+        Commands::default().run("echo", &[]).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "failed with exit status: 3: token expired")]
+    fn should_throw_with_stderr_on_failure() {
+        Commands::default()
+            .with_allowed(vec!["sh".into()])
+            .run("sh", &sh("echo 'token expired' >&2; exit 3"))
+            .unwrap();
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+use std::ops::Range;
+
+/// an error of a template, that shows the template source with the failing span highlighted
+///
+/// ```text
+/// unknown function: base64 is unknown
+///    |
+///  1 | Bearer {{ base64(token) }}
+///    |           ^^^^^^^^^^^^^
+/// ```
+#[derive(Debug)]
+pub struct TemplateError {
+    reason: String,
+    source: String,
+    line: Option<usize>,
+    /// the columns of the failing span within the `line`
+    span: Option<Range<usize>>,
+}
+
+impl TemplateError {
+    /// the error `e` of the template `name` with the given `source`
+    pub fn new(e: minijinja::Error, name: &str, source: &str) -> Self {
+        let in_template = e.name() == Some(name);
+        let reason = e.to_string();
+        // errors of imported templates, like of the template library, keep the template name
+        let reason = match e.line() {
+            Some(line) if in_template => reason
+                .strip_suffix(&format!(" (in {name}:{line})"))
+                .map(str::to_string)
+                .unwrap_or(reason),
+            _ => reason,
+        };
+
+        Self {
+            reason,
+            source: source.to_string(),
+            line: e.line().filter(|_| in_template),
+            span: span_of(&e).filter(|_| in_template),
+        }
+    }
+}
+
+/// minijinja keeps the span of an error private, but it prints it as `^^^` marker in its debug info,
+/// the referenced variables of the debug info are never shown, because they might be secrets,
+/// the format of the debug info is not stable, that is why minijinja is pinned
+fn span_of(e: &minijinja::Error) -> Option<Range<usize>> {
+    let debug_info = format!("{e:#}");
+    let marker = debug_info
+        .lines()
+        .find_map(|line| line.strip_prefix("     i "))?;
+    let start = marker.len() - marker.trim_start_matches(' ').len();
+    let len = marker[start..].len() - marker[start..].trim_start_matches('^').len();
+
+    (len > 0).then_some(start..start + len)
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.reason)?;
+
+        let Some(line) = self.line else {
+            return Ok(());
+        };
+        let Some(code) = self.source.lines().nth(line - 1) else {
+            return Ok(());
+        };
+        let width = line.to_string().len();
+        write!(f, "\n{:width$} |\n{line} | {code}", "")?;
+        if let Some(span) = self.span.as_ref() {
+            write!(
+                f,
+                "\n{:width$} | {}{}",
+                "",
+                " ".repeat(span.start),
+                "^".repeat(span.len())
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use minijinja::Environment;
+
+    fn render_error(source: &str) -> TemplateError {
+        let mut env = Environment::new();
+        env.set_debug(true);
+        env.add_function("secret", || "s3cr3t");
+        let e = env
+            .render_named_str("header", source, ())
+            .expect_err("the template should fail");
+
+        TemplateError::new(e, "header", source)
+    }
+
+    #[test]
+    fn should_highlight_the_failing_span() {
+        assert_eq!(
+            render_error("Bearer {{ secret() }}\n{{ base64(token) }}").to_string(),
+            "unknown function: base64 is unknown
+  |
+2 | {{ base64(token) }}
+  |    ^^^^^^^^^^^^^"
+        );
+    }
+
+    /// fails when an update of minijinja changes the format of its debug info
+    #[test]
+    fn should_find_the_span_in_the_debug_info() {
+        let mut env = Environment::new();
+        env.set_debug(true);
+        let e = env
+            .render_named_str("header", "{{ nope(1) }}", ())
+            .expect_err("the template should fail");
+
+        assert_eq!(
+            span_of(&e),
+            Some(3..10),
+            "the `^^^` marker is gone from the debug info of minijinja:\n{e:#}"
+        );
+    }
+
+    #[test]
+    fn should_show_the_line_of_syntax_errors() {
+        let e = render_error("Bearer {{ secret( }}").to_string();
+
+        assert!(e.starts_with("syntax error: "), "{e}");
+        assert!(e.contains("\n1 | Bearer {{ secret( }}"), "{e}");
+        assert!(!e.contains("(in header:1)"), "{e}");
+    }
+
+    #[test]
+    fn should_never_show_the_variables() {
+        let e = render_error("{% set token = secret() %}{{ token | nope }}").to_string();
+
+        assert!(!e.contains("s3cr3t"), "{e}");
+    }
+}
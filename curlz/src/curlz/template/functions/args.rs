@@ -0,0 +1,80 @@
+use minijinja::value::{ArgType, Kwargs, Value};
+use minijinja::Error;
+
+/// optional arguments of a template function, that can be provided
+/// either positional or as keyword arguments, like `totp(secret, 8)` or `totp(secret, digits=8)`
+pub struct Arguments<'a> {
+    args: &'a [Value],
+    kwargs: Kwargs,
+}
+
+impl<'a> Arguments<'a> {
+    pub fn new(args: &'a [Value]) -> Self {
+        let (args, kwargs) = Kwargs::from_args(args);
+
+        Self { args, kwargs }
+    }
+
+    /// looks up the argument at the positional `index` first, and by its keyword `name` second
+    pub fn get<T>(&self, index: usize, name: &str) -> Result<Option<T>, Error>
+    where
+        T: for<'x> ArgType<'x, Output = T>,
+    {
+        match self.args.get(index) {
+            Some(value) => Option::<T>::from_value(Some(value)),
+            None => self.kwargs.get::<Option<T>>(name),
+        }
+    }
+
+    /// looks up an argument that can only be provided by its keyword `name`
+    pub fn keyword<T>(&self, name: &str) -> Result<Option<T>, Error>
+    where
+        T: for<'x> ArgType<'x, Output = T>,
+    {
+        self.kwargs.get::<Option<T>>(name)
+    }
+
+    /// makes sure that no unknown keyword arguments are provided
+    pub fn assert_all_used(&self) -> Result<(), Error> {
+        self.kwargs.assert_all_used()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RenderBuilder;
+    use minijinja::value::Rest;
+    use minijinja::State;
+
+    fn greet(_: &State, args: Rest<Value>) -> Result<String, Error> {
+        let args = Arguments::new(&args);
+        let greeting: String = args.get(0, "greeting")?.unwrap_or("Hello".into());
+        let name: String = args.get(1, "name")?.unwrap_or("World".into());
+        args.assert_all_used()?;
+
+        Ok(format!("{greeting} {name}"))
+    }
+
+    #[test]
+    fn should_take_positional_and_keyword_arguments() {
+        let render = |template| {
+            RenderBuilder::new()
+                .with_function("greet", greet)
+                .render(template)
+        };
+
+        assert_eq!(render(r#"{{ greet() }}"#), "Hello World");
+        assert_eq!(render(r#"{{ greet("Hi") }}"#), "Hi World");
+        assert_eq!(render(r#"{{ greet("Hi", name="Bob") }}"#), "Hi Bob");
+        assert_eq!(render(r#"{{ greet(name="Bob") }}"#), "Hello Bob");
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown keyword argument 'nmae'")]
+    fn should_throw_on_unknown_keyword_arguments() {
+        RenderBuilder::new()
+            .with_function("greet", greet)
+            .render(r#"{{ greet(nmae="Bob") }}"#);
+    }
+}
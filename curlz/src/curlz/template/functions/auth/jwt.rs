@@ -208,7 +208,7 @@ mod tests {
                 iat: i64,
             }
 
-            let jwt = jwt.as_str().split(' ').last().unwrap();
+            let jwt = jwt.as_str().split(' ').next_back().unwrap();
             decode::<Claims>(
                 jwt,
                 &DecodingKey::from_secret(JWT_SECRET_KEY.as_bytes()),
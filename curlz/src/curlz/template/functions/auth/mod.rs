@@ -1,12 +1,18 @@
+use crate::template::functions::chrono::clock::SystemClock;
+
 use minijinja::Environment;
 
 mod basic;
 mod jwt;
+mod otp;
 
 use basic::basic;
 use jwt::jwt;
+use otp::{hotp, totp};
 
 pub fn register_functions(env: &mut Environment) {
     env.add_function("jwt", jwt);
     env.add_function("basic", basic);
+    env.add_function("totp", totp(SystemClock));
+    env.add_function("hotp", hotp);
 }
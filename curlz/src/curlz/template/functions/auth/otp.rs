@@ -0,0 +1,228 @@
+//! one-time passwords as described in [RFC 4226 (HOTP)](https://www.rfc-editor.org/rfc/rfc4226)
+//! and [RFC 6238 (TOTP)](https://www.rfc-editor.org/rfc/rfc6238)
+
+use crate::template::functions::args::Arguments;
+use crate::template::functions::chrono::clock::Clock;
+use crate::template::functions::crypto::sign;
+
+use hmac::Hmac;
+use minijinja::value::{Rest, Value};
+use minijinja::{Error, ErrorKind, State};
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+
+const DEFAULT_DIGITS: u32 = 6;
+const DEFAULT_PERIOD: u64 = 30;
+const DEFAULT_ALGORITHM: &str = "SHA1";
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// creates the `totp` template function, the current time is taken from the given `clock`
+///
+/// `totp(secret, digits=6, period=30, algorithm="SHA1")`
+pub(super) fn totp(
+    clock: impl Clock,
+) -> impl Fn(&State, &str, Rest<Value>) -> Result<String, Error> + Send + Sync + 'static {
+    move |_, secret, args| {
+        let args = Arguments::new(&args);
+        let digits = args.get(0, "digits")?.unwrap_or(DEFAULT_DIGITS);
+        let period = args.get(1, "period")?.unwrap_or(DEFAULT_PERIOD);
+        let algorithm: String = args
+            .get(2, "algorithm")?
+            .unwrap_or_else(|| DEFAULT_ALGORITHM.to_string());
+        args.assert_all_used()?;
+
+        if period == 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidOperation,
+                "totp `period` must be at least 1 second",
+            ));
+        }
+        let counter = clock.now().timestamp().max(0) as u64 / period;
+
+        one_time_password(secret, counter, digits, &algorithm)
+    }
+}
+
+/// generates a counter based one-time password
+///
+/// `hotp(secret, counter, digits=6, algorithm="SHA1")`
+pub(super) fn hotp(
+    _: &State,
+    secret: &str,
+    counter: u64,
+    args: Rest<Value>,
+) -> Result<String, Error> {
+    let args = Arguments::new(&args);
+    let digits = args.get(0, "digits")?.unwrap_or(DEFAULT_DIGITS);
+    let algorithm: String = args
+        .get(1, "algorithm")?
+        .unwrap_or_else(|| DEFAULT_ALGORITHM.to_string());
+    args.assert_all_used()?;
+
+    one_time_password(secret, counter, digits, &algorithm)
+}
+
+fn one_time_password(
+    secret: &str,
+    counter: u64,
+    digits: u32,
+    algorithm: &str,
+) -> Result<String, Error> {
+    if !(1..=10).contains(&digits) {
+        return Err(Error::new(
+            ErrorKind::InvalidOperation,
+            "one-time password `digits` must be between 1 and 10",
+        ));
+    }
+
+    let key = decode_base32(secret)?;
+    let message = counter.to_be_bytes();
+    let mac = match algorithm.to_uppercase().replace('-', "").as_str() {
+        "SHA1" => sign::<Hmac<Sha1>>(&key, &message),
+        "SHA256" => sign::<Hmac<Sha256>>(&key, &message),
+        "SHA512" => sign::<Hmac<Sha512>>(&key, &message),
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidOperation,
+                format!(
+                    "unsupported one-time password algorithm `{algorithm}`, use one of `SHA1`, `SHA256` or `SHA512`"
+                ),
+            ))
+        }
+    };
+
+    // dynamic truncation, see RFC 4226 section 5.3
+    let offset = (mac[mac.len() - 1] & 0x0f) as usize;
+    let code = u32::from_be_bytes([
+        mac[offset] & 0x7f,
+        mac[offset + 1],
+        mac[offset + 2],
+        mac[offset + 3],
+    ]) as u64;
+    let code = code % 10_u64.pow(digits);
+
+    Ok(format!("{:0width$}", code, width = digits as usize))
+}
+
+/// decodes a base32 (RFC 4648) secret, whitespaces and padding are ignored
+fn decode_base32(secret: &str) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::with_capacity(secret.len() * 5 / 8);
+    let mut buffer = 0_u32;
+    let mut bits = 0_u32;
+
+    for c in secret.chars().filter(|c| !c.is_whitespace() && *c != '=') {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|a| *a as char == c.to_ascii_uppercase())
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidOperation,
+                    format!("the one-time password secret is not base32 encoded, found `{c}`"),
+                )
+            })?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::functions::chrono::clock::FixedClock;
+    use crate::test_utils::RenderBuilder;
+    use rstest::rstest;
+
+    /// the ascii secret `12345678901234567890` as used by the RFCs
+    const SECRET_SHA1: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+    const SECRET_SHA256: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZA====";
+    const SECRET_SHA512: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQGEZDGNA=";
+
+    /// test vectors of RFC 6238 appendix B
+    #[rstest]
+    #[case(59, SECRET_SHA1, "SHA1", "94287082")]
+    #[case(59, SECRET_SHA256, "SHA256", "46119246")]
+    #[case(59, SECRET_SHA512, "SHA512", "90693936")]
+    #[case(1111111109, SECRET_SHA1, "SHA1", "07081804")]
+    #[case(1234567890, SECRET_SHA256, "SHA256", "91819424")]
+    #[case(20000000000, SECRET_SHA512, "SHA512", "47863826")]
+    fn should_generate_totp_at_a_given_time(
+        #[case] timestamp: i64,
+        #[case] secret: &str,
+        #[case] algorithm: &str,
+        #[case] expected: &str,
+    ) {
+        let e = RenderBuilder::new()
+            .with_function("totp", totp(FixedClock::at_timestamp(timestamp)))
+            .with_env_var("secret", secret)
+            .with_env_var("algorithm", algorithm);
+
+        assert_eq!(
+            e.render(r#"{{ totp(secret, digits=8, algorithm=algorithm) }}"#),
+            expected
+        );
+    }
+
+    #[test]
+    fn should_default_to_6_digits_and_30_seconds() {
+        let render = |timestamp| {
+            RenderBuilder::new()
+                .with_function("totp", totp(FixedClock::at_timestamp(timestamp)))
+                .render(r#"{{ totp("GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ") }}"#)
+        };
+
+        assert_eq!(render(59), "287082");
+        assert_eq!(render(60), render(89));
+        assert_ne!(render(89), render(90));
+    }
+
+    #[test]
+    fn should_take_positional_arguments() {
+        let e = RenderBuilder::new()
+            .with_function("totp", totp(FixedClock::at_timestamp(59)))
+            .with_env_var("secret", SECRET_SHA256);
+
+        assert_eq!(
+            e.render(r#"{{ totp(secret, 8, 30, "SHA256") }}"#),
+            "46119246"
+        );
+    }
+
+    /// test vectors of RFC 4226 appendix D
+    #[rstest]
+    #[case(0, "755224")]
+    #[case(1, "287082")]
+    #[case(9, "520489")]
+    fn should_generate_hotp(#[case] counter: u64, #[case] expected: &str) {
+        let e = RenderBuilder::new()
+            .with_function("hotp", hotp)
+            .with_env_var("counter", counter);
+
+        assert_eq!(
+            e.render(r#"{{ hotp("gezd gnbv gy3t qojq gezd gnbv gy3t qojq", counter) }}"#),
+            expected
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the one-time password secret is not base32 encoded, found `1`")]
+    fn should_throw_on_invalid_secrets() {
+        RenderBuilder::new()
+            .with_function("totp", totp(FixedClock::at_timestamp(59)))
+            .render(r#"{{ totp("12345678") }}"#);
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported one-time password algorithm `MD5`")]
+    fn should_throw_on_unsupported_algorithms() {
+        RenderBuilder::new()
+            .with_function("totp", totp(FixedClock::at_timestamp(59)))
+            .render(r#"{{ totp("GEZDGNBV", algorithm="MD5") }}"#);
+    }
+}
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+
+/// tells the current point in time, it can be replaced by a fixed clock in tests
+pub trait Clock: Send + Sync + 'static {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// the clock of the operating system
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// a clock that is stopped at a given point in time
+#[cfg(test)]
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+#[cfg(test)]
+impl FixedClock {
+    /// a clock stopped at the given UNIX timestamp
+    pub fn at_timestamp(timestamp: i64) -> Self {
+        use chrono::TimeZone;
+
+        Self(Utc.timestamp_opt(timestamp, 0).unwrap())
+    }
+}
+
+#[cfg(test)]
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
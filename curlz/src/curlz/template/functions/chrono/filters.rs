@@ -0,0 +1,114 @@
+use crate::template::functions::args::Arguments;
+use crate::template::functions::chrono::format::{format_date, in_timezone, parse_date};
+use chrono::{DateTime, Duration, FixedOffset, SecondsFormat, Utc};
+use minijinja::value::{Rest, Value, ValueKind};
+use minijinja::{Error, ErrorKind, State};
+
+/// adds a duration like `2h` or `1day 30min` to a date,
+/// a UNIX timestamp stays a timestamp and everything else becomes RFC 3339
+///
+/// `now() | add("2h")`
+pub(super) fn add(_: &State, value: Value, duration: &str) -> Result<Value, Error> {
+    shift(value, parse_duration(duration)?)
+}
+
+/// subtracts a duration like `2h` or `1day 30min` from a date,
+/// a UNIX timestamp stays a timestamp and everything else becomes RFC 3339
+///
+/// `now() | sub("7days")`
+pub(super) fn sub(_: &State, value: Value, duration: &str) -> Result<Value, Error> {
+    shift(value, -parse_duration(duration)?)
+}
+
+/// formats a date, or UNIX timestamp, with the given format and optional time zone
+///
+/// `"2024-01-01" | date("%s")`, `timestamp() | date("http")`, `now() | date(tz="Asia/Tokyo")`
+pub(super) fn date(_: &State, value: Value, args: Rest<Value>) -> Result<String, Error> {
+    let args = Arguments::new(&args);
+    let format: Option<String> = args.get(0, "format")?;
+    let tz: Option<String> = args.get(1, "tz")?;
+    args.assert_all_used()?;
+
+    let date = parse_date(&value)?;
+    let date = match tz {
+        Some(tz) => in_timezone(date.with_timezone(&Utc), Some(&tz))?,
+        None => date,
+    };
+
+    Ok(format_date(&date, format.as_deref()))
+}
+
+fn shift(value: Value, duration: Duration) -> Result<Value, Error> {
+    let date: DateTime<FixedOffset> = parse_date(&value)?;
+    let shifted = date.checked_add_signed(duration).ok_or_else(|| {
+        Error::new(
+            ErrorKind::InvalidOperation,
+            format!("`{value}` shifted by {duration} is out of range"),
+        )
+    })?;
+
+    Ok(match value.kind() {
+        ValueKind::Number => Value::from(shifted.timestamp()),
+        _ => Value::from(shifted.to_rfc3339_opts(SecondsFormat::Secs, true)),
+    })
+}
+
+fn parse_duration(duration: &str) -> Result<Duration, Error> {
+    humantime::parse_duration(duration)
+        .map_err(|e| e.to_string())
+        .and_then(|d| Duration::from_std(d).map_err(|e| e.to_string()))
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidOperation,
+                format!("invalid duration `{duration}`: {e}"),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RenderBuilder;
+    use rstest::rstest;
+
+    fn render(template: &str) -> String {
+        RenderBuilder::new()
+            .with_filter("add", add)
+            .with_filter("sub", sub)
+            .with_filter("date", date)
+            .with_env_var("ts", 1679679000)
+            .render(template)
+    }
+
+    #[rstest]
+    #[case(r#"{{ "2023-03-24T17:30:00Z" | add("2h") }}"#, "2023-03-24T19:30:00Z")]
+    #[case(
+        r#"{{ "2023-03-24T17:30:00+01:00" | add("1day 30min") }}"#,
+        "2023-03-25T18:00:00+01:00"
+    )]
+    #[case(r#"{{ "2023-03-24" | sub("1d") }}"#, "2023-03-23T00:00:00Z")]
+    #[case(r#"{{ ts | add("1m") }}"#, "1679679060")]
+    #[case(r#"{{ ts | sub("1h") | date("%H:%M") }}"#, "16:30")]
+    fn test_add_and_sub(#[case] template: &str, #[case] expected: &str) {
+        assert_eq!(render(template), expected);
+    }
+
+    #[rstest]
+    #[case(r#"{{ "2024-01-01" | date("%s") }}"#, "1704067200")]
+    #[case(r#"{{ ts | date }}"#, "2023-03-24T17:30:00Z")]
+    #[case(r#"{{ ts | date("http") }}"#, "Fri, 24 Mar 2023 17:30:00 GMT")]
+    #[case(r#"{{ ts | date(tz="Asia/Tokyo") }}"#, "2023-03-25T02:30:00+09:00")]
+    #[case(
+        r#"{{ "2023-03-24T17:30:00+01:00" | date("%H:%M %z") }}"#,
+        "17:30 +0100"
+    )]
+    fn test_date(#[case] template: &str, #[case] expected: &str) {
+        assert_eq!(render(template), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid duration `2 fortnights`")]
+    fn should_throw_on_invalid_durations() {
+        render(r#"{{ ts | add("2 fortnights") }}"#);
+    }
+}
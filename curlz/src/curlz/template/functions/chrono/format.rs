@@ -0,0 +1,171 @@
+use chrono::{
+    DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, Offset, SecondsFormat, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+use minijinja::value::{Value, ValueKind};
+use minijinja::{Error, ErrorKind};
+
+/// converts a point in time into the given time zone,
+/// which is `UTC`, `local`, an offset like `+02:00` or a IANA name like `Europe/Berlin`
+pub(super) fn in_timezone(
+    date: DateTime<Utc>,
+    tz: Option<&str>,
+) -> Result<DateTime<FixedOffset>, Error> {
+    let offset = match tz.map(str::trim) {
+        None => Utc.fix(),
+        Some(tz) if tz.eq_ignore_ascii_case("utc") || tz.eq_ignore_ascii_case("z") => Utc.fix(),
+        Some(tz) if tz.eq_ignore_ascii_case("local") => date.with_timezone(&Local).offset().fix(),
+        Some(tz) if tz.starts_with(['+', '-']) => parse_offset(tz)?,
+        Some(tz) => tz
+            .parse::<Tz>()
+            .map(|tz| date.with_timezone(&tz).offset().fix())
+            .map_err(|_| {
+                Error::new(
+                    ErrorKind::InvalidOperation,
+                    format!("unknown time zone `{tz}`, use `UTC`, `local`, an offset like `+02:00` or a name like `Europe/Berlin`"),
+                )
+            })?,
+    };
+
+    Ok(date.with_timezone(&offset))
+}
+
+fn parse_offset(offset: &str) -> Result<FixedOffset, Error> {
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let digits: String = offset[1..].chars().filter(|c| *c != ':').collect();
+    let (hours, minutes) = match digits.len() {
+        2 => (digits.parse::<i32>().ok(), Some(0)),
+        4 => (
+            digits[..2].parse::<i32>().ok(),
+            digits[2..].parse::<i32>().ok(),
+        ),
+        _ => (None, None),
+    };
+
+    hours
+        .zip(minutes)
+        .and_then(|(h, m)| FixedOffset::east_opt(sign * (h * 3600 + m * 60)))
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidOperation,
+                format!("invalid time zone offset `{offset}`, use a format like `+02:00`"),
+            )
+        })
+}
+
+/// formats a point in time with a `strftime` like format,
+/// also the names `rfc3339`, `iso8601`, `rfc2822` and `http` are supported,
+/// without a format it's RFC 3339 like `2023-03-24T17:30:00Z`
+pub(super) fn format_date(date: &DateTime<FixedOffset>, format: Option<&str>) -> String {
+    match format.map(str::to_lowercase).as_deref() {
+        None | Some("rfc3339") | Some("iso8601") => date.to_rfc3339_opts(SecondsFormat::Secs, true),
+        Some("rfc2822") => date.to_rfc2822(),
+        Some("http") => date
+            .with_timezone(&Utc)
+            .format("%a, %d %b %Y %H:%M:%S GMT")
+            .to_string(),
+        Some(_) => date.format(format.unwrap_or_default()).to_string(),
+    }
+}
+
+/// parses a point in time from a template value, which is either a UNIX timestamp
+/// or a string in the RFC 3339, RFC 2822, `%Y-%m-%d %H:%M:%S` or `%Y-%m-%d` format,
+/// dates without an offset are taken as UTC
+pub(super) fn parse_date(value: &Value) -> Result<DateTime<FixedOffset>, Error> {
+    let invalid = || {
+        Error::new(
+            ErrorKind::InvalidOperation,
+            format!("`{value}` is not a date, use a UNIX timestamp or a format like `2023-03-24T17:30:00Z`"),
+        )
+    };
+
+    let date = match value.kind() {
+        ValueKind::Number => {
+            let timestamp = i64::try_from(value.clone()).map_err(|_| invalid())?;
+            Utc.timestamp_opt(timestamp, 0).single()
+        }
+        ValueKind::String => {
+            let s = value.as_str().unwrap_or_default().trim();
+            if let Ok(date) = DateTime::parse_from_rfc3339(s) {
+                return Ok(date);
+            }
+            if let Ok(date) = DateTime::parse_from_rfc2822(s) {
+                return Ok(date);
+            }
+            s.parse::<i64>()
+                .ok()
+                .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+                .or_else(|| {
+                    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S"]
+                        .iter()
+                        .find_map(|format| NaiveDateTime::parse_from_str(s, format).ok())
+                        .or_else(|| {
+                            NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                                .ok()
+                                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                        })
+                        .map(|date| Utc.from_utc_datetime(&date))
+                })
+        }
+        _ => None,
+    };
+
+    date.map(|date| date.with_timezone(&Utc.fix()))
+        .ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn sample_date() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2023, 3, 24, 17, 30, 0).unwrap()
+    }
+
+    #[rstest]
+    #[case(None, "2023-03-24T17:30:00Z")]
+    #[case(Some("UTC"), "2023-03-24T17:30:00Z")]
+    #[case(Some("+02:00"), "2023-03-24T19:30:00+02:00")]
+    #[case(Some("-0530"), "2023-03-24T12:00:00-05:30")]
+    #[case(Some("Europe/Berlin"), "2023-03-24T18:30:00+01:00")]
+    #[case(Some("America/New_York"), "2023-03-24T13:30:00-04:00")]
+    fn should_convert_into_timezones(#[case] tz: Option<&str>, #[case] expected: &str) {
+        let date = in_timezone(sample_date(), tz).unwrap();
+        assert_eq!(format_date(&date, None), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown time zone `Mars/Olympus`")]
+    fn should_throw_on_unknown_timezones() {
+        in_timezone(sample_date(), Some("Mars/Olympus")).unwrap();
+    }
+
+    #[rstest]
+    #[case(Some("rfc3339"), "2023-03-24T17:30:00Z")]
+    #[case(Some("rfc2822"), "Fri, 24 Mar 2023 17:30:00 +0000")]
+    #[case(Some("http"), "Fri, 24 Mar 2023 17:30:00 GMT")]
+    #[case(Some("%s"), "1679679000")]
+    #[case(Some("%Y-%m-%d"), "2023-03-24")]
+    fn should_format_dates(#[case] format: Option<&str>, #[case] expected: &str) {
+        let date = in_timezone(sample_date(), None).unwrap();
+        assert_eq!(format_date(&date, format), expected);
+    }
+
+    #[rstest]
+    #[case(Value::from(1679679000), "2023-03-24T17:30:00Z")]
+    #[case(Value::from("1679679000"), "2023-03-24T17:30:00Z")]
+    #[case(Value::from("2023-03-24T19:30:00+02:00"), "2023-03-24T19:30:00+02:00")]
+    #[case(Value::from("Fri, 24 Mar 2023 17:30:00 +0000"), "2023-03-24T17:30:00Z")]
+    #[case(Value::from("2023-03-24 17:30:00"), "2023-03-24T17:30:00Z")]
+    #[case(Value::from("2023-03-24"), "2023-03-24T00:00:00Z")]
+    fn should_parse_dates(#[case] value: Value, #[case] expected: &str) {
+        assert_eq!(format_date(&parse_date(&value).unwrap(), None), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "`yesterday` is not a date")]
+    fn should_throw_on_unparsable_dates() {
+        parse_date(&Value::from("yesterday")).unwrap();
+    }
+}
@@ -1,7 +1,17 @@
+use crate::template::functions::chrono::clock::SystemClock;
 use minijinja::Environment;
 
+pub(super) mod clock;
+mod filters;
+mod format;
+mod now;
 mod timestamp;
 
 pub(super) fn register_functions(env: &mut Environment) {
-    env.add_function("timestamp", timestamp::timestamp);
+    env.add_function("timestamp", timestamp::timestamp(SystemClock));
+    env.add_function("timestamp_ms", timestamp::timestamp_ms(SystemClock));
+    env.add_function("now", now::now(SystemClock));
+    env.add_filter("add", filters::add);
+    env.add_filter("sub", filters::sub);
+    env.add_filter("date", filters::date);
 }
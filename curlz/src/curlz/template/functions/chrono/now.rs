@@ -0,0 +1,46 @@
+use crate::template::functions::args::Arguments;
+use crate::template::functions::chrono::clock::Clock;
+use crate::template::functions::chrono::format::{format_date, in_timezone};
+use minijinja::value::{Rest, Value};
+use minijinja::{Error, State};
+
+/// creates the `now` template function, the current time is taken from the given `clock`
+///
+/// `now(format="rfc3339", tz="UTC")`
+pub(super) fn now(
+    clock: impl Clock,
+) -> impl Fn(&State, Rest<Value>) -> Result<String, Error> + Send + Sync + 'static {
+    move |_, args| {
+        let args = Arguments::new(&args);
+        let format: Option<String> = args.get(0, "format")?;
+        let tz: Option<String> = args.get(1, "tz")?;
+        args.assert_all_used()?;
+
+        let now = in_timezone(clock.now(), tz.as_deref())?;
+
+        Ok(format_date(&now, format.as_deref()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::functions::chrono::clock::FixedClock;
+    use crate::test_utils::RenderBuilder;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(r#"{{ now() }}"#, "2023-03-24T17:30:00Z")]
+    #[case(r#"{{ now("iso8601") }}"#, "2023-03-24T17:30:00Z")]
+    #[case(r#"{{ now("%Y-%m-%d") }}"#, "2023-03-24")]
+    #[case(r#"{{ now("http") }}"#, "Fri, 24 Mar 2023 17:30:00 GMT")]
+    #[case(r#"{{ now(tz="Europe/Berlin") }}"#, "2023-03-24T18:30:00+01:00")]
+    #[case(r#"{{ now(format="%H:%M", tz="+02:00") }}"#, "19:30")]
+    fn test_now(#[case] template: &str, #[case] expected: &str) {
+        let result = RenderBuilder::new()
+            .with_function("now", now(FixedClock::at_timestamp(1679679000)))
+            .render(template);
+
+        assert_eq!(result, expected);
+    }
+}
@@ -1,14 +1,21 @@
-use chrono::Utc;
+use crate::template::functions::chrono::clock::Clock;
 use minijinja::value::Value;
 use minijinja::State;
 
-pub(super) fn timestamp(_: &State) -> Value {
-    Value::from(Utc::now().timestamp())
+/// creates the `timestamp` template function, the UNIX timestamp in seconds
+pub(super) fn timestamp(clock: impl Clock) -> impl Fn(&State) -> Value + Send + Sync + 'static {
+    move |_| Value::from(clock.now().timestamp())
+}
+
+/// creates the `timestamp_ms` template function, the UNIX timestamp in milliseconds
+pub(super) fn timestamp_ms(clock: impl Clock) -> impl Fn(&State) -> Value + Send + Sync + 'static {
+    move |_| Value::from(clock.now().timestamp_millis())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::template::functions::chrono::clock::{FixedClock, SystemClock};
     use crate::test_utils::RenderBuilder;
     use chrono::Utc;
 
@@ -16,9 +23,20 @@ mod tests {
     fn test_timestamp() {
         let now = Utc::now();
         let timestamp_result = RenderBuilder::new()
-            .with_function("timestamp", timestamp)
+            .with_function("timestamp", timestamp(SystemClock))
             .render(r#"now = {{ timestamp() }}"#);
 
         assert_eq!(format!("now = {}", now.timestamp()), timestamp_result);
     }
+
+    #[test]
+    fn test_timestamp_ms() {
+        let clock = FixedClock::at_timestamp(1679679000);
+        let timestamp_result = RenderBuilder::new()
+            .with_function("timestamp", timestamp(clock))
+            .with_function("timestamp_ms", timestamp_ms(clock))
+            .render(r#"{{ timestamp() }} {{ timestamp_ms() }}"#);
+
+        assert_eq!(timestamp_result, "1679679000 1679679000000");
+    }
 }
@@ -0,0 +1,64 @@
+use base64::{engine::general_purpose, Engine};
+use minijinja::{Error, ErrorKind};
+
+/// the textual representation of a binary digest or signature
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(super) enum OutputEncoding {
+    #[default]
+    Hex,
+    Base64,
+    Base64Url,
+}
+
+impl OutputEncoding {
+    pub fn encode(&self, bytes: impl AsRef<[u8]>) -> String {
+        match self {
+            OutputEncoding::Hex => hex::encode(bytes),
+            OutputEncoding::Base64 => general_purpose::STANDARD.encode(bytes),
+            OutputEncoding::Base64Url => general_purpose::URL_SAFE_NO_PAD.encode(bytes),
+        }
+    }
+}
+
+/// an omitted encoding falls back to `hex`
+impl TryFrom<Option<&str>> for OutputEncoding {
+    type Error = Error;
+
+    fn try_from(encoding: Option<&str>) -> Result<Self, Self::Error> {
+        match encoding.map(str::to_lowercase).as_deref() {
+            None | Some("hex") => Ok(OutputEncoding::Hex),
+            Some("base64") => Ok(OutputEncoding::Base64),
+            Some("base64url") => Ok(OutputEncoding::Base64Url),
+            Some(other) => Err(Error::new(
+                ErrorKind::InvalidOperation,
+                format!(
+                    "unsupported encoding `{other}`, use one of `hex`, `base64` or `base64url`"
+                ),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_default_to_hex() {
+        assert_eq!(OutputEncoding::try_from(None).unwrap(), OutputEncoding::Hex);
+        assert_eq!(OutputEncoding::Hex.encode([0xca, 0xfe]), "cafe");
+    }
+
+    #[test]
+    fn should_encode_base64_variants() {
+        let bytes = [0xfb, 0xff];
+        assert_eq!(OutputEncoding::Base64.encode(bytes), "+/8=");
+        assert_eq!(OutputEncoding::Base64Url.encode(bytes), "-_8");
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported encoding `base32`")]
+    fn should_throw_on_unknown_encodings() {
+        OutputEncoding::try_from(Some("base32")).unwrap();
+    }
+}
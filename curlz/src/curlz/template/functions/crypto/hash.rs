@@ -0,0 +1,54 @@
+use super::encoding::OutputEncoding;
+
+use md5::Md5;
+use minijinja::{Error, State};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+/// hashes a message with sha256, the digest is hex encoded unless another `encoding` is given
+pub(super) fn sha256(_: &State, message: &str, encoding: Option<&str>) -> Result<String, Error> {
+    hash::<Sha256>(message, encoding)
+}
+
+/// hashes a message with sha1, the digest is hex encoded unless another `encoding` is given
+pub(super) fn sha1(_: &State, message: &str, encoding: Option<&str>) -> Result<String, Error> {
+    hash::<Sha1>(message, encoding)
+}
+
+/// hashes a message with md5, the digest is hex encoded unless another `encoding` is given
+pub(super) fn md5(_: &State, message: &str, encoding: Option<&str>) -> Result<String, Error> {
+    hash::<Md5>(message, encoding)
+}
+
+fn hash<D: Digest>(message: &str, encoding: Option<&str>) -> Result<String, Error> {
+    let encoding = OutputEncoding::try_from(encoding)?;
+
+    Ok(encoding.encode(D::digest(message.as_bytes())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RenderBuilder;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        r#"{{ sha256("abc") }}"#,
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    )]
+    #[case(
+        r#"{{ sha256("abc", "base64") }}"#,
+        "ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0="
+    )]
+    #[case(r#"{{ sha1("abc") }}"#, "a9993e364706816aba3e25717850c26c9cd0d89d")]
+    #[case(r#"{{ md5("abc") }}"#, "900150983cd24fb0d6963f7d28e17f72")]
+    fn should_hash_messages(#[case] template: &str, #[case] expected: &str) {
+        let e = RenderBuilder::new()
+            .with_function("sha256", sha256)
+            .with_function("sha1", sha1)
+            .with_function("md5", md5);
+
+        assert_eq!(e.render(template), expected);
+    }
+}
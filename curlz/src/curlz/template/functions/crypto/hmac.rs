@@ -0,0 +1,94 @@
+use super::encoding::OutputEncoding;
+
+use hmac::{Hmac, Mac};
+use md5::Md5;
+use minijinja::{Error, ErrorKind, State};
+use sha1::Sha1;
+use sha2::{Sha256, Sha384, Sha512};
+
+/// signs a message with a HMAC, based on the hash `algorithm` and a secret `key`
+///
+/// the signature is hex encoded unless another `encoding` is given
+pub(super) fn hmac(
+    _: &State,
+    algorithm: &str,
+    key: &str,
+    message: &str,
+    encoding: Option<&str>,
+) -> Result<String, Error> {
+    let encoding = OutputEncoding::try_from(encoding)?;
+    let key = key.as_bytes();
+    let message = message.as_bytes();
+
+    let signature = match algorithm.to_lowercase().replace('-', "").as_str() {
+        "sha1" => sign::<Hmac<Sha1>>(key, message),
+        "sha256" => sign::<Hmac<Sha256>>(key, message),
+        "sha384" => sign::<Hmac<Sha384>>(key, message),
+        "sha512" => sign::<Hmac<Sha512>>(key, message),
+        "md5" => sign::<Hmac<Md5>>(key, message),
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidOperation,
+                format!(
+                    "unsupported hmac algorithm `{algorithm}`, use one of `sha1`, `sha256`, `sha384`, `sha512` or `md5`"
+                ),
+            ))
+        }
+    };
+
+    Ok(encoding.encode(signature))
+}
+
+/// computes the raw HMAC of `message`, for the hash function of the given [`Mac`]
+pub(in crate::template::functions) fn sign<M: Mac + hmac::digest::KeyInit>(
+    key: &[u8],
+    message: &[u8],
+) -> Vec<u8> {
+    // hmac accepts keys of any length, so this cannot fail
+    let mut mac = <M as Mac>::new_from_slice(key).expect("HMAC can take a key of any size");
+    mac.update(message);
+
+    mac.finalize().into_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RenderBuilder;
+    use rstest::rstest;
+
+    const MESSAGE: &str = "The quick brown fox jumps over the lazy dog";
+
+    #[rstest]
+    #[case(
+        r#"{{ hmac("sha256", "key", message) }}"#,
+        "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+    )]
+    #[case(
+        r#"{{ hmac("SHA-256", "key", message, "base64") }}"#,
+        "97yD9DBThCSxMpjmqm+xQ+9NWaFJRhdZl0edvC0aPNg="
+    )]
+    #[case(
+        r#"{{ hmac("sha1", "key", message) }}"#,
+        "de7c9b85b8b78aa6bc8a7a36f70a90701c9db4d9"
+    )]
+    #[case(
+        r#"{{ hmac("md5", "key", message) }}"#,
+        "80070713463e7749b90c2dc24911e275"
+    )]
+    fn should_sign_messages(#[case] template: &str, #[case] expected: &str) {
+        let e = RenderBuilder::new()
+            .with_function("hmac", hmac)
+            .with_env_var("message", MESSAGE);
+
+        assert_eq!(e.render(template), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported hmac algorithm `crc32`")]
+    fn should_throw_on_unsupported_algorithms() {
+        RenderBuilder::new()
+            .with_function("hmac", hmac)
+            .render(r#"{{ hmac("crc32", "key", "message") }}"#);
+    }
+}
@@ -0,0 +1,14 @@
+use minijinja::Environment;
+
+mod encoding;
+mod hash;
+mod hmac;
+
+pub(super) use self::hmac::sign;
+
+pub(super) fn register_functions(env: &mut Environment) {
+    env.add_function("hmac", hmac::hmac);
+    env.add_function("sha256", hash::sha256);
+    env.add_function("sha1", hash::sha1);
+    env.add_function("md5", hash::md5);
+}
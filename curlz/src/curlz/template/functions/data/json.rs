@@ -0,0 +1,85 @@
+use crate::template::functions::args::Arguments;
+use minijinja::value::{Rest, Value};
+use minijinja::{Error, ErrorKind, State};
+
+/// serializes a value as json, `pretty=true` spreads it over multiple lines
+///
+/// `{{ payload | tojson }}`, `{{ payload | tojson(pretty=true) }}`
+pub(super) fn tojson(_: &State, value: Value, args: Rest<Value>) -> Result<Value, Error> {
+    let args = Arguments::new(&args);
+    let pretty = args.get(0, "pretty")?.unwrap_or(false);
+    args.assert_all_used()?;
+
+    let json = if pretty {
+        serde_json::to_string_pretty(&value)
+    } else {
+        serde_json::to_string(&value)
+    }
+    .map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidOperation,
+            format!("tojson failed to serialize `{value}`: {e}"),
+        )
+    })?;
+
+    Ok(Value::from_safe_string(json))
+}
+
+/// parses a json string into a structured value
+///
+/// `{% set user = '{"name": "Bob"}' | fromjson %}{{ user.name }}`
+pub(super) fn fromjson(_: &State, value: &str) -> Result<Value, Error> {
+    serde_json::from_str::<serde_json::Value>(value)
+        .map(|json| Value::from_serializable(&json))
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidOperation,
+                format!("fromjson failed, the input is not valid json: {e}"),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RenderBuilder;
+    use indoc::indoc;
+    use rstest::rstest;
+
+    fn render(template: &str) -> String {
+        RenderBuilder::new()
+            .with_filter("tojson", tojson)
+            .with_filter("fromjson", fromjson)
+            .with_object(
+                "payload",
+                Value::from_serializable(&serde_json::json!({"name": "Bob \"B\"", "tags": [1, 2]})),
+            )
+            .render(template)
+    }
+
+    #[rstest]
+    #[case(r#"{{ payload | tojson }}"#, r#"{"name":"Bob \"B\"","tags":[1,2]}"#)]
+    #[case(r#"{{ "<tag>" | tojson }}"#, r#""<tag>""#)]
+    #[case(r#"{{ payload | tojson(pretty=true) }}"#, indoc! {r#"
+        {
+          "name": "Bob \"B\"",
+          "tags": [
+            1,
+            2
+          ]
+        }"#})]
+    #[case(r#"{{ ('{"a": {"b": [true]}}' | fromjson).a.b[0] }}"#, "true")]
+    #[case(
+        r#"{{ payload | tojson | fromjson | tojson }}"#,
+        r#"{"name":"Bob \"B\"","tags":[1,2]}"#
+    )]
+    fn test_json(#[case] template: &str, #[case] expected: &str) {
+        assert_eq!(render(template), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "fromjson failed, the input is not valid json")]
+    fn should_throw_on_invalid_json() {
+        render(r#"{{ "{nope" | fromjson }}"#);
+    }
+}
@@ -0,0 +1,66 @@
+use minijinja::value::{Value, ValueKind};
+use minijinja::{Error, ErrorKind, State};
+use serde_json_path::JsonPath;
+
+/// queries a structured value, or a json string, with a [JSONPath](https://www.rfc-editor.org/rfc/rfc9535) expression,
+/// a single match is returned as is, multiple matches as a list and no match as `none`
+///
+/// `{{ response | jsonpath("$.items[0].id") }}`
+pub(super) fn jsonpath(_: &State, value: Value, expr: &str) -> Result<Value, Error> {
+    let path = JsonPath::parse(expr).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidOperation,
+            format!("jsonpath `{expr}` is invalid: {e}"),
+        )
+    })?;
+    let json = match value.kind() {
+        ValueKind::String => serde_json::from_str(value.as_str().unwrap_or_default()),
+        _ => serde_json::to_value(&value),
+    }
+    .map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidOperation,
+            format!("jsonpath needs json or a structured value: {e}"),
+        )
+    })?;
+
+    let nodes = path.query(&json).all();
+    Ok(match nodes.as_slice() {
+        [] => Value::from(()),
+        [node] => Value::from_serializable(node),
+        nodes => Value::from_serializable(&nodes),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RenderBuilder;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(r#"{{ response | jsonpath("$.items[0].id") }}"#, "1")]
+    #[case(r#"{{ response | jsonpath("$.items[*].id") }}"#, "[1, 2]")]
+    #[case(r#"{{ response | jsonpath("$.items[?@.id > 1].name") }}"#, "two")]
+    #[case(r#"{{ response | jsonpath("$.nope") }}"#, "none")]
+    #[case(r#"{{ '{"token": "abc"}' | jsonpath("$.token") }}"#, "abc")]
+    fn test_jsonpath(#[case] template: &str, #[case] expected: &str) {
+        let response = serde_json::json!({
+            "items": [{"id": 1, "name": "one"}, {"id": 2, "name": "two"}]
+        });
+        let result = RenderBuilder::new()
+            .with_filter("jsonpath", jsonpath)
+            .with_object("response", Value::from_serializable(&response))
+            .render(template);
+
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "jsonpath `items[` is invalid")]
+    fn should_throw_on_invalid_expressions() {
+        RenderBuilder::new()
+            .with_filter("jsonpath", jsonpath)
+            .render(r#"{{ "{}" | jsonpath("items[") }}"#);
+    }
+}
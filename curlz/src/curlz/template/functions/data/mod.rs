@@ -0,0 +1,13 @@
+use minijinja::Environment;
+
+mod json;
+mod jsonpath;
+mod yaml;
+
+pub(super) fn register_functions(env: &mut Environment) {
+    env.add_filter("tojson", json::tojson);
+    env.add_filter("fromjson", json::fromjson);
+    env.add_filter("toyaml", yaml::toyaml);
+    env.add_filter("fromyaml", yaml::fromyaml);
+    env.add_filter("jsonpath", jsonpath::jsonpath);
+}
@@ -0,0 +1,57 @@
+use minijinja::value::Value;
+use minijinja::{Error, ErrorKind, State};
+
+/// serializes a value as yaml
+///
+/// `{{ payload | toyaml }}`
+pub(super) fn toyaml(_: &State, value: Value) -> Result<Value, Error> {
+    serde_yaml::to_string(&value)
+        .map(|yaml| Value::from_safe_string(yaml.trim_end().to_string()))
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidOperation,
+                format!("toyaml failed to serialize `{value}`: {e}"),
+            )
+        })
+}
+
+/// parses a yaml string into a structured value
+///
+/// `{{ ("name: Bob" | fromyaml).name }}`
+pub(super) fn fromyaml(_: &State, value: &str) -> Result<Value, Error> {
+    serde_yaml::from_str::<serde_yaml::Value>(value)
+        .map(|yaml| Value::from_serializable(&yaml))
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidOperation,
+                format!("fromyaml failed, the input is not valid yaml: {e}"),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RenderBuilder;
+    use indoc::indoc;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(r#"{{ payload | toyaml }}"#, indoc! {r#"
+        name: Bob
+        tags:
+        - a"#})]
+    #[case(r#"{{ ("name: Bob\nage: 42" | fromyaml).age + 1 }}"#, "43")]
+    fn test_yaml(#[case] template: &str, #[case] expected: &str) {
+        let result = RenderBuilder::new()
+            .with_filter("toyaml", toyaml)
+            .with_filter("fromyaml", fromyaml)
+            .with_object(
+                "payload",
+                Value::from_serializable(&serde_json::json!({"name": "Bob", "tags": ["a"]})),
+            )
+            .render(template);
+
+        assert_eq!(result, expected);
+    }
+}
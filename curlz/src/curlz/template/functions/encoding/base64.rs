@@ -0,0 +1,88 @@
+use crate::template::functions::args::Arguments;
+use base64::engine::{general_purpose, DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::{alphabet, Engine};
+use minijinja::value::{Rest, Value};
+use minijinja::{Error, ErrorKind, State};
+
+/// decodes with or without padding
+const DECODE_CONFIG: GeneralPurposeConfig =
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent);
+
+/// base64 encodes a string, with `urlsafe=true` the url-safe alphabet without padding is used
+///
+/// `{{ "hello" | base64encode }}`, `{{ "hello" | base64encode(urlsafe=true) }}`
+pub(super) fn base64encode(_: &State, value: &str, args: Rest<Value>) -> Result<String, Error> {
+    Ok(if urlsafe(&args)? {
+        general_purpose::URL_SAFE_NO_PAD.encode(value)
+    } else {
+        general_purpose::STANDARD.encode(value)
+    })
+}
+
+/// decodes a base64 string, with `urlsafe=true` of the url-safe alphabet, padding is optional
+///
+/// `{{ "aGVsbG8=" | base64decode }}`, `{{ "aGVsbG8" | base64decode(urlsafe=true) }}`
+pub(super) fn base64decode(_: &State, value: &str, args: Rest<Value>) -> Result<String, Error> {
+    let alphabet = if urlsafe(&args)? {
+        &alphabet::URL_SAFE
+    } else {
+        &alphabet::STANDARD
+    };
+    let bytes = GeneralPurpose::new(alphabet, DECODE_CONFIG)
+        .decode(value.trim())
+        .map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidOperation,
+                format!("base64decode failed, `{value}` is not valid base64: {e}"),
+            )
+        })?;
+
+    String::from_utf8(bytes).map_err(|_| {
+        Error::new(
+            ErrorKind::InvalidOperation,
+            format!("base64decode failed, `{value}` does not contain text"),
+        )
+    })
+}
+
+fn urlsafe(args: &[Value]) -> Result<bool, Error> {
+    let args = Arguments::new(args);
+    let urlsafe = args.get(0, "urlsafe")?.unwrap_or(false);
+    args.assert_all_used()?;
+
+    Ok(urlsafe)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RenderBuilder;
+    use rstest::rstest;
+
+    fn render(template: &str) -> String {
+        RenderBuilder::new()
+            .with_filter("base64encode", base64encode)
+            .with_filter("base64decode", base64decode)
+            .render(template)
+    }
+
+    #[rstest]
+    #[case(r#"{{ "subjects?_d" | base64encode }}"#, "c3ViamVjdHM/X2Q=")]
+    #[case(
+        r#"{{ "subjects?_d" | base64encode(urlsafe=true) }}"#,
+        "c3ViamVjdHM_X2Q"
+    )]
+    #[case(r#"{{ "c3ViamVjdHM/X2Q=" | base64decode }}"#, "subjects?_d")]
+    #[case(r#"{{ "c3ViamVjdHM/X2Q" | base64decode }}"#, "subjects?_d")]
+    #[case(r#"{{ "c3ViamVjdHM_X2Q" | base64decode(true) }}"#, "subjects?_d")]
+    #[case(r#"{{ "hällo" | base64encode | base64decode }}"#, "hällo")]
+    fn test_base64(#[case] template: &str, #[case] expected: &str) {
+        assert_eq!(render(template), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "base64decode failed, `c3ViamVjdHM_X2Q` is not valid base64")]
+    fn should_throw_on_the_wrong_alphabet() {
+        render(r#"{{ "c3ViamVjdHM_X2Q" | base64decode }}"#);
+    }
+}
@@ -0,0 +1,41 @@
+use minijinja::value::{Value, ValueKind};
+use minijinja::{Error, ErrorKind, State};
+
+/// hex encodes the bytes of a string, or formats a number as hex
+///
+/// `{{ "hello" | hex }}`, `{{ 255 | hex }}`
+pub(super) fn hex(_: &State, value: Value) -> Result<String, Error> {
+    match value.kind() {
+        ValueKind::String => Ok(hex::encode(value.as_str().unwrap_or_default())),
+        ValueKind::Number => u64::try_from(value.clone())
+            .map(|n| format!("{n:x}"))
+            .map_err(|_| {
+                Error::new(
+                    ErrorKind::InvalidOperation,
+                    format!("hex supports only positive integers, but got `{value}`"),
+                )
+            }),
+        _ => Err(Error::new(
+            ErrorKind::InvalidOperation,
+            format!("hex supports only strings and numbers, but got `{value}`"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RenderBuilder;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(r#"{{ "hello" | hex }}"#, "68656c6c6f")]
+    #[case(r#"{{ 255 | hex }}"#, "ff")]
+    fn test_hex(#[case] template: &str, #[case] expected: &str) {
+        let result = RenderBuilder::new()
+            .with_filter("hex", hex)
+            .render(template);
+
+        assert_eq!(result, expected);
+    }
+}
@@ -0,0 +1,12 @@
+use minijinja::Environment;
+
+mod base64;
+mod hex;
+mod url;
+
+pub(super) fn register_functions(env: &mut Environment) {
+    env.add_filter("urlencode", url::urlencode);
+    env.add_filter("base64encode", base64::base64encode);
+    env.add_filter("base64decode", base64::base64decode);
+    env.add_filter("hex", hex::hex);
+}
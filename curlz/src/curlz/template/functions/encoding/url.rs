@@ -0,0 +1,54 @@
+use crate::utils::percent_encode as encode;
+use minijinja::value::{Value, ValueKind};
+use minijinja::{Error, ErrorKind, State};
+
+/// percent-encodes a value for the use in a url, a map becomes a query string like `a=1&b=2`
+///
+/// `{{ q | urlencode }}`
+pub(super) fn urlencode(_: &State, value: Value) -> Result<String, Error> {
+    match value.kind() {
+        ValueKind::Map => value
+            .try_iter()?
+            .filter_map(|key| {
+                let value = value.get_item(&key).ok()?;
+                (!value.is_undefined() && !value.is_none()).then(|| {
+                    Ok(format!(
+                        "{}={}",
+                        encode(&key.to_string()),
+                        encode(&value.to_string())
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()
+            .map(|pairs| pairs.join("&")),
+        ValueKind::Undefined | ValueKind::None => Ok(String::new()),
+        ValueKind::Seq => Err(Error::new(
+            ErrorKind::InvalidOperation,
+            "urlencode does not support lists, encode the items one by one",
+        )),
+        _ => Ok(encode(&value.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::RenderBuilder;
+    use rstest::rstest;
+    use std::collections::BTreeMap;
+
+    #[rstest]
+    #[case(r#"{{ "a b&c=d/ü" | urlencode }}"#, "a%20b%26c%3Dd%2F%C3%BC")]
+    #[case(r#"{{ "safe-_.~" | urlencode }}"#, "safe-_.~")]
+    #[case(r#"{{ 42 | urlencode }}"#, "42")]
+    #[case(r#"{{ query | urlencode }}"#, "page=2&q=rust%20lang")]
+    fn test_urlencode(#[case] template: &str, #[case] expected: &str) {
+        let query = BTreeMap::from([("q", "rust lang"), ("page", "2")]);
+        let result = RenderBuilder::new()
+            .with_filter("urlencode", urlencode)
+            .with_object("query", Value::from_serializable(&query))
+            .render(template);
+
+        assert_eq!(result, expected);
+    }
+}
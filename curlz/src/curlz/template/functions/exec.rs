@@ -0,0 +1,61 @@
+use crate::template::commands::Commands;
+use crate::template::functions::args::Arguments;
+use crate::template::session::RenderSession;
+use minijinja::value::{Rest, Value};
+use minijinja::{Environment, Error, ErrorKind, State};
+
+pub(super) fn register_functions(env: &mut Environment, session: &RenderSession) {
+    env.add_function("exec", exec(session.commands()));
+}
+
+/// creates the `exec` template function, it returns the trimmed stdout of an allowlisted command
+///
+/// `exec("vault", args=["kv", "get", "-field=token", "secret/api"])`
+fn exec(
+    commands: Commands,
+) -> impl Fn(&State, Rest<Value>) -> Result<String, Error> + Send + Sync + 'static {
+    move |state, args| {
+        let args = Arguments::new(&args);
+        let command: String = args.get(0, "cmd")?.ok_or_else(|| {
+            Error::new(
+                ErrorKind::MissingArgument,
+                "the command to run is missing, like `exec(\"vault\")`",
+            )
+        })?;
+        let command_args: Vec<String> = args.get(1, "args")?.unwrap_or_default();
+        args.assert_all_used()?;
+
+        commands.run(&command, &command_args).map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidOperation,
+                format!("`exec()` of template `{}` failed: {e}", state.name()),
+            )
+        })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::test_utils::RenderBuilder;
+
+    #[test]
+    fn should_return_the_stdout_of_a_command() {
+        let session = RenderSession::new().with_allowed_commands(vec!["echo".into()]);
+
+        assert_eq!(
+            RenderBuilder::new()
+                .with_function("exec", exec(session.commands()))
+                .render(r#"Bearer {{ exec("echo", args=["-n", "s3cr3t"]) }}"#),
+            "Bearer s3cr3t"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the command `echo` is not allowed")]
+    fn should_throw_on_commands_that_are_not_allowed() {
+        RenderBuilder::new()
+            .with_function("exec", exec(RenderSession::new().commands()))
+            .render(r#"{{ exec("echo", args=["hi"]) }}"#);
+    }
+}
@@ -0,0 +1,193 @@
+use crate::template::session::RenderSession;
+use base64::{engine::general_purpose, Engine};
+use minijinja::value::Value;
+use minijinja::{Environment, Error, ErrorKind, State};
+use std::path::{Path, PathBuf};
+
+pub(super) fn register_functions(env: &mut Environment, session: &RenderSession, ctx: &Value) {
+    let files = Files::from(session);
+    env.add_function("file", file(files.clone()));
+    env.add_function("file_base64", file_base64(files.clone()));
+    env.add_function("include_template", include_template(files, ctx.clone()));
+}
+
+/// resolves and reads the files of template functions like `file()`
+#[derive(Clone, Debug)]
+struct Files {
+    base_dir: PathBuf,
+    sandbox: Option<PathBuf>,
+}
+
+impl From<&RenderSession> for Files {
+    fn from(session: &RenderSession) -> Self {
+        Self {
+            base_dir: session.base_dir().to_path_buf(),
+            sandbox: session.sandbox().map(Path::to_path_buf),
+        }
+    }
+}
+
+impl Files {
+    /// reads the file at `path`, relative paths are resolved against the base dir
+    fn read(&self, state: &State, path: &str) -> Result<Vec<u8>, Error> {
+        let error = |reason: String| {
+            Error::new(
+                ErrorKind::InvalidOperation,
+                format!("file `{path}` of template `{}` {reason}", state.name()),
+            )
+        };
+        let resolved = self
+            .base_dir
+            .join(path)
+            .canonicalize()
+            .map_err(|e| error(format!("cannot be read: {e}")))?;
+
+        if let Some(sandbox) = self.sandbox.as_ref() {
+            let sandbox = sandbox
+                .canonicalize()
+                .map_err(|e| error(format!("cannot be checked: {e}")))?;
+            if !resolved.starts_with(&sandbox) {
+                return Err(error(format!(
+                    "is outside of the project `{}`, use `--allow-outside-project` to read it anyway",
+                    sandbox.display()
+                )));
+            }
+        }
+
+        std::fs::read(&resolved).map_err(|e| error(format!("cannot be read: {e}")))
+    }
+
+    fn read_to_string(&self, state: &State, path: &str) -> Result<String, Error> {
+        String::from_utf8(self.read(state, path)?).map_err(|_| {
+            Error::new(
+                ErrorKind::InvalidOperation,
+                format!(
+                    "file `{path}` of template `{}` is not a text file, use `file_base64()` instead",
+                    state.name()
+                ),
+            )
+        })
+    }
+}
+
+/// creates the `file` template function, it returns the contents of a text file
+///
+/// `file("./fixtures/user.json")`
+fn file(files: Files) -> impl Fn(&State, &str) -> Result<String, Error> + Send + Sync + 'static {
+    move |state, path| files.read_to_string(state, path)
+}
+
+/// creates the `file_base64` template function, it returns the base64 encoded contents of any file
+///
+/// `file_base64("./avatar.png")`
+fn file_base64(
+    files: Files,
+) -> impl Fn(&State, &str) -> Result<String, Error> + Send + Sync + 'static {
+    move |state, path| {
+        files
+            .read(state, path)
+            .map(|bytes| general_purpose::STANDARD.encode(bytes))
+    }
+}
+
+/// creates the `include_template` template function, it renders a template file with the same variables
+///
+/// `include_template("./partials/headers.j2")`
+fn include_template(
+    files: Files,
+    ctx: Value,
+) -> impl Fn(&State, &str) -> Result<String, Error> + Send + Sync + 'static {
+    move |state, path| {
+        let source = files.read_to_string(state, path)?;
+
+        state.env().render_named_str(path, &source, &ctx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{create_file, RenderBuilder};
+    use std::collections::HashMap;
+
+    fn files_in(base_dir: &Path) -> Files {
+        Files {
+            base_dir: base_dir.to_path_buf(),
+            sandbox: Some(base_dir.to_path_buf()),
+        }
+    }
+
+    #[test]
+    fn test_file() {
+        let tmp = create_file("fixtures/user.json", r#"{"name": "Bob"}"#).unwrap();
+        let result = RenderBuilder::new()
+            .with_function("file", file(files_in(tmp.path())))
+            .render(r#"{{ file("./fixtures/user.json") }}"#);
+
+        assert_eq!(result, r#"{"name": "Bob"}"#);
+    }
+
+    #[test]
+    fn test_file_base64() {
+        let tmp = create_file("avatar.png", "\u{89}PNG").unwrap();
+        let result = RenderBuilder::new()
+            .with_function("file_base64", file_base64(files_in(tmp.path())))
+            .render(r#"{{ file_base64("avatar.png") }}"#);
+
+        assert_eq!(result, "wolQTkc=");
+    }
+
+    #[test]
+    fn test_include_template() {
+        let tmp = create_file("partials/greeting.j2", r#"Hello {{ name }}!"#).unwrap();
+        let ctx = Value::from_serializable(&HashMap::from([("name", "Bob")]));
+        let result = RenderBuilder::new()
+            .with_function(
+                "include_template",
+                include_template(files_in(tmp.path()), ctx),
+            )
+            .render(r#"{{ include_template("./partials/greeting.j2") }}"#);
+
+        assert_eq!(result, "Hello Bob!");
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "file `./missing.json` of template `render-builder-template` cannot be read"
+    )]
+    fn should_name_template_and_path_of_missing_files() {
+        let tmp = tempfile::tempdir().unwrap();
+        RenderBuilder::new()
+            .with_function("file", file(files_in(tmp.path())))
+            .render(r#"{{ file("./missing.json") }}"#);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "file `../secret.txt` of template `render-builder-template` is outside of the project"
+    )]
+    fn should_not_read_files_outside_of_the_sandbox() {
+        let tmp = create_file("secret.txt", "top secret").unwrap();
+        std::fs::create_dir(tmp.path().join("project")).unwrap();
+
+        RenderBuilder::new()
+            .with_function("file", file(files_in(&tmp.path().join("project"))))
+            .render(r#"{{ file("../secret.txt") }}"#);
+    }
+
+    #[test]
+    fn should_read_files_outside_of_the_sandbox_when_allowed() {
+        let tmp = create_file("secret.txt", "top secret").unwrap();
+        std::fs::create_dir(tmp.path().join("project")).unwrap();
+        let files = Files {
+            base_dir: tmp.path().join("project"),
+            sandbox: None,
+        };
+
+        let result = RenderBuilder::new()
+            .with_function("file", file(files))
+            .render(r#"{{ file("../secret.txt") }}"#);
+
+        assert_eq!(result, "top secret");
+    }
+}
@@ -1,18 +1,35 @@
+use crate::template::session::RenderSession;
 use minijinja::value::Value;
 use minijinja::Environment;
 
+mod args;
 mod auth;
 mod chrono;
+mod crypto;
+mod data;
+mod encoding;
+mod exec;
+mod files;
 mod process_env;
 mod prompt;
+mod random;
 
-pub(super) fn register_functions(env: &mut Environment) {
+pub(super) use prompt::prompt_placeholder;
+
+/// `ctx` holds the variables of the environment, templates included by `include_template()` use them too
+pub(super) fn register_functions(env: &mut Environment, session: &RenderSession, ctx: &Value) {
     env.add_function("processEnv", process_env::process_env);
     env.add_function("process_env", process_env::process_env);
     // this provides lazy env var lookup
     env.add_global("env", Value::from_struct_object(process_env::ProcessEnv));
 
-    prompt::register_functions(env);
+    prompt::register_functions(env, session);
     auth::register_functions(env);
+    encoding::register_functions(env);
+    data::register_functions(env);
     chrono::register_functions(env);
+    crypto::register_functions(env);
+    random::register_functions(env, session);
+    files::register_functions(env, session, ctx);
+    exec::register_functions(env, session);
 }
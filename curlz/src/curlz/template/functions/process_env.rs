@@ -58,7 +58,7 @@ mod tests {
 
     #[test]
     fn should_resolve_lazy_via_env_virtuell_object() {
-        let mut r = Renderer::new(&Environment::default());
+        let r = Renderer::new(&Environment::default());
         #[cfg(not(windows))]
         assert_eq!(
             r.render(r#"{{ env.USER }}"#, "template").unwrap(),
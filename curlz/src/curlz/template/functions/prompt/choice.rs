@@ -0,0 +1,297 @@
+use super::{invalid_answer, stdin_error, Prompter, Question};
+use crate::template::functions::args::Arguments;
+
+use dialoguer::{Confirm, MultiSelect, Select};
+use minijinja::value::{Rest, Value, ValueKind};
+use minijinja::{Error, ErrorKind, State};
+
+/// prompt for a yes or no answer
+///
+/// `prompt_confirm(label, default=false, remember=false)`
+pub(super) fn prompt_confirm(
+    prompter: Prompter,
+) -> impl Fn(&State, Value, Rest<Value>) -> Result<Value, Error> + Send + Sync + 'static {
+    move |state, prompt, args| {
+        let args = Arguments::new(&args);
+        let default: Option<bool> = args.get(0, "default")?;
+        let remember = args.keyword("remember")?.unwrap_or(false);
+        args.assert_all_used()?;
+
+        let label = prompt.to_string();
+        let question = Question {
+            label: &label,
+            default: default.map(Value::from),
+            remember,
+        };
+        prompter.ask(
+            state,
+            question,
+            |value| {
+                parse_bool(&value)
+                    .map(Value::from)
+                    .ok_or_else(|| invalid_answer(&label, &value, "is neither yes nor no"))
+            },
+            |remembered| {
+                let default = remembered.map(|answer| answer.is_true()).or(default);
+
+                Confirm::new()
+                    .with_prompt(&label)
+                    .default(default.unwrap_or(false))
+                    .interact()
+                    .map(Value::from)
+                    .map_err(stdin_error)
+            },
+        )
+    }
+}
+
+/// prompt to select one of the given `options`
+///
+/// `prompt_select(label, ["eu-west-1", "us-east-1"], default="us-east-1", remember=false)`
+pub(super) fn prompt_select(
+    prompter: Prompter,
+) -> impl Fn(&State, Value, Vec<Value>, Rest<Value>) -> Result<Value, Error> + Send + Sync + 'static
+{
+    move |state, prompt, options, args| {
+        let args = Arguments::new(&args);
+        let default: Option<Value> = args.get(0, "default")?;
+        let remember = args.keyword("remember")?.unwrap_or(false);
+        args.assert_all_used()?;
+        check_options(&options)?;
+        let default = default
+            .map(|default| position_of(&options, &default))
+            .transpose()?;
+
+        let label = prompt.to_string();
+        let question = Question {
+            label: &label,
+            default: default.map(|i| options[i].clone()),
+            remember,
+        };
+        prompter.ask(
+            state,
+            question,
+            |value| {
+                position_of(&options, &value)
+                    .map(|i| options[i].clone())
+                    .map_err(|_| invalid_answer(&label, &value, "is not one of the options"))
+            },
+            |remembered| {
+                let default = remembered
+                    .and_then(|answer| position_of(&options, &answer).ok())
+                    .or(default);
+
+                let index = Select::new()
+                    .with_prompt(&label)
+                    .items(&options)
+                    .default(default.unwrap_or_default())
+                    .interact()
+                    .map_err(stdin_error)?;
+
+                Ok(options[index].clone())
+            },
+        )
+    }
+}
+
+/// prompt to select any number of the given `options`, the answer is a list
+///
+/// `prompt_multi(label, ["read", "write", "admin"], defaults=["read"], remember=false)`
+pub(super) fn prompt_multi(
+    prompter: Prompter,
+) -> impl Fn(&State, Value, Vec<Value>, Rest<Value>) -> Result<Value, Error> + Send + Sync + 'static
+{
+    move |state, prompt, options, args| {
+        let args = Arguments::new(&args);
+        let defaults: Option<Vec<Value>> = args.get(0, "defaults")?;
+        let remember = args.keyword("remember")?.unwrap_or(false);
+        args.assert_all_used()?;
+        check_options(&options)?;
+        let defaults = defaults
+            .map(|defaults| {
+                defaults
+                    .iter()
+                    .map(|default| position_of(&options, default))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+
+        let label = prompt.to_string();
+        let selected = |indices: &[usize]| {
+            Value::from(
+                indices
+                    .iter()
+                    .map(|&i| options[i].clone())
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let question = Question {
+            label: &label,
+            default: defaults.as_deref().map(selected),
+            remember,
+        };
+        prompter.ask(
+            state,
+            question,
+            |value| {
+                let answers: Vec<Value> = match value.kind() {
+                    ValueKind::Seq => value.try_iter()?.collect(),
+                    _ => value
+                        .to_string()
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|answer| !answer.is_empty())
+                        .map(Value::from)
+                        .collect(),
+                };
+                let indices = answers
+                    .iter()
+                    .map(|answer| position_of(&options, answer))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| invalid_answer(&label, &value, "are not all options"))?;
+
+                Ok(selected(&indices))
+            },
+            |remembered| {
+                let defaults = remembered
+                    .and_then(|answer| answer.try_iter().ok().map(Iterator::collect::<Vec<_>>))
+                    .map(|answer| {
+                        answer
+                            .iter()
+                            .filter_map(|a| position_of(&options, a).ok())
+                            .collect()
+                    })
+                    .or(defaults)
+                    .unwrap_or_default();
+                let mut checked = vec![false; options.len()];
+                defaults.into_iter().for_each(|i| checked[i] = true);
+
+                let indices = MultiSelect::new()
+                    .with_prompt(&label)
+                    .items(&options)
+                    .defaults(&checked)
+                    .interact()
+                    .map_err(stdin_error)?;
+
+                Ok(selected(&indices))
+            },
+        )
+    }
+}
+
+/// a yes or no answer, e.g. from a variable like `CONFIRM=yes`
+pub(super) fn parse_bool(value: &Value) -> Option<bool> {
+    match value.kind() {
+        ValueKind::Bool => Some(value.is_true()),
+        _ => match value.to_string().trim().to_lowercase().as_str() {
+            "true" | "yes" | "y" | "1" => Some(true),
+            "false" | "no" | "n" | "0" => Some(false),
+            _ => None,
+        },
+    }
+}
+
+fn check_options(options: &[Value]) -> Result<(), Error> {
+    if options.is_empty() {
+        Err(Error::new(
+            ErrorKind::InvalidOperation,
+            "a prompt needs at least one option to choose from",
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// the option equal to the `answer`, a text answer also matches an option like `8080`
+fn position_of(options: &[Value], answer: &Value) -> Result<usize, Error> {
+    options
+        .iter()
+        .position(|o| o == answer)
+        .or_else(|| {
+            let answer = answer.to_string();
+            options.iter().position(|o| o.to_string() == answer)
+        })
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidOperation,
+                format!("the default `{answer}` is not one of the options"),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::no_env_vars;
+    use super::*;
+    use crate::template::answers::Answers;
+    use crate::test_utils::RenderBuilder;
+    use rstest::rstest;
+
+    fn render_non_interactive(template: &str) -> String {
+        let prompter = Prompter {
+            answers: Answers::default(),
+            interactive: false,
+            env_var: no_env_vars,
+        };
+        RenderBuilder::new()
+            .with_function("prompt_confirm", prompt_confirm(prompter.clone()))
+            .with_function("prompt_select", prompt_select(prompter.clone()))
+            .with_function("prompt_multi", prompt_multi(prompter))
+            .with_env_var("VERBOSE", "yes")
+            .with_env_var("port", "8080")
+            .with_env_var("scopes", "read, admin")
+            .render(template)
+    }
+
+    #[rstest]
+    #[case(Value::from(true), Some(true))]
+    #[case(Value::from("Yes"), Some(true))]
+    #[case(Value::from("0"), Some(false))]
+    #[case(Value::from("n"), Some(false))]
+    #[case(Value::from("maybe"), None)]
+    fn should_parse_yes_or_no(#[case] value: Value, #[case] expected: Option<bool>) {
+        assert_eq!(parse_bool(&value), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "the default `ap` is not one of the options")]
+    fn should_throw_on_unknown_defaults() {
+        let options = [Value::from("eu"), Value::from("us")];
+
+        position_of(&options, &Value::from("ap")).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "a prompt needs at least one option to choose from")]
+    fn should_throw_without_options() {
+        check_options(&[]).unwrap();
+    }
+
+    #[rstest]
+    #[case(r#"{{ prompt_confirm("Verbose") }}"#, "true")]
+    #[case(r#"{{ prompt_confirm("Dry Run", default=false) }}"#, "false")]
+    #[case(r#"{{ prompt_select("Port", [80, 8080]) + 1 }}"#, "8081")]
+    #[case(r#"{{ prompt_select("Region", ["eu", "us"], default="us") }}"#, "us")]
+    #[case(
+        r#"{{ prompt_multi("Scopes", ["read", "write", "admin"]) }}"#,
+        r#"["read", "admin"]"#
+    )]
+    #[case(
+        r#"{{ prompt_multi("Roles", ["a", "b"], defaults=["b"]) }}"#,
+        r#"["b"]"#
+    )]
+    fn should_answer_from_variables_when_not_interactive(
+        #[case] template: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(render_non_interactive(template), expected);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the answer `read, admin` of the prompt `Scopes` are not all options"
+    )]
+    fn should_throw_on_unknown_options_of_variables() {
+        render_non_interactive(r#"{{ prompt_multi("Scopes", ["read", "write"]) }}"#);
+    }
+}
@@ -0,0 +1,281 @@
+use super::{invalid_answer, stdin_error, Prompter, Question};
+use crate::template::functions::args::Arguments;
+
+use dialoguer::{Input, Password};
+use minijinja::value::{Rest, Value, ValueKind};
+use minijinja::{Error, ErrorKind, State};
+use regex::Regex;
+
+/// the label of `prompt_password()` without arguments
+const PASSWORD_LABEL: &str = "Password";
+
+/// prompt for a password, to be used in a minijinja template, it is never remembered
+///
+/// `prompt_password(label="Password")`
+pub(super) fn prompt_password(
+    prompter: Prompter,
+) -> impl Fn(&State, Rest<Value>) -> Result<Value, Error> + Send + Sync + 'static {
+    move |state, args| {
+        let args = Arguments::new(&args);
+        let label: String = args
+            .get(0, "label")?
+            .unwrap_or_else(|| PASSWORD_LABEL.to_string());
+        args.assert_all_used()?;
+
+        let question = Question {
+            label: &label,
+            default: None,
+            remember: false,
+        };
+        prompter.ask(
+            state,
+            question,
+            |value| Ok(Value::from(value.to_string())),
+            |_| {
+                Password::new()
+                    .with_prompt(&label)
+                    .allow_empty_password(true)
+                    .interact()
+                    .map(Value::from)
+                    .map_err(|e| {
+                        Error::new(ErrorKind::UndefinedError, "cannot read password from stdin")
+                            .with_source(e)
+                    })
+            },
+        )
+    }
+}
+
+/// prompt for something that has a name, to be used in a minijinja template
+///
+/// `prompt_for(label, default="", validate="^[a-z]+$", remember=false)`
+pub(super) fn prompt_for(
+    prompter: Prompter,
+) -> impl Fn(&State, Value, Rest<Value>) -> Result<Value, Error> + Send + Sync + 'static {
+    move |state, prompt, args| {
+        let args = Arguments::new(&args);
+        let default: Option<String> = args.get(0, "default")?;
+        let validate = args
+            .get::<String>(1, "validate")?
+            .map(|pattern| anchored_regex(&pattern))
+            .transpose()?;
+        let remember = args.keyword("remember")?.unwrap_or(false);
+        args.assert_all_used()?;
+
+        let label = prompt.to_string();
+        let question = Question {
+            label: &label,
+            default: default.clone().map(Value::from),
+            remember,
+        };
+        let is_valid = |answer: &str| validate.as_ref().is_none_or(|v| v.is_match(answer));
+        prompter.ask(
+            state,
+            question,
+            |value| {
+                let answer = value.to_string();
+                if is_valid(&answer) {
+                    Ok(Value::from(answer))
+                } else {
+                    Err(invalid_answer(&label, &value, "is not valid"))
+                }
+            },
+            |remembered| {
+                let default = remembered
+                    .map(|answer| answer.to_string())
+                    .filter(|answer| is_valid(answer))
+                    .or(default);
+
+                let mut input = Input::<String>::new();
+                input.with_prompt(&label).allow_empty(true);
+                if let Some(default) = default {
+                    input.default(default);
+                }
+                if let Some(validate) = validate.clone() {
+                    input.validate_with(move |answer: &String| {
+                        if validate.is_match(answer) {
+                            Ok(())
+                        } else {
+                            Err(format!("the answer must match `{}`", validate.as_str()))
+                        }
+                    });
+                }
+
+                input.interact_text().map(Value::from).map_err(stdin_error)
+            },
+        )
+    }
+}
+
+/// prompt for a whole number within `min` and `max` (inclusive)
+///
+/// `prompt_number(label, min, max, default=1, remember=false)`
+pub(super) fn prompt_number(
+    prompter: Prompter,
+) -> impl Fn(&State, Value, i64, i64, Rest<Value>) -> Result<Value, Error> + Send + Sync + 'static {
+    move |state, prompt, min, max, args| {
+        let args = Arguments::new(&args);
+        let default: Option<i64> = args.get(0, "default")?;
+        let remember = args.keyword("remember")?.unwrap_or(false);
+        args.assert_all_used()?;
+        check_range(min, max, default)?;
+
+        let label = prompt.to_string();
+        let question = Question {
+            label: &label,
+            default: default.map(Value::from),
+            remember,
+        };
+        prompter.ask(
+            state,
+            question,
+            |value| {
+                let number = match value.kind() {
+                    ValueKind::Number => i64::try_from(value.clone()).ok(),
+                    _ => value.to_string().trim().parse().ok(),
+                };
+                number
+                    .filter(|number| (min..=max).contains(number))
+                    .map(Value::from)
+                    .ok_or_else(|| {
+                        invalid_answer(
+                            &label,
+                            &value,
+                            format!("is not a number between {min} and {max}"),
+                        )
+                    })
+            },
+            |remembered| {
+                let default = remembered
+                    .and_then(|answer| i64::try_from(answer).ok())
+                    .filter(|answer| (min..=max).contains(answer))
+                    .or(default);
+
+                let mut input = Input::<i64>::new();
+                input
+                    .with_prompt(&label)
+                    .validate_with(move |answer: &i64| {
+                        if (min..=max).contains(answer) {
+                            Ok(())
+                        } else {
+                            Err(format!("the number must be between {min} and {max}"))
+                        }
+                    });
+                if let Some(default) = default {
+                    input.default(default);
+                }
+
+                input.interact_text().map(Value::from).map_err(stdin_error)
+            },
+        )
+    }
+}
+
+/// the whole answer must match the `pattern`, not just a part of it
+fn anchored_regex(pattern: &str) -> Result<Regex, Error> {
+    Regex::new(&format!("^(?:{pattern})$")).map_err(|e| {
+        Error::new(
+            ErrorKind::InvalidOperation,
+            format!("invalid `validate` pattern `{pattern}`"),
+        )
+        .with_source(e)
+    })
+}
+
+fn check_range(min: i64, max: i64, default: Option<i64>) -> Result<(), Error> {
+    if min > max {
+        return Err(Error::new(
+            ErrorKind::InvalidOperation,
+            format!("prompt_number `min` ({min}) must not be greater than `max` ({max})"),
+        ));
+    }
+    match default {
+        Some(default) if !(min..=max).contains(&default) => Err(Error::new(
+            ErrorKind::InvalidOperation,
+            format!("prompt_number `default` ({default}) must be between {min} and {max}"),
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::no_env_vars;
+    use super::*;
+    use crate::template::answers::Answers;
+    use crate::test_utils::RenderBuilder;
+    use rstest::rstest;
+
+    fn render_non_interactive(template: &str) -> String {
+        let prompter = Prompter {
+            answers: Answers::default(),
+            interactive: false,
+            env_var: no_env_vars,
+        };
+        RenderBuilder::new()
+            .with_function("prompt_for", prompt_for(prompter.clone()))
+            .with_function("prompt_number", prompt_number(prompter.clone()))
+            .with_function("prompt_password", prompt_password(prompter))
+            .with_env_var("tenant", "acme")
+            .with_env_var("PORT", "8080")
+            .with_env_var("Password", "secret")
+            .render(template)
+    }
+
+    #[rstest]
+    #[case("[a-z]+", "tenant", true)]
+    #[case("[a-z]+", "tenant-1", false)]
+    #[case("eu|us", "eu", true)]
+    #[case("eu|us", "europe", false)]
+    fn should_match_the_whole_answer(
+        #[case] pattern: &str,
+        #[case] answer: &str,
+        #[case] expected: bool,
+    ) {
+        assert_eq!(anchored_regex(pattern).unwrap().is_match(answer), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid `validate` pattern `[a-z`")]
+    fn should_throw_on_invalid_patterns() {
+        anchored_regex("[a-z").unwrap();
+    }
+
+    #[rstest]
+    #[case(1, 10, None)]
+    #[case(1, 10, Some(10))]
+    #[case(-5, -5, Some(-5))]
+    fn should_accept_valid_ranges(
+        #[case] min: i64,
+        #[case] max: i64,
+        #[case] default: Option<i64>,
+    ) {
+        check_range(min, max, default).unwrap();
+    }
+
+    #[test]
+    #[should_panic(expected = "prompt_number `default` (11) must be between 1 and 10")]
+    fn should_throw_on_defaults_outside_of_the_range() {
+        check_range(1, 10, Some(11)).unwrap();
+    }
+
+    #[rstest]
+    #[case(r#"{{ prompt_for("Tenant", validate="[a-z]+") }}"#, "acme")]
+    #[case(r#"{{ prompt_for("Region", default="eu") }}"#, "eu")]
+    #[case(r#"{{ prompt_number("Port", 1, 65535) + 1 }}"#, "8081")]
+    #[case(r#"{{ prompt_password() }}"#, "secret")]
+    fn should_answer_from_variables_when_not_interactive(
+        #[case] template: &str,
+        #[case] expected: &str,
+    ) {
+        assert_eq!(render_non_interactive(template), expected);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the answer `8080` of the prompt `Port` is not a number between 1 and 80"
+    )]
+    fn should_throw_on_invalid_answers_of_variables() {
+        render_non_interactive(r#"{{ prompt_number("Port", 1, 80) }}"#);
+    }
+}
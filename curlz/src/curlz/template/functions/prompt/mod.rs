@@ -0,0 +1,256 @@
+use crate::template::answers::{env_var_name, variable_names, Answers};
+use crate::template::session::RenderSession;
+use crate::template::variables::Placeholder;
+
+use minijinja::value::Value;
+use minijinja::{Environment, Error, ErrorKind, State};
+
+mod choice;
+mod input;
+mod placeholder;
+
+pub fn register_functions(env: &mut Environment, session: &RenderSession) {
+    let prompter = Prompter::from(session);
+
+    env.add_function("prompt_password", input::prompt_password(prompter.clone()));
+    env.add_function("prompt_for", input::prompt_for(prompter.clone()));
+    env.add_function("prompt_number", input::prompt_number(prompter.clone()));
+    env.add_function("prompt_confirm", choice::prompt_confirm(prompter.clone()));
+    env.add_function("prompt_select", choice::prompt_select(prompter.clone()));
+    env.add_function("prompt_multi", choice::prompt_multi(prompter));
+}
+
+/// asks for the value of a bookmark `placeholder`, that is not satisfied by the environment
+pub fn prompt_placeholder(
+    session: &RenderSession,
+    placeholder: &Placeholder,
+) -> Result<Value, Error> {
+    placeholder::prompt_placeholder(&Prompter::from(session), placeholder)
+}
+
+/// what a prompt asks for
+struct Question<'a> {
+    label: &'a str,
+    default: Option<Value>,
+    remember: bool,
+}
+
+/// asks questions on behalf of the prompt functions
+#[derive(Clone)]
+struct Prompter {
+    answers: Answers,
+    interactive: bool,
+    /// looks up an env var of the process, like `CURLZ_API_TOKEN`
+    env_var: fn(&str) -> Option<String>,
+}
+
+impl From<&RenderSession> for Prompter {
+    fn from(session: &RenderSession) -> Self {
+        Self {
+            answers: session.answers(),
+            interactive: session.is_interactive(),
+            env_var: |name| std::env::var(name).ok(),
+        }
+    }
+}
+
+impl Prompter {
+    /// asks a question only once per run, a given answer is reused for the same label
+    ///
+    /// - `parse` turns the value of a variable into an answer, when the run is not interactive
+    /// - `prompt` asks the user, it gets the remembered answer of the last run as default
+    fn ask(
+        &self,
+        state: &State,
+        question: Question,
+        parse: impl FnOnce(Value) -> Result<Value, Error>,
+        prompt: impl FnOnce(Option<Value>) -> Result<Value, Error>,
+    ) -> Result<Value, Error> {
+        self.answer(question, |name| state.lookup(name), parse, prompt)
+    }
+
+    fn answer(
+        &self,
+        question: Question,
+        lookup: impl Fn(&str) -> Option<Value>,
+        parse: impl FnOnce(Value) -> Result<Value, Error>,
+        prompt: impl FnOnce(Option<Value>) -> Result<Value, Error>,
+    ) -> Result<Value, Error> {
+        let Question {
+            label,
+            default,
+            remember,
+        } = question;
+        if let Some(answer) = self.answers.given(label) {
+            return Ok(answer);
+        }
+        let remembered = remember.then(|| self.answers.remembered(label)).flatten();
+
+        let answer = if self.interactive {
+            prompt(remembered)?
+        } else if let Some(value) = variable_of(label, lookup, self.env_var) {
+            parse(value)?
+        } else if let Some(answer) = remembered.or(default) {
+            answer
+        } else {
+            // renders empty, so that all unanswered prompts are found before the request is refused
+            self.answers.unanswered(label);
+            return Ok(Value::from(""));
+        };
+
+        self.answers
+            .give(label, answer.clone(), remember && self.interactive)
+            .map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidOperation,
+                    format!("cannot remember the answer of `{label}`: {e}"),
+                )
+            })?;
+
+        Ok(answer)
+    }
+}
+
+/// looks up the template variables named after the `label`, then its process env var like `CURLZ_API_TOKEN`,
+/// other env vars like `USER` or `PATH` never answer a prompt
+fn variable_of(
+    label: &str,
+    lookup: impl Fn(&str) -> Option<Value>,
+    env_var: impl Fn(&str) -> Option<String>,
+) -> Option<Value> {
+    variable_names(label)
+        .iter()
+        .find_map(|name| lookup(name).filter(|v| !v.is_undefined() && !v.is_none()))
+        .or_else(|| env_var(&env_var_name(label)).map(Value::from))
+}
+
+fn invalid_answer(label: &str, answer: &Value, reason: impl std::fmt::Display) -> Error {
+    Error::new(
+        ErrorKind::InvalidOperation,
+        format!("the answer `{answer}` of the prompt `{label}` {reason}"),
+    )
+}
+
+fn stdin_error(e: std::io::Error) -> Error {
+    Error::new(ErrorKind::UndefinedError, "cannot read prompt from stdin").with_source(e)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn question(label: &str) -> Question<'_> {
+        Question {
+            label,
+            default: None,
+            remember: false,
+        }
+    }
+
+    fn no_variables(_: &str) -> Option<Value> {
+        None
+    }
+
+    pub(super) fn no_env_vars(_: &str) -> Option<String> {
+        None
+    }
+
+    #[test]
+    fn should_ask_only_once_per_label() {
+        let prompter = Prompter {
+            answers: Answers::default(),
+            interactive: true,
+            env_var: no_env_vars,
+        };
+        let mut asked = 0;
+        let mut ask = |label: &str| {
+            prompter
+                .answer(question(label), no_variables, Ok, |_| {
+                    asked += 1;
+                    Ok(Value::from(format!("answer {asked}")))
+                })
+                .unwrap()
+        };
+
+        assert_eq!(ask("Tenant"), Value::from("answer 1"));
+        assert_eq!(ask("Tenant"), Value::from("answer 1"));
+        assert_eq!(ask("Region"), Value::from("answer 2"));
+    }
+
+    #[test]
+    fn should_offer_remembered_answers_as_default() {
+        let tmp = tempfile::tempdir().unwrap();
+        let file = tmp.path().join("answers.yml");
+        let ask = |remember: bool, answer: &str| {
+            let prompter = Prompter {
+                answers: Answers::default().with_file(&file),
+                interactive: true,
+                env_var: no_env_vars,
+            };
+            let question = Question {
+                remember,
+                ..question("Tenant")
+            };
+            prompter
+                .answer(question, no_variables, Ok, |remembered| {
+                    Ok(remembered.unwrap_or_else(|| Value::from(answer)))
+                })
+                .unwrap()
+        };
+
+        assert_eq!(ask(true, "acme"), Value::from("acme"));
+        assert_eq!(ask(true, "globex"), Value::from("acme"));
+        assert_eq!(ask(false, "globex"), Value::from("globex"));
+    }
+
+    #[test]
+    fn should_answer_from_variables_when_not_interactive() {
+        let prompter = Prompter {
+            answers: Answers::default(),
+            interactive: false,
+            env_var: no_env_vars,
+        };
+        let variables = |name: &str| (name == "GITHUB_USERNAME").then(|| Value::from("octocat"));
+        let default = Question {
+            default: Some(Value::from("eu")),
+            ..question("Region")
+        };
+
+        let never_prompt = |_| unreachable!("must not prompt when not interactive");
+        assert_eq!(
+            prompter
+                .answer(question("GitHub Username"), variables, Ok, never_prompt)
+                .unwrap(),
+            Value::from("octocat")
+        );
+        assert_eq!(
+            prompter
+                .answer(default, variables, Ok, never_prompt)
+                .unwrap(),
+            Value::from("eu")
+        );
+        assert!(prompter
+            .answer(question("Tenant"), variables, Ok, never_prompt)
+            .unwrap()
+            .to_string()
+            .is_empty());
+        assert!(prompter
+            .answer(question("Token"), variables, Ok, never_prompt)
+            .unwrap()
+            .to_string()
+            .is_empty());
+        assert_eq!(prompter.answers.take_unanswered(), ["Tenant", "Token"]);
+    }
+
+    #[test]
+    fn should_answer_only_from_curlz_env_vars() {
+        let user = |name: &str| (name == "USER").then(|| "root".to_string());
+        assert_eq!(variable_of("User", no_variables, user), None);
+
+        let curlz_user = |name: &str| (name == "CURLZ_USER").then(|| "john".to_string());
+        assert_eq!(
+            variable_of("User", no_variables, curlz_user),
+            Some(Value::from("john"))
+        );
+    }
+}
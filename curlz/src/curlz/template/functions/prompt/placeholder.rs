@@ -0,0 +1,191 @@
+use super::choice::parse_bool;
+use super::{invalid_answer, stdin_error, Prompter, Question};
+use crate::template::variables::{Placeholder, PlaceholderType};
+
+use dialoguer::{Confirm, Input, Password, Select};
+use minijinja::value::Value;
+use minijinja::Error;
+
+/// asks for the value of a `placeholder` of a bookmark, according to its type,
+/// like the prompt functions it is asked only once per run and never when not interactive
+pub(super) fn prompt_placeholder(
+    prompter: &Prompter,
+    placeholder: &Placeholder,
+) -> Result<Value, Error> {
+    let Placeholder {
+        name,
+        default,
+        prompt,
+        kind,
+        choices,
+        ..
+    } = placeholder;
+    let label = prompt.as_deref().unwrap_or(name);
+    let default = default
+        .as_deref()
+        .map(|default| parse(*kind, name, Value::from(default)))
+        .transpose()?;
+    let question = Question {
+        label: name,
+        default: default.clone(),
+        remember: false,
+    };
+
+    prompter.answer(
+        question,
+        |_| None,
+        |value| {
+            if !choices.is_empty() && !choices.contains(&value.to_string()) {
+                return Err(invalid_answer(name, &value, "is not one of the choices"));
+            }
+            parse(*kind, name, value)
+        },
+        |_| match kind {
+            _ if !choices.is_empty() => {
+                let index = Select::new()
+                    .with_prompt(label)
+                    .items(choices)
+                    .default(
+                        default
+                            .and_then(|d| choices.iter().position(|c| *c == d.to_string()))
+                            .unwrap_or_default(),
+                    )
+                    .interact()
+                    .map_err(stdin_error)?;
+                parse(*kind, name, Value::from(choices[index].as_str()))
+            }
+            PlaceholderType::String => {
+                let mut input = Input::<String>::new();
+                input.with_prompt(label).allow_empty(true);
+                if let Some(default) = default {
+                    input.default(default.to_string());
+                }
+                input.interact_text().map(Value::from).map_err(stdin_error)
+            }
+            PlaceholderType::Number => {
+                let mut input = Input::<String>::new();
+                input.with_prompt(label).validate_with(|answer: &String| {
+                    match answer.trim().parse::<f64>() {
+                        Ok(_) => Ok(()),
+                        Err(_) => Err("the answer must be a number"),
+                    }
+                });
+                if let Some(default) = default {
+                    input.default(default.to_string());
+                }
+                let answer = input.interact_text().map_err(stdin_error)?;
+                parse(*kind, name, Value::from(answer))
+            }
+            PlaceholderType::Bool => Confirm::new()
+                .with_prompt(label)
+                .default(default.is_some_and(|default| default.is_true()))
+                .interact()
+                .map(Value::from)
+                .map_err(stdin_error),
+            PlaceholderType::Secret => Password::new()
+                .with_prompt(label)
+                .allow_empty_password(true)
+                .interact()
+                .map(Value::from)
+                .map_err(stdin_error),
+        },
+    )
+}
+
+/// turns a text, like the default or a variable, into a value of the placeholder type
+fn parse(kind: PlaceholderType, name: &str, value: Value) -> Result<Value, Error> {
+    match kind {
+        PlaceholderType::String | PlaceholderType::Secret => Ok(Value::from(value.to_string())),
+        PlaceholderType::Number => {
+            let text = value.to_string();
+            let text = text.trim();
+            text.parse::<i64>()
+                .map(Value::from)
+                .or_else(|_| text.parse::<f64>().map(Value::from))
+                .map_err(|_| invalid_answer(name, &value, "is not a number"))
+        }
+        PlaceholderType::Bool => parse_bool(&value)
+            .map(Value::from)
+            .ok_or_else(|| invalid_answer(name, &value, "is neither yes nor no")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::no_env_vars;
+    use super::*;
+    use crate::template::answers::Answers;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(PlaceholderType::String, "42", Value::from("42"))]
+    #[case(PlaceholderType::Number, "42", Value::from(42))]
+    #[case(PlaceholderType::Number, " 0.5", Value::from(0.5))]
+    #[case(PlaceholderType::Bool, "yes", Value::from(true))]
+    #[case(PlaceholderType::Secret, "s3cr3t", Value::from("s3cr3t"))]
+    fn should_parse_values_by_type(
+        #[case] kind: PlaceholderType,
+        #[case] value: &str,
+        #[case] expected: Value,
+    ) {
+        assert_eq!(parse(kind, "test", Value::from(value)).unwrap(), expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "the answer `many` of the prompt `id` is not a number")]
+    fn should_throw_on_values_of_the_wrong_type() {
+        parse(PlaceholderType::Number, "id", Value::from("many")).unwrap();
+    }
+
+    #[test]
+    fn should_take_defaults_when_not_interactive() {
+        let prompter = Prompter {
+            answers: Answers::default(),
+            interactive: false,
+            env_var: no_env_vars,
+        };
+        let page = Placeholder {
+            name: "curlz_test_page".to_string(),
+            default: Some("1".to_string()),
+            kind: PlaceholderType::Number,
+            ..Default::default()
+        };
+        let email = Placeholder {
+            name: "curlz_test_email".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            prompt_placeholder(&prompter, &page).unwrap(),
+            Value::from(1)
+        );
+        assert_eq!(
+            prompt_placeholder(&prompter, &email).unwrap(),
+            Value::from("")
+        );
+        assert_eq!(prompter.answers.take_unanswered(), ["curlz_test_email"]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the answer `closed` of the prompt `test_status` is not one of the choices"
+    )]
+    fn should_throw_on_answers_that_are_no_choice() {
+        let prompter = Prompter {
+            answers: Answers::default(),
+            interactive: false,
+            env_var: |name| match name {
+                "CURLZ_TEST_STATUS" => Some("closed".to_string()),
+                "TEST_STATUS" => Some("sold".to_string()),
+                _ => None,
+            },
+        };
+        let status = Placeholder {
+            name: "test_status".to_string(),
+            choices: vec!["available".to_string(), "sold".to_string()],
+            ..Default::default()
+        };
+
+        prompt_placeholder(&prompter, &status).unwrap();
+    }
+}
@@ -0,0 +1,135 @@
+use crate::template::session::SharedRng;
+use minijinja::{Error, ErrorKind, State};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+const KINDS: &str = "name, first_name, last_name, username, email, company, word, ipv4, ipv6";
+
+const FIRST_NAMES: &[&str] = &[
+    "Ada", "Alan", "Barbara", "Brian", "Carol", "Dennis", "Edsger", "Frances", "Grace", "Guido",
+    "Hedy", "Ken", "Linus", "Margaret", "Niklaus", "Radia", "Sophie", "Tim", "Yukihiro", "Zoe",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Allen",
+    "Backus",
+    "Dijkstra",
+    "Goldberg",
+    "Hamilton",
+    "Hopper",
+    "Kernighan",
+    "Knuth",
+    "Lamarr",
+    "Liskov",
+    "Lovelace",
+    "Matsumoto",
+    "Perlman",
+    "Ritchie",
+    "Rossum",
+    "Thompson",
+    "Torvalds",
+    "Turing",
+    "Wilson",
+    "Wirth",
+];
+
+const COMPANIES: &[&str] = &[
+    "Acme",
+    "Globex",
+    "Initech",
+    "Umbrella",
+    "Hooli",
+    "Stark Industries",
+    "Wayne Enterprises",
+    "Cyberdyne",
+    "Soylent",
+    "Wonka",
+];
+
+const WORDS: &[&str] = &[
+    "alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel", "india", "juliet",
+    "kilo", "lima", "mike", "november", "oscar", "papa", "quebec", "romeo", "sierra", "tango",
+];
+
+/// reserved for documentation, see [RFC 2606](https://www.rfc-editor.org/rfc/rfc2606)
+const EMAIL_DOMAINS: &[&str] = &["example.com", "example.org", "example.net"];
+
+/// creates the `fake` template function, it generates realistic looking test data of a `kind`
+///
+/// `fake("email")`
+pub(super) fn fake(
+    rng: SharedRng,
+) -> impl Fn(&State, &str) -> Result<String, Error> + Send + Sync + 'static {
+    move |_, kind| {
+        let mut rng = rng.lock().unwrap();
+        let rng = &mut *rng;
+
+        Ok(match kind {
+            "name" => format!("{} {}", pick(rng, FIRST_NAMES), pick(rng, LAST_NAMES)),
+            "first_name" => pick(rng, FIRST_NAMES).to_string(),
+            "last_name" => pick(rng, LAST_NAMES).to_string(),
+            "username" => username(rng),
+            "email" => format!("{}@{}", username(rng), pick(rng, EMAIL_DOMAINS)),
+            "company" => pick(rng, COMPANIES).to_string(),
+            "word" => pick(rng, WORDS).to_string(),
+            "ipv4" => Ipv4Addr::from(rng.gen::<[u8; 4]>()).to_string(),
+            "ipv6" => Ipv6Addr::from(rng.gen::<[u8; 16]>()).to_string(),
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidOperation,
+                    format!("unsupported fake `{kind}`, use one of {KINDS}"),
+                ))
+            }
+        })
+    }
+}
+
+fn pick(rng: &mut StdRng, items: &[&'static str]) -> &'static str {
+    items.choose(rng).copied().unwrap_or_default()
+}
+
+fn username(rng: &mut StdRng) -> String {
+    format!(
+        "{}.{}{}",
+        pick(rng, FIRST_NAMES),
+        pick(rng, LAST_NAMES),
+        rng.gen_range(1..100)
+    )
+    .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::session::RenderSession;
+    use crate::test_utils::RenderBuilder;
+    use rstest::rstest;
+
+    fn render(template: &str) -> String {
+        RenderBuilder::new()
+            .with_function("fake", fake(RenderSession::new().with_seed(Some(7)).rng()))
+            .render(template)
+    }
+
+    #[rstest]
+    #[case("name", |s: &str| s.split(' ').count() == 2)]
+    #[case("email", |s: &str| s.contains('@') && EMAIL_DOMAINS.iter().any(|d| s.ends_with(d)))]
+    #[case("username", |s: &str| s.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '.'))]
+    #[case("ipv4", |s: &str| s.parse::<Ipv4Addr>().is_ok())]
+    #[case("ipv6", |s: &str| s.parse::<Ipv6Addr>().is_ok())]
+    fn test_fake(#[case] kind: &str, #[case] is_valid: fn(&str) -> bool) {
+        let template = format!(r#"{{{{ fake("{kind}") }}}}"#);
+        let result = render(&template);
+
+        assert!(is_valid(&result), "unexpected fake {kind}: {result}");
+        assert_eq!(result, render(&template));
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported fake `phone`")]
+    fn should_throw_on_unsupported_kinds() {
+        render(r#"{{ fake("phone") }}"#);
+    }
+}
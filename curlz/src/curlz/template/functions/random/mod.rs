@@ -0,0 +1,16 @@
+use crate::template::functions::chrono::clock::SystemClock;
+use crate::template::session::RenderSession;
+use minijinja::Environment;
+
+mod fake;
+mod uuid;
+mod values;
+
+pub(super) fn register_functions(env: &mut Environment, session: &RenderSession) {
+    env.add_function("uuid", uuid::uuid(session.rng()));
+    env.add_function("uuid_v7", uuid::uuid_v7(SystemClock, session.rng()));
+    env.add_function("random_int", values::random_int(session.rng()));
+    env.add_function("random_string", values::random_string(session.rng()));
+    env.add_function("random_choice", values::random_choice(session.rng()));
+    env.add_function("fake", fake::fake(session.rng()));
+}
@@ -0,0 +1,80 @@
+use crate::template::functions::chrono::clock::Clock;
+use crate::template::session::SharedRng;
+use minijinja::State;
+use rand::Rng;
+use uuid::Builder;
+
+/// creates the `uuid` template function, a random UUID version 4
+pub(super) fn uuid(rng: SharedRng) -> impl Fn(&State) -> String + Send + Sync + 'static {
+    move |_| {
+        let bytes = rng.lock().unwrap().gen();
+
+        Builder::from_random_bytes(bytes).into_uuid().to_string()
+    }
+}
+
+/// creates the `uuid_v7` template function, a time ordered UUID version 7
+pub(super) fn uuid_v7(
+    clock: impl Clock,
+    rng: SharedRng,
+) -> impl Fn(&State) -> String + Send + Sync + 'static {
+    move |_| {
+        let millis = clock.now().timestamp_millis().max(0) as u64;
+        let bytes = rng.lock().unwrap().gen();
+
+        Builder::from_unix_timestamp_millis(millis, &bytes)
+            .into_uuid()
+            .to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::functions::chrono::clock::FixedClock;
+    use crate::template::session::RenderSession;
+    use crate::test_utils::RenderBuilder;
+    use uuid::{Uuid, Version};
+
+    #[test]
+    fn test_uuid() {
+        let rng = RenderSession::new().rng();
+        let result = RenderBuilder::new()
+            .with_function("uuid", uuid(rng))
+            .render(r#"{{ uuid() }} {{ uuid() }}"#);
+        let (first, second) = result.split_once(' ').unwrap();
+
+        assert_eq!(
+            Uuid::parse_str(first).unwrap().get_version(),
+            Some(Version::Random)
+        );
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_uuid_v7() {
+        let rng = RenderSession::new().rng();
+        let result = RenderBuilder::new()
+            .with_function(
+                "uuid_v7",
+                uuid_v7(FixedClock::at_timestamp(1679679000), rng),
+            )
+            .render(r#"{{ uuid_v7() }}"#);
+        let uuid = Uuid::parse_str(&result).unwrap();
+
+        assert_eq!(uuid.get_version(), Some(Version::SortRand));
+        assert_eq!(uuid.get_timestamp().unwrap().to_unix(), (1679679000, 0));
+    }
+
+    #[test]
+    fn should_be_reproducible_with_a_seed() {
+        let render = || {
+            let rng = RenderSession::new().with_seed(Some(42)).rng();
+            RenderBuilder::new()
+                .with_function("uuid", uuid(rng))
+                .render(r#"{{ uuid() }}"#)
+        };
+
+        assert_eq!(render(), render());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::template::functions::args::Arguments;
+use crate::template::session::SharedRng;
+use minijinja::value::{Rest, Value};
+use minijinja::{Error, ErrorKind, State};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const ALPHA: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const LOWER: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const NUMERIC: &str = "0123456789";
+const HEX: &str = "0123456789abcdef";
+
+/// creates the `random_int` template function, a random number between `min` and `max` (inclusive)
+///
+/// `random_int(min, max)`
+pub(super) fn random_int(
+    rng: SharedRng,
+) -> impl Fn(&State, i64, i64) -> Result<i64, Error> + Send + Sync + 'static {
+    move |_, min, max| {
+        if min > max {
+            return Err(Error::new(
+                ErrorKind::InvalidOperation,
+                format!("random_int `min` ({min}) must not be greater than `max` ({max})"),
+            ));
+        }
+
+        Ok(rng.lock().unwrap().gen_range(min..=max))
+    }
+}
+
+/// creates the `random_string` template function, a random string of `len` characters
+/// out of a `charset`, which is either `alphanumeric` (default), `alpha`, `lower`, `upper`,
+/// `numeric`, `hex` or a string of the characters to pick from
+///
+/// `random_string(len, charset="alphanumeric")`
+pub(super) fn random_string(
+    rng: SharedRng,
+) -> impl Fn(&State, usize, Rest<Value>) -> Result<String, Error> + Send + Sync + 'static {
+    move |_, len, args| {
+        let args = Arguments::new(&args);
+        let charset: Option<String> = args.get(0, "charset")?;
+        args.assert_all_used()?;
+
+        let chars: Vec<char> = match charset.as_deref() {
+            None | Some("alphanumeric") => format!("{ALPHA}{NUMERIC}").chars().collect(),
+            Some("alpha") => ALPHA.chars().collect(),
+            Some("lower") => LOWER.chars().collect(),
+            Some("upper") => UPPER.chars().collect(),
+            Some("numeric") => NUMERIC.chars().collect(),
+            Some("hex") => HEX.chars().collect(),
+            Some(chars) => chars.chars().collect(),
+        };
+        if chars.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidOperation,
+                "random_string `charset` must not be empty",
+            ));
+        }
+
+        let mut rng = rng.lock().unwrap();
+        Ok((0..len)
+            .map(|_| chars[rng.gen_range(0..chars.len())])
+            .collect())
+    }
+}
+
+/// creates the `random_choice` template function, it picks a random item of a list
+///
+/// `random_choice(["red", "green", "blue"])`
+pub(super) fn random_choice(
+    rng: SharedRng,
+) -> impl Fn(&State, Vec<Value>) -> Result<Value, Error> + Send + Sync + 'static {
+    move |_, items| {
+        items
+            .choose(&mut *rng.lock().unwrap())
+            .cloned()
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidOperation,
+                    "random_choice needs a list with at least one item",
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::template::session::RenderSession;
+    use crate::test_utils::RenderBuilder;
+    use rstest::rstest;
+
+    fn render(template: &str) -> String {
+        let rng = RenderSession::new().with_seed(Some(42)).rng();
+        RenderBuilder::new()
+            .with_function("random_int", random_int(rng.clone()))
+            .with_function("random_string", random_string(rng.clone()))
+            .with_function("random_choice", random_choice(rng))
+            .render(template)
+    }
+
+    #[test]
+    fn test_random_int() {
+        let result: i64 = render(r#"{{ random_int(10, 20) }}"#).parse().unwrap();
+        assert!((10..=20).contains(&result));
+
+        assert_eq!(render(r#"{{ random_int(-3, -3) }}"#), "-3");
+    }
+
+    #[test]
+    #[should_panic(expected = "random_int `min` (2) must not be greater than `max` (1)")]
+    fn should_throw_on_an_empty_range() {
+        render(r#"{{ random_int(2, 1) }}"#);
+    }
+
+    #[rstest]
+    #[case(r#"{{ random_string(12) }}"#, |c: char| c.is_ascii_alphanumeric())]
+    #[case(r#"{{ random_string(12, "hex") }}"#, |c: char| c.is_ascii_hexdigit() && !c.is_ascii_uppercase())]
+    #[case(r#"{{ random_string(12, charset="numeric") }}"#, |c: char| c.is_ascii_digit())]
+    #[case(r#"{{ random_string(12, "xy") }}"#, |c: char| c == 'x' || c == 'y')]
+    fn test_random_string(#[case] template: &str, #[case] is_valid: fn(char) -> bool) {
+        let result = render(template);
+
+        assert_eq!(result.len(), 12);
+        assert!(result.chars().all(is_valid), "unexpected chars in {result}");
+    }
+
+    #[test]
+    fn test_random_choice() {
+        let result = render(r#"{{ random_choice(["red", "green", "blue"]) }}"#);
+
+        assert!(["red", "green", "blue"].contains(&result.as_str()));
+    }
+
+    #[test]
+    #[should_panic(expected = "random_choice needs a list with at least one item")]
+    fn should_throw_on_an_empty_choice() {
+        render(r#"{{ random_choice([]) }}"#);
+    }
+
+    #[test]
+    fn should_be_reproducible_with_a_seed() {
+        let template = r#"{{ random_int(0, 1000000) }} {{ random_string(16) }}"#;
+
+        assert_eq!(render(template), render(template));
+    }
+}
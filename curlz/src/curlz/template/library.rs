@@ -0,0 +1,130 @@
+//! the template library of a project, `.j2` files with macros in `.curlz/templates/`,
+//! that requests can import like `{% import "auth.j2" as auth %}{{ auth.header("svc") }}`
+use anyhow::Context;
+use minijinja::machinery::ast::{Expr, Stmt};
+use minijinja::machinery::parse;
+use minijinja::Source;
+use std::path::Path;
+
+/// the folder of the template library within the workspace folder
+pub const TEMPLATES_FOLDER: &str = "templates";
+
+/// the file extension of library templates
+const TEMPLATE_EXTENSION: &str = "j2";
+
+/// a template of the library and the macros it provides
+#[derive(Debug, PartialEq, Eq)]
+pub struct LibraryTemplate {
+    pub name: String,
+    /// the signatures of the macros, like `header(service, [scope])`
+    pub macros: Vec<String>,
+}
+
+/// loads the templates of the library in `dir` on demand, when they are imported or included
+pub(super) fn source(dir: &Path) -> Source {
+    Source::from_path(dir)
+}
+
+/// lists all templates of the library in `dir`, sorted by name
+pub fn list_templates(dir: impl AsRef<Path>) -> crate::Result<Vec<LibraryTemplate>> {
+    let dir = dir.as_ref();
+    if !dir.is_dir() {
+        return Ok(vec![]);
+    }
+
+    let mut templates = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path.extension().and_then(|ext| ext.to_str()) == Some(TEMPLATE_EXTENSION)
+        })
+        .map(|path| {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+            let source = std::fs::read_to_string(&path)?;
+            let macros =
+                macros_of(&source, &name).with_context(|| format!("invalid template `{name}`"))?;
+
+            Ok(LibraryTemplate { name, macros })
+        })
+        .collect::<crate::Result<Vec<_>>>()?;
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(templates)
+}
+
+/// the signatures of all macros a template exports
+fn macros_of(source: &str, name: &str) -> crate::Result<Vec<String>> {
+    let Stmt::Template(template) = parse(source, name)? else {
+        return Ok(vec![]);
+    };
+
+    Ok(template
+        .children
+        .iter()
+        .filter_map(|stmt| match stmt {
+            Stmt::Macro(m) => {
+                let required = m.args.len() - m.defaults.len();
+                let args = m
+                    .args
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, arg)| match arg {
+                        Expr::Var(var) if i < required => Some(var.id.to_string()),
+                        Expr::Var(var) => Some(format!("[{}]", var.id)),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                Some(format!("{}({})", m.name, args))
+            }
+            _ => None,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::create_file;
+
+    const AUTH: &str = r#"{% macro header(service, scope="read") -%}
+Bearer {{ jwt(sub=service, scope=scope) }}
+{%- endmacro %}
+{% macro basic_auth() %}{{ basic("john", "secret") }}{% endmacro %}"#;
+
+    #[test]
+    fn should_list_templates_with_their_macros() {
+        let tmp = create_file("auth.j2", AUTH).unwrap();
+        std::fs::write(tmp.path().join("README.md"), "no template").unwrap();
+        std::fs::write(tmp.path().join("paging.j2"), "page={{ page }}").unwrap();
+
+        assert_eq!(
+            list_templates(tmp.path()).unwrap(),
+            [
+                LibraryTemplate {
+                    name: "auth.j2".to_string(),
+                    macros: vec!["header(service, [scope])".into(), "basic_auth()".into()],
+                },
+                LibraryTemplate {
+                    name: "paging.j2".to_string(),
+                    macros: vec![],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_list_no_templates_without_a_library() {
+        assert!(list_templates("not/existing/templates").unwrap().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid template `broken.j2`")]
+    fn should_throw_on_invalid_templates() {
+        let tmp = create_file("broken.j2", "{% macro x( %}").unwrap();
+        list_templates(tmp.path()).unwrap();
+    }
+}
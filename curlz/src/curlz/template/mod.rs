@@ -1,14 +1,24 @@
+pub mod answers;
+pub mod commands;
+pub mod error;
 mod functions;
+pub mod library;
+pub mod session;
+mod undeclared;
 pub mod variables;
 
 use crate::domain::environment::Environment;
+use crate::template::error::TemplateError;
+use crate::template::session::RenderSession;
+use crate::template::variables::Placeholder;
 
 use minijinja::value::Value;
-use minijinja::Environment as MEnvironment;
+use minijinja::{Environment as MEnvironment, UndefinedBehavior};
 
 pub struct Renderer<'source> {
     env: MEnvironment<'source>,
     ctx: Value,
+    session: RenderSession,
 }
 
 impl<'source> From<&Environment> for Renderer<'source> {
@@ -19,23 +29,87 @@ impl<'source> From<&Environment> for Renderer<'source> {
 
 impl<'source> Renderer<'source> {
     pub fn new(env: &Environment) -> Self {
+        Self::with_session(env, &RenderSession::default())
+    }
+
+    /// creates a renderer whose template functions share the state of the given `session`
+    pub fn with_session(env: &Environment, session: &RenderSession) -> Self {
         let ctx: Value = env.into();
         let mut env = MEnvironment::new();
+        // keeps the span of errors, so that they can be highlighted
+        env.set_debug(true);
+        if session.is_strict() {
+            env.set_undefined_behavior(UndefinedBehavior::Strict);
+        }
+        if let Some(templates_dir) = session.templates_dir() {
+            env.set_source(library::source(templates_dir));
+        }
 
-        functions::register_functions(&mut env);
+        functions::register_functions(&mut env, session, &ctx);
 
-        Self { env, ctx }
+        Self {
+            env,
+            ctx,
+            session: session.clone(),
+        }
     }
 
     pub fn inject_variable(&mut self, p0: &'source str, p1: String) {
         self.env.add_global(p0, Value::from_safe_string(p1));
     }
 
-    pub fn render(&mut self, str: &'source str, name: &'source str) -> crate::Result<String> {
-        self.env.add_template(name, str)?;
-        let template = self.env.get_template(name)?;
+    /// injects a global object, like a map, that is accessible by all subsequent renderings
+    pub fn inject_object(&mut self, name: &'source str, value: Value) {
+        self.env.add_global(name, value);
+    }
 
-        template.render(&self.ctx).map_err(|e| e.into())
+    /// injects the `placeholders` that are not satisfied by the environment,
+    /// those without a value are prompted for
+    pub fn inject_placeholders(
+        &mut self,
+        placeholders: &'source [Placeholder],
+    ) -> crate::Result<()> {
+        for placeholder in placeholders {
+            let name = placeholder.name.as_str();
+            if self
+                .ctx
+                .get_attr(name)
+                .is_ok_and(|value| !value.is_undefined())
+            {
+                continue;
+            }
+            let value = match placeholder.value.as_ref() {
+                Some(value) => Value::from_safe_string(value.to_string()),
+                None => functions::prompt_placeholder(&self.session, placeholder)?,
+            };
+            self.env.add_global(name, value);
+        }
+
+        Ok(())
+    }
+
+    /// renders the template `str`, errors show its source with the failing span highlighted
+    pub fn render(&self, str: &str, name: &str) -> crate::Result<String> {
+        self.env
+            .render_named_str(name, str, &self.ctx)
+            .map_err(|e| TemplateError::new(e, name, str).into())
+    }
+
+    /// the variables a template refers to, that are neither defined by the environment,
+    /// nor by injected variables or template functions
+    pub fn undefined_variables(&self, str: &str, name: &str) -> crate::Result<Vec<String>> {
+        Ok(undeclared::undeclared_variables(str, name)?
+            .into_iter()
+            .filter(|variable| !self.is_defined(variable))
+            .collect())
+    }
+
+    fn is_defined(&self, variable: &str) -> bool {
+        self.env
+            .compile_expression(variable)
+            .and_then(|expr| expr.eval(&self.ctx))
+            .map(|value| !value.is_undefined())
+            .unwrap_or(false)
     }
 }
 
@@ -53,4 +127,89 @@ mod tests {
         assert_eq!(r.render("{{ foo }}", "something").unwrap(), "bar");
         assert_eq!(r.render("{{ bak }}", "something2").unwrap(), "foo");
     }
+
+    #[test]
+    fn test_renderer_with_injected_object() {
+        let mut r = Renderer::new(&Environment::default());
+        r.inject_object(
+            "request",
+            Value::from_serializable(&std::collections::HashMap::from([("body", "hello")])),
+        );
+
+        assert_eq!(
+            r.render("{{ request.body }}", "something").unwrap(),
+            "hello"
+        );
+    }
+
+    #[test]
+    fn should_inject_placeholders_not_satisfied_by_the_environment() {
+        let mut env = Environment::default();
+        env.insert("id", "42");
+        let session = RenderSession::default().with_interactive(false);
+        let mut r = Renderer::with_session(&env, &session);
+        let placeholders = [
+            Placeholder::new("id", "1"),
+            Placeholder::new("name", "john"),
+            Placeholder {
+                name: "page".to_string(),
+                default: Some("2".to_string()),
+                kind: variables::PlaceholderType::Number,
+                ..Default::default()
+            },
+        ];
+        r.inject_placeholders(&placeholders).unwrap();
+
+        assert_eq!(
+            r.render("{{ id }} {{ name }} {{ page + 1 }}", "placeholders")
+                .unwrap(),
+            "42 john 3"
+        );
+    }
+
+    #[test]
+    fn should_import_macros_of_the_template_library() {
+        let tmp = crate::test_utils::create_file(
+            "auth.j2",
+            r#"{% macro header(service) %}Bearer {{ service }}-{{ token }}{% endmacro %}"#,
+        )
+        .unwrap();
+        let mut env = Environment::default();
+        env.insert("token", "abc");
+        let session = RenderSession::default().with_templates_dir(tmp.path());
+        let r = Renderer::with_session(&env, &session);
+
+        assert_eq!(
+            r.render(
+                r#"{% import "auth.j2" as auth %}{{ auth.header("svc") }}"#,
+                "header"
+            )
+            .unwrap(),
+            "Bearer svc-abc"
+        );
+    }
+
+    #[test]
+    fn should_find_undefined_variables() {
+        let mut env = Environment::default();
+        env.insert("base_url", "http://localhost");
+        let mut r = Renderer::new(&env);
+        r.inject_variable("id", "42".to_string());
+
+        assert_eq!(
+            r.undefined_variables("{{ base_ur }}/{{ id }}/{{ uuid() }}?{{ base_url }}", "url")
+                .unwrap(),
+            ["base_ur"]
+        );
+    }
+
+    #[test]
+    fn should_not_render_undefined_variables_in_strict_mode() {
+        let r = Renderer::new(&Environment::default());
+        assert!(r.render("{{ base_ur }}", "strict").is_err());
+
+        let session = RenderSession::default().with_strict(false);
+        let r = Renderer::with_session(&Environment::default(), &session);
+        assert_eq!(r.render("{{ base_ur }}", "lenient").unwrap(), "");
+    }
 }
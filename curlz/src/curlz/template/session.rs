@@ -0,0 +1,152 @@
+use crate::domain::bookmark::{project_dir, WORKSPACE_FOLDER};
+use crate::domain::config::{ProjectConfig, CONFIG_FILE};
+use crate::template::answers::{Answers, ANSWERS_FILE};
+use crate::template::commands::Commands;
+use crate::template::library::TEMPLATES_FOLDER;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// the random number generator that is shared by all template functions of a run
+pub type SharedRng = Arc<Mutex<StdRng>>;
+
+/// the state that is shared by all renderings of a run, e.g. of all requests of a http file
+#[derive(Clone)]
+pub struct RenderSession {
+    rng: SharedRng,
+    base_dir: PathBuf,
+    sandbox: Option<PathBuf>,
+    answers: Answers,
+    interactive: bool,
+    strict: bool,
+    templates_dir: Option<PathBuf>,
+    commands: Commands,
+}
+
+impl Default for RenderSession {
+    fn default() -> Self {
+        Self {
+            rng: Arc::new(Mutex::new(StdRng::from_entropy())),
+            base_dir: PathBuf::from("."),
+            sandbox: Some(PathBuf::from(".")),
+            answers: Answers::default(),
+            interactive: true,
+            strict: true,
+            templates_dir: None,
+            commands: Commands::default(),
+        }
+    }
+}
+
+impl RenderSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// the session of the current project, with its answers, templates and allowed commands,
+    /// the template functions resolve files relative to the current folder, restricted to the project
+    pub fn for_project(interactive: bool) -> crate::Result<Self> {
+        let project = project_dir()?;
+        let workspace = project.join(WORKSPACE_FOLDER);
+        let config = ProjectConfig::load(workspace.join(CONFIG_FILE))?;
+
+        Ok(Self::new()
+            .with_base_dir(std::env::current_dir()?)
+            .with_answers_file(workspace.join(ANSWERS_FILE))
+            .with_templates_dir(workspace.join(TEMPLATES_FOLDER))
+            .with_interactive(interactive)
+            .with_allowed_commands(config.exec.allow)
+            .with_sandbox(Some(project)))
+    }
+
+    /// makes all random values, like `uuid()` or `fake("email")`, reproducible for the given `seed`
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        if let Some(seed) = seed {
+            self.rng = Arc::new(Mutex::new(StdRng::seed_from_u64(seed)));
+        }
+
+        self
+    }
+
+    /// relative paths of template functions like `file()` are resolved against `base_dir`
+    pub fn with_base_dir(mut self, base_dir: impl Into<PathBuf>) -> Self {
+        self.base_dir = base_dir.into();
+
+        self
+    }
+
+    /// restricts template functions like `file()` to files within `sandbox`, `None` allows all files
+    pub fn with_sandbox(mut self, sandbox: Option<PathBuf>) -> Self {
+        self.sandbox = sandbox;
+
+        self
+    }
+
+    /// prompts with `remember=true` store their answers in the yaml file `answers_file`
+    pub fn with_answers_file(mut self, answers_file: impl Into<PathBuf>) -> Self {
+        self.answers = self.answers.with_file(answers_file);
+
+        self
+    }
+
+    /// without `interactive` prompts never ask, they resolve from variables named after their label
+    pub fn with_interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+
+        self
+    }
+
+    /// with `strict` undefined variables are errors, otherwise they render empty
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+
+        self
+    }
+
+    /// the templates in `templates_dir`, like `.curlz/templates/`, can be imported by all templates
+    pub fn with_templates_dir(mut self, templates_dir: impl Into<PathBuf>) -> Self {
+        self.templates_dir = Some(templates_dir.into());
+
+        self
+    }
+
+    /// the template function `exec()` may run the commands `allowed`, like `vault`
+    pub fn with_allowed_commands(mut self, allowed: Vec<String>) -> Self {
+        self.commands = self.commands.with_allowed(allowed);
+
+        self
+    }
+
+    pub fn rng(&self) -> SharedRng {
+        self.rng.clone()
+    }
+
+    pub fn base_dir(&self) -> &Path {
+        &self.base_dir
+    }
+
+    pub fn sandbox(&self) -> Option<&Path> {
+        self.sandbox.as_deref()
+    }
+
+    pub fn answers(&self) -> Answers {
+        self.answers.clone()
+    }
+
+    pub fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    pub fn templates_dir(&self) -> Option<&Path> {
+        self.templates_dir.as_deref()
+    }
+
+    pub fn commands(&self) -> Commands {
+        self.commands.clone()
+    }
+}
@@ -0,0 +1,241 @@
+//! finds the variables a template refers to, without rendering it
+use crate::template::error::TemplateError;
+use minijinja::machinery::ast::{Expr, Stmt};
+use minijinja::machinery::parse;
+use std::collections::HashSet;
+
+/// filters and tests that are meant for undefined variables, like `foo | default("bar")`
+const GUARDS: &[&str] = &["default", "d", "defined", "undefined", "none"];
+
+/// the names of all variables a template refers to, in order of appearance,
+/// except the variables the template assigns itself, e.g. by `{% set %}` or `{% for %}`,
+/// and variables that are guarded, e.g. by `{% if foo %}` or `foo is defined`
+pub(crate) fn undeclared_variables(source: &str, name: &str) -> crate::Result<Vec<String>> {
+    let template = parse(source, name).map_err(|e| TemplateError::new(e, name, source))?;
+    let mut tracker = Tracker::default();
+    tracker.walk(&template);
+
+    let Tracker { out, guarded, .. } = tracker;
+    Ok(out
+        .into_iter()
+        .filter(|name| !guarded.contains(name))
+        .map(str::to_string)
+        .collect())
+}
+
+/// tracks the variables that are assigned per scope
+struct Tracker<'a> {
+    out: Vec<&'a str>,
+    guarded: HashSet<&'a str>,
+    assigned: Vec<HashSet<&'a str>>,
+}
+
+impl<'a> Default for Tracker<'a> {
+    fn default() -> Self {
+        Self {
+            out: vec![],
+            guarded: HashSet::new(),
+            assigned: vec![HashSet::from(["self"])],
+        }
+    }
+}
+
+impl<'a> Tracker<'a> {
+    fn is_assigned(&self, name: &str) -> bool {
+        self.assigned.iter().any(|scope| scope.contains(name))
+    }
+
+    fn assign(&mut self, name: &'a str) {
+        self.assigned.last_mut().unwrap().insert(name);
+    }
+
+    fn assign_nested(&mut self, target: &Expr<'a>) {
+        match target {
+            Expr::Var(var) => self.assign(var.id),
+            Expr::List(list) => list.items.iter().for_each(|x| self.assign_nested(x)),
+            _ => {}
+        }
+    }
+
+    fn scoped(&mut self, f: impl FnOnce(&mut Self)) {
+        self.assigned.push(HashSet::new());
+        f(self);
+        self.assigned.pop();
+    }
+
+    /// a bare variable like `foo` in `{% if foo %}` is checked for being defined
+    fn guard(&mut self, expr: &Expr<'a>) {
+        if let Expr::Var(var) = expr {
+            self.guarded.insert(var.id);
+        }
+    }
+
+    fn visit_all(&mut self, exprs: &[Expr<'a>]) {
+        exprs.iter().for_each(|x| self.visit(x));
+    }
+
+    fn visit_opt(&mut self, expr: &Option<Expr<'a>>) {
+        if let Some(expr) = expr {
+            self.visit(expr);
+        }
+    }
+
+    fn visit(&mut self, expr: &Expr<'a>) {
+        match expr {
+            Expr::Var(var) => {
+                if !self.is_assigned(var.id) && !self.out.contains(&var.id) {
+                    self.out.push(var.id);
+                }
+            }
+            Expr::Const(_) => {}
+            Expr::UnaryOp(expr) => self.visit(&expr.expr),
+            Expr::BinOp(expr) => {
+                self.visit(&expr.left);
+                self.visit(&expr.right);
+            }
+            Expr::IfExpr(expr) => {
+                self.guard(&expr.test_expr);
+                self.visit(&expr.test_expr);
+                self.visit(&expr.true_expr);
+                self.visit_opt(&expr.false_expr);
+            }
+            Expr::Filter(expr) => {
+                if GUARDS.contains(&expr.name) {
+                    if let Some(inner) = expr.expr.as_ref() {
+                        self.guard(inner);
+                    }
+                }
+                self.visit_opt(&expr.expr);
+                self.visit_all(&expr.args);
+            }
+            Expr::Test(expr) => {
+                if GUARDS.contains(&expr.name) {
+                    self.guard(&expr.expr);
+                }
+                self.visit(&expr.expr);
+                self.visit_all(&expr.args);
+            }
+            Expr::GetAttr(expr) => self.visit(&expr.expr),
+            Expr::GetItem(expr) => {
+                self.visit(&expr.expr);
+                self.visit(&expr.subscript_expr);
+            }
+            Expr::Slice(slice) => {
+                self.visit(&slice.expr);
+                self.visit_opt(&slice.start);
+                self.visit_opt(&slice.stop);
+                self.visit_opt(&slice.step);
+            }
+            Expr::Call(expr) => {
+                self.visit(&expr.expr);
+                self.visit_all(&expr.args);
+            }
+            Expr::List(expr) => self.visit_all(&expr.items),
+            Expr::Map(expr) => {
+                self.visit_all(&expr.keys);
+                self.visit_all(&expr.values);
+            }
+            Expr::Kwargs(expr) => expr.pairs.iter().for_each(|(_, v)| self.visit(v)),
+        }
+    }
+
+    fn walk_all(&mut self, stmts: &[Stmt<'a>]) {
+        stmts.iter().for_each(|x| self.walk(x));
+    }
+
+    fn walk(&mut self, stmt: &Stmt<'a>) {
+        match stmt {
+            Stmt::Template(stmt) => self.walk_all(&stmt.children),
+            Stmt::EmitExpr(expr) => self.visit(&expr.expr),
+            Stmt::EmitRaw(_) => {}
+            Stmt::ForLoop(stmt) => {
+                self.visit(&stmt.iter);
+                self.scoped(|s| {
+                    s.assign("loop");
+                    s.assign_nested(&stmt.target);
+                    s.visit_opt(&stmt.filter_expr);
+                    s.walk_all(&stmt.body);
+                });
+                self.scoped(|s| s.walk_all(&stmt.else_body));
+            }
+            Stmt::IfCond(stmt) => {
+                self.guard(&stmt.expr);
+                self.visit(&stmt.expr);
+                self.scoped(|s| s.walk_all(&stmt.true_body));
+                self.scoped(|s| s.walk_all(&stmt.false_body));
+            }
+            Stmt::WithBlock(stmt) => self.scoped(|s| {
+                for (target, expr) in &stmt.assignments {
+                    s.visit(expr);
+                    s.assign_nested(target);
+                }
+                s.walk_all(&stmt.body);
+            }),
+            Stmt::Set(stmt) => {
+                self.visit(&stmt.expr);
+                self.assign_nested(&stmt.target);
+            }
+            Stmt::SetBlock(stmt) => {
+                self.scoped(|s| s.walk_all(&stmt.body));
+                self.assign_nested(&stmt.target);
+            }
+            Stmt::AutoEscape(stmt) => self.scoped(|s| s.walk_all(&stmt.body)),
+            Stmt::FilterBlock(stmt) => self.scoped(|s| s.walk_all(&stmt.body)),
+            Stmt::Block(stmt) => self.scoped(|s| {
+                s.assign("super");
+                s.walk_all(&stmt.body);
+            }),
+            Stmt::Extends(_) | Stmt::Include(_) => {}
+            Stmt::Import(stmt) => self.assign_nested(&stmt.name),
+            Stmt::FromImport(stmt) => stmt.names.iter().for_each(|(name, alias)| {
+                self.assign_nested(alias.as_ref().unwrap_or(name));
+            }),
+            Stmt::Macro(stmt) => {
+                self.assign(stmt.name);
+                self.scoped(|s| {
+                    s.assign("caller");
+                    stmt.args.iter().for_each(|arg| s.assign_nested(arg));
+                    s.visit_all(&stmt.defaults);
+                    s.walk_all(&stmt.body);
+                });
+            }
+            Stmt::CallBlock(stmt) => {
+                self.visit(&stmt.call.expr);
+                self.visit_all(&stmt.call.args);
+            }
+            Stmt::Do(stmt) => {
+                self.visit(&stmt.call.expr);
+                self.visit_all(&stmt.call.args);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("{{ base_url }}/users/{{ id }}", &["base_url", "id"])]
+    #[case("{{ user.name }} {{ user['id'] }}", &["user"])]
+    #[case(r#"{{ jwt(claims, key=signing_key) }}"#, &["jwt", "claims", "signing_key"])]
+    #[case("{% set id = 42 %}{{ id }}", &[])]
+    #[case("{% for id in ids %}{{ id }}{{ loop.index }}{% endfor %}", &["ids"])]
+    #[case("{{ region | default('eu') }}", &[])]
+    #[case("{% if token is defined %}{{ token }}{% endif %}", &[])]
+    #[case("{% if verbose %}-v{% endif %}{{ tenant if tenant else 'acme' }}", &[])]
+    #[case("{% macro user(id) %}{{ id }}{{ host }}{% endmacro %}{{ user(1) }}", &["host"])]
+    fn should_find_undeclared_variables(#[case] template: &str, #[case] expected: &[&str]) {
+        assert_eq!(
+            undeclared_variables(template, "test").unwrap(),
+            expected.to_vec()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "syntax error")]
+    fn should_throw_on_syntax_errors() {
+        undeclared_variables("{{ foo", "test").unwrap();
+    }
+}
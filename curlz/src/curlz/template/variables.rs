@@ -1,20 +1,196 @@
+use crate::domain::environment::Environment;
+use crate::template::Renderer;
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Placeholder {
     pub name: String,
+    #[serde(default)]
     pub value: Option<String>,
+    #[serde(default)]
     pub default: Option<String>,
+    #[serde(default)]
     pub prompt: Option<String>,
+    #[serde(rename = "type", default)]
+    pub kind: PlaceholderType,
+    /// the allowed values, they are offered as selection
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<String>,
+}
+
+/// the type of value a placeholder expects, it decides how the user is prompted
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PlaceholderType {
+    #[default]
+    String,
+    Number,
+    Bool,
+    /// like a password or a token, it is never shown nor stored as default
+    Secret,
 }
 
+/// names that hint a placeholder is a secret, like `api_token` or `password`
+const SECRET_HINTS: &[&str] = &["password", "passwd", "secret", "token", "apikey", "api_key"];
+
 impl Placeholder {
     pub fn new(key: impl AsRef<str>, value: impl AsRef<str>) -> Self {
         Self {
             name: key.as_ref().to_owned(),
             value: value.as_ref().to_owned().into(),
-            default: None,
-            prompt: None,
+            ..Default::default()
+        }
+    }
+
+    /// a placeholder for a variable that was found in a template,
+    /// the value of a `defined` placeholder becomes its default, unless it is a secret,
+    /// its prompt, type and choices are kept
+    pub fn discovered(name: impl AsRef<str>, defined: Option<&Placeholder>) -> Self {
+        let name = name.as_ref();
+        let value = defined.and_then(|p| p.value.as_ref().or(p.default.as_ref()));
+        let lowercase = name.to_lowercase();
+        let kind = if SECRET_HINTS.iter().any(|hint| lowercase.contains(hint)) {
+            PlaceholderType::Secret
+        } else if let Some(defined) = defined.filter(|p| p.kind != PlaceholderType::String) {
+            defined.kind
+        } else {
+            value
+                .map(|value| PlaceholderType::of(value))
+                .unwrap_or_default()
+        };
+
+        Self {
+            name: name.to_string(),
+            value: None,
+            default: value.filter(|_| kind != PlaceholderType::Secret).cloned(),
+            prompt: defined
+                .and_then(|p| p.prompt.clone())
+                .or_else(|| Some(name.replace(['_', '-'], " "))),
+            kind,
+            choices: defined.map(|p| p.choices.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+impl PlaceholderType {
+    /// the type of an example `value`, like `42` or `true`
+    pub fn of(value: &str) -> Self {
+        if value.parse::<f64>().is_ok() {
+            Self::Number
+        } else if value.parse::<bool>().is_ok() {
+            Self::Bool
+        } else {
+            Self::String
         }
     }
 }
+
+/// the placeholders for all variables the `templates` refer to, in order of appearance,
+/// template functions like `uuid()` and the `ignored` names are no placeholders
+///
+/// the already `defined` placeholders, e.g. by `--define`, provide the defaults
+pub fn discover_placeholders<'a>(
+    templates: impl IntoIterator<Item = &'a str>,
+    ignored: &[&str],
+    defined: &[Placeholder],
+) -> crate::Result<Vec<Placeholder>> {
+    let renderer = Renderer::new(&Environment::default());
+    let mut placeholders: Vec<Placeholder> = vec![];
+    for template in templates {
+        for name in renderer.undefined_variables(template, "placeholder")? {
+            if ignored.contains(&name.as_str()) || placeholders.iter().any(|p| p.name == name) {
+                continue;
+            }
+            let defined = defined.iter().find(|p| p.name == name);
+            placeholders.push(Placeholder::discovered(name, defined));
+        }
+    }
+
+    Ok(placeholders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("42", PlaceholderType::Number)]
+    #[case("-0.5", PlaceholderType::Number)]
+    #[case("true", PlaceholderType::Bool)]
+    #[case("john", PlaceholderType::String)]
+    fn should_tell_the_type_of_a_value(#[case] value: &str, #[case] expected: PlaceholderType) {
+        assert_eq!(PlaceholderType::of(value), expected);
+    }
+
+    #[test]
+    fn should_discover_placeholders_of_templates() {
+        let defined = [
+            Placeholder::new("id", "42"),
+            Placeholder::new("api_token", "s3cr3t"),
+        ];
+        let placeholders = discover_placeholders(
+            [
+                "{{ base_url }}/users/{{ id }}?ts={{ timestamp() }}",
+                "Bearer {{ api_token }}",
+                "{{ request.body }} {{ base_url }}",
+            ],
+            &["request"],
+            &defined,
+        )
+        .unwrap();
+
+        assert_eq!(
+            placeholders,
+            [
+                Placeholder {
+                    name: "base_url".to_string(),
+                    prompt: Some("base url".to_string()),
+                    ..Default::default()
+                },
+                Placeholder {
+                    name: "id".to_string(),
+                    default: Some("42".to_string()),
+                    prompt: Some("id".to_string()),
+                    kind: PlaceholderType::Number,
+                    ..Default::default()
+                },
+                Placeholder {
+                    name: "api_token".to_string(),
+                    prompt: Some("api token".to_string()),
+                    kind: PlaceholderType::Secret,
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn should_keep_prompt_type_and_choices_of_defined_placeholders() {
+        let defined = [Placeholder {
+            name: "status".to_string(),
+            default: Some("available".to_string()),
+            prompt: Some("the status of the pets".to_string()),
+            choices: vec!["available".to_string(), "sold".to_string()],
+            ..Default::default()
+        }];
+        let placeholders =
+            discover_placeholders(["/pets?status={{ status }}"], &[], &defined).unwrap();
+
+        assert_eq!(placeholders, defined);
+    }
+
+    #[test]
+    fn should_read_placeholders_without_type() {
+        let placeholder: Placeholder = serde_yaml::from_str("name: email\nvalue: null").unwrap();
+
+        assert_eq!(
+            placeholder,
+            Placeholder {
+                name: "email".to_string(),
+                ..Default::default()
+            }
+        );
+    }
+}
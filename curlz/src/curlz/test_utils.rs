@@ -1,3 +1,5 @@
+use crate::domain::http::{HttpMethod, HttpRequest, HttpVersion};
+use minijinja::filters::Filter;
 use minijinja::functions::Function;
 use minijinja::value::{FunctionArgs, FunctionResult, Value};
 use minijinja::Environment;
@@ -35,10 +37,25 @@ pub mod sample_requests {
             ),
             curl_params: Default::default(),
             placeholders: Default::default(),
+            auth: None,
         }
     }
 }
 
+/// a request of `method` to `url`, without headers, body or placeholders
+pub fn request(url: &str, method: HttpMethod) -> HttpRequest {
+    HttpRequest {
+        url: url.into(),
+        method,
+        version: HttpVersion::Http11,
+        headers: Default::default(),
+        body: Default::default(),
+        curl_params: vec![],
+        placeholders: vec![],
+        auth: None,
+    }
+}
+
 /// [`RenderBuilder`] simplifies test case creation
 pub struct RenderBuilder<'source> {
     env: Environment<'source>,
@@ -82,6 +99,19 @@ impl<'source> RenderBuilder<'source> {
         self
     }
 
+    /// registers a template filter
+    pub fn with_filter<F, Rv, Args>(mut self, name: &'source str, f: F) -> Self
+    where
+        // the crazy bounds here exist to enable borrowing in closures
+        F: Filter<Rv, Args> + for<'a> Filter<Rv, <Args as FunctionArgs<'a>>::Output>,
+        Rv: FunctionResult,
+        Args: for<'a> FunctionArgs<'a>,
+    {
+        self.env.add_filter(name, f);
+
+        self
+    }
+
     /// registers an object as e.g. global object
     pub fn with_object<N, V>(mut self, name: N, value: V) -> Self
     where
@@ -1,3 +1,12 @@
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+/// all characters but the unreserved ones of [RFC 3986](https://www.rfc-editor.org/rfc/rfc3986#section-2.3)
+const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
 /// parses pairs like `"key=value"` strings into tuples of `Option<(key, value)>`
 /// spaces around the `separator` are being removed
 #[inline]
@@ -7,6 +16,11 @@ pub fn parse_pairs(pairs: &str, separator: char) -> Option<(&str, &str)> {
         .map(|(key, value)| (key.trim(), value.trim()))
 }
 
+/// percent-encodes all characters of `s`, but the unreserved ones, like `a b` into `a%20b`
+pub fn percent_encode(s: &str) -> String {
+    utf8_percent_encode(s, COMPONENT).to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
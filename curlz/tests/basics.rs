@@ -1,6 +1,9 @@
 use assert_cmd::prelude::*;
 use curlz::domain::http::{HttpBody, HttpMethod};
 use predicates::prelude::*;
+use sha2::{Digest, Sha256};
+use wiremock::matchers::{header, method, path};
+use wiremock::{Match, Mock, MockServer, Request, ResponseTemplate};
 
 use crate::testlib::{binary, CurlzTestSuite};
 
@@ -55,3 +58,931 @@ async fn should_send_as_post_with_body_variables() {
         .send_request()
         .await;
 }
+
+#[tokio::test]
+async fn should_sign_the_rendered_body_in_a_header() {
+    CurlzTestSuite::new()
+        .with_env_variable("username", "john")
+        .with_path("/post")
+        .with_method(HttpMethod::Post)
+        .with_payload(r#"{ "user": "{{ username }}" }"#)
+        .with_header(r#"X-Signature: sha256={{ hmac("sha256", "secret", request.body) }}"#)
+        .expect_header(
+            "X-Signature",
+            "sha256=8e71d6197f000097f34434146a4c31b984fa8fd1ac40e4993632d7929dd5e818",
+        )
+        .expect_payload(predicate::str::contains(r#"{ "user": "john" }"#))
+        .send_request()
+        .await;
+}
+
+#[tokio::test]
+async fn should_send_structured_env_data_as_json() {
+    let env_dir = tempfile::tempdir().unwrap();
+    let env_file = env_dir.path().join(".env.yml");
+    std::fs::write(&env_file, "payload:\n  user: john\n  roles: [admin]\n").unwrap();
+
+    CurlzTestSuite::new()
+        .with_args(["--env-file", env_file.to_str().unwrap()])
+        .with_path("/post")
+        .with_method(HttpMethod::Post)
+        .with_payload(r#"{{ payload | tojson }}"#)
+        .expect_payload(predicate::str::contains(
+            r#"{"user":"john","roles":["admin"]}"#,
+        ))
+        .send_request()
+        .await;
+}
+
+#[tokio::test]
+async fn should_send_a_file_as_body() {
+    let project = tempfile::tempdir().unwrap();
+    std::fs::create_dir(project.path().join("fixtures")).unwrap();
+    std::fs::write(
+        project.path().join("fixtures/user.json"),
+        r#"{"user":"john"}"#,
+    )
+    .unwrap();
+
+    CurlzTestSuite::new()
+        .in_dir(project.path())
+        .with_path("/post")
+        .with_method(HttpMethod::Post)
+        .with_payload(r#"{{ file("./fixtures/user.json") }}"#)
+        .expect_payload(predicate::str::contains(r#"{"user":"john"}"#))
+        .send_request()
+        .await;
+}
+
+const DIGEST_CHALLENGE: &str = r#"Digest realm="curlz@example.org", qop="auth", algorithm=SHA-256, nonce="7ypf/xlj9XXwfDPEoM4URrv", opaque="FQhe""#;
+
+/// matches only requests that answered [`DIGEST_CHALLENGE`] with the credentials `john:secret`
+struct DigestAnswered;
+
+impl Match for DigestAnswered {
+    fn matches(&self, request: &Request) -> bool {
+        // wiremock splits header values by comma
+        let Some(authorization) = request
+            .headers
+            .iter()
+            .find(|(name, _)| name.as_str().eq_ignore_ascii_case("authorization"))
+            .map(|(_, values)| {
+                values
+                    .iter()
+                    .map(|v| v.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+        else {
+            return false;
+        };
+        let param = |name: &str| {
+            authorization
+                .split(", ")
+                .map(|param| param.trim_start_matches("Digest "))
+                .filter_map(|param| param.split_once('='))
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.trim_matches('"').to_string())
+                .unwrap_or_default()
+        };
+        let sha256 = |data: String| hex::encode(Sha256::digest(data));
+        let ha1 = sha256("john:curlz@example.org:secret".to_string());
+        let ha2 = sha256("GET:/digest".to_string());
+        let expected = sha256(format!(
+            "{}:7ypf/xlj9XXwfDPEoM4URrv:{}:{}:auth:{}",
+            ha1,
+            param("nc"),
+            param("cnonce"),
+            ha2
+        ));
+
+        param("response") == expected
+    }
+}
+
+#[tokio::test]
+async fn should_answer_a_digest_challenge() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/digest"))
+        .and(DigestAnswered)
+        .respond_with(ResponseTemplate::new(200).set_body_string("authenticated"))
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/digest"))
+        .respond_with(
+            ResponseTemplate::new(401).insert_header("WWW-Authenticate", DIGEST_CHALLENGE),
+        )
+        .mount(&mock_server)
+        .await;
+
+    binary()
+        .args(["r", "-u", "john:secret", "--auth-type", "digest"])
+        .arg(format!("{}/digest", mock_server.uri()))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("authenticated"));
+}
+
+#[tokio::test]
+async fn should_throw_when_the_server_asks_for_no_digest() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/no-digest"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("public"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    binary()
+        .args(["r", "-u", "john:secret", "--auth-type", "digest"])
+        .arg(format!("{}/no-digest", mock_server.uri()))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "the server did not ask for digest authentication",
+        ));
+}
+
+#[tokio::test]
+async fn should_send_a_bearer_token() {
+    CurlzTestSuite::new()
+        .with_path("/bearer")
+        .expect_header("Authorization", "Bearer abc.def.ghi")
+        .with_args(["-u", "abc.def.ghi", "--auth-type", "bearer"])
+        .expect_payload(predicate::str::is_empty())
+        .send_request()
+        .await;
+}
+
+#[tokio::test]
+async fn should_answer_prompts_by_defines_when_not_interactive() {
+    CurlzTestSuite::new()
+        .with_path("/tenants")
+        .with_env_variable("tenant", "acme")
+        .with_args(["--non-interactive"])
+        .with_header(r#"X-Tenant: {{ prompt_for("Tenant") }}"#)
+        .expect_header("X-Tenant", "acme")
+        .send_request()
+        .await;
+}
+
+#[test]
+fn should_list_unanswered_prompts_when_not_interactive() {
+    binary()
+        .args(["r", "--non-interactive", "-H"])
+        .arg(r#"X-Tenant: {{ prompt_for("Tenant") }}"#)
+        .arg(r#"http://localhost/{{ prompt_number("Api Version", 1, 3) }}"#)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "the following prompts cannot be asked in a non-interactive run",
+        ))
+        .stderr(predicate::str::contains(
+            "`Api Version`: define one of `Api Version`, `api_version`, `API_VERSION`",
+        ))
+        .stderr(predicate::str::contains(
+            "`Tenant`: define one of `Tenant`, `tenant`, `TENANT`",
+        ));
+}
+
+#[test]
+fn should_report_all_undefined_variables_before_sending() {
+    binary()
+        .args(["r", "-H", "X-Tenant: {{ tenant }}", "-d", "{{ payload }}"])
+        .arg("{{ base_ur }}/users/{{ id }}")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "the request refers to undefined variables",
+        ))
+        .stderr(predicate::str::contains("- url: `base_ur`, `id`"))
+        .stderr(predicate::str::contains("- body: `payload`"))
+        .stderr(predicate::str::contains("- header `X-Tenant`: `tenant`"));
+}
+
+#[tokio::test]
+async fn should_render_undefined_variables_empty_when_lenient() {
+    CurlzTestSuite::new()
+        .with_path("/lenient")
+        .with_method(HttpMethod::Post)
+        .with_args(["--lenient"])
+        .with_payload("id={{ id }}")
+        .expect_payload(predicate::str::diff("id="))
+        .send_request()
+        .await;
+}
+
+#[tokio::test]
+async fn should_prompt_only_for_unsatisfied_placeholders_of_a_bookmark() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users/1"))
+        .and(header("Authorization", "Bearer abc"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("user 1"))
+        .mount(&mock_server)
+        .await;
+    let project = tempfile::tempdir().unwrap();
+    let base_url = format!("base_url={}", mock_server.uri());
+
+    binary()
+        .current_dir(project.path())
+        .args(["r", "--define", base_url.as_str(), "--define", "id=1"])
+        .args(["--define", "api_token=abc", "--bookmark-as", "users"])
+        .args(["-H", "Authorization: Bearer {{ api_token }}"])
+        .arg("{{ base_url }}/users/{{ id }}")
+        .assert()
+        .success();
+    let bookmark =
+        std::fs::read_to_string(project.path().join(".curlz/bookmarks/get_users.yml")).unwrap();
+    assert!(bookmark.contains("name: api_token\n    value: null\n    default: null"));
+    assert!(bookmark.contains("type: secret"));
+
+    binary()
+        .current_dir(project.path())
+        .args(["r", "--non-interactive", "users"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "`api_token`: define one of `api_token`, `API_TOKEN`",
+        ));
+    binary()
+        .current_dir(project.path())
+        .args([
+            "r",
+            "--non-interactive",
+            "users",
+            "--define",
+            "api_token=abc",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("user 1"));
+}
+
+#[tokio::test]
+async fn should_import_macros_of_the_template_library() {
+    let project = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(project.path().join(".curlz/templates")).unwrap();
+    std::fs::write(
+        project.path().join(".curlz/templates/auth.j2"),
+        r#"{% macro header(service, scope="read") %}Bearer {{ service }}:{{ scope }}{% endmacro %}"#,
+    )
+    .unwrap();
+
+    CurlzTestSuite::new()
+        .in_dir(project.path())
+        .with_path("/macros")
+        .with_header(r#"Authorization: {% import "auth.j2" as auth %}{{ auth.header("svc") }}"#)
+        .expect_header("Authorization", "Bearer svc:read")
+        .send_request()
+        .await;
+
+    binary()
+        .current_dir(project.path())
+        .args(["templates", "list"])
+        .assert()
+        .success()
+        .stdout("auth.j2\n  header(service, [scope])\n");
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn should_exec_allowlisted_commands() {
+    let project = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(project.path().join(".curlz")).unwrap();
+    std::fs::write(
+        project.path().join(".curlz/config.yml"),
+        "exec:\n  allow:\n    - echo\n",
+    )
+    .unwrap();
+
+    CurlzTestSuite::new()
+        .in_dir(project.path())
+        .with_path("/exec")
+        .with_header(r#"Authorization: Bearer {{ exec("echo", args=["s3cr3t"]) }}"#)
+        .expect_header("Authorization", "Bearer s3cr3t")
+        .send_request()
+        .await;
+}
+
+#[test]
+fn should_show_where_a_template_of_a_bookmark_fails() {
+    let project = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(project.path().join(".curlz/bookmarks")).unwrap();
+    std::fs::write(
+        project.path().join(".curlz/bookmarks/get_users.yml"),
+        r#"slug: users
+request:
+  url: http://localhost/users
+  method: GET
+  version: HTTP/1.1
+  headers:
+  - - X-Tenant
+    - '{{ "acme" | nope }}'
+  body: None
+  curl_params: []
+  placeholders: []
+"#,
+    )
+    .unwrap();
+
+    binary()
+        .current_dir(project.path())
+        .args(["r", "users"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "cannot render the header `X-Tenant` of bookmark `users`",
+        ))
+        .stderr(predicate::str::contains(
+            "unknown filter: filter nope is unknown",
+        ))
+        .stderr(predicate::str::contains("1 | {{ \"acme\" | nope }}"))
+        .stderr(predicate::str::contains("  |             ^^^^\n"))
+        .stderr(predicate::str::contains("panicked").not());
+}
+
+#[tokio::test]
+async fn should_map_raw_curl_args_into_the_request() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/raw"))
+        .and(header("User-Agent", "curlz-test"))
+        .and(header("X-Id", "42"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("raw args"))
+        .mount(&mock_server)
+        .await;
+    let url = format!("{}/raw", mock_server.uri());
+
+    binary()
+        .args(["r", "--define", "id=42", "--", "-sA", "curlz-test"])
+        .args([
+            "--header=X-Id: {{ id }}",
+            "-d",
+            "a=1",
+            "--url",
+            url.as_str(),
+        ])
+        .assert()
+        .success()
+        .stdout("raw args");
+}
+
+#[tokio::test]
+async fn should_leave_data_of_stdin_to_curl_and_send_rendered_bodies_raw() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/stdin"))
+        .and(wiremock::matchers::body_string("from stdin"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("stdin"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/raw"))
+        .and(wiremock::matchers::body_string("@not-a-file"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("raw"))
+        .mount(&mock_server)
+        .await;
+
+    assert_cmd::Command::from_std(binary())
+        .args(["r", "--", "-s", "-d", "@-"])
+        .arg(format!("{}/stdin", mock_server.uri()))
+        .write_stdin("from stdin")
+        .assert()
+        .success()
+        .stdout("stdin");
+    binary()
+        .args(["r", "--", "-s", "--data-raw", "@not-a-file"])
+        .arg(format!("{}/raw", mock_server.uri()))
+        .assert()
+        .success()
+        .stdout("raw");
+}
+
+#[tokio::test]
+async fn should_send_head_requests_without_waiting_for_a_body() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("HEAD"))
+        .and(path("/head"))
+        .respond_with(ResponseTemplate::new(200).insert_header("X-Head", "yes"))
+        .mount(&mock_server)
+        .await;
+
+    assert_cmd::Command::from_std(binary())
+        .args(["r", "--", "-s", "-I"])
+        .arg(format!("{}/head", mock_server.uri()))
+        .timeout(std::time::Duration::from_secs(10))
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("x-head: yes"));
+}
+
+#[tokio::test]
+async fn should_send_the_raw_body_of_a_bookmark_run() {
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/echo"))
+        .and(wiremock::matchers::body_string("hello"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("echoed"))
+        .mount(&mock_server)
+        .await;
+    let project = tempfile::tempdir().unwrap();
+    let url = format!("{}/echo", mock_server.uri());
+
+    binary()
+        .current_dir(project.path())
+        .env("XDG_CONFIG_HOME", project.path())
+        .args([
+            "import",
+            "curl",
+            &format!("curl -X POST {}", url),
+            "--bookmark-as",
+            "echo",
+            "--non-interactive",
+        ])
+        .assert()
+        .success();
+
+    binary()
+        .current_dir(project.path())
+        .env("XDG_CONFIG_HOME", project.path())
+        .args(["r", "echo", "--", "-s", "-d", "hello"])
+        .assert()
+        .success()
+        .stdout("echoed");
+}
+
+#[test]
+fn should_import_a_curl_command_as_bookmark() {
+    let project = tempfile::tempdir().unwrap();
+
+    assert_cmd::Command::from_std(binary())
+        .current_dir(project.path())
+        .args(["import", "curl", "--placeholders", "--bookmark-as", "user"])
+        .write_stdin(
+            "curl 'https://api.example.com/users/42' \\\n  -H 'authorization: Bearer abc' \\\n  --compressed\n",
+        )
+        .assert()
+        .success();
+
+    let bookmark =
+        std::fs::read_to_string(project.path().join(".curlz/bookmarks/get_user.yml")).unwrap();
+    assert!(bookmark.contains("url: '{{ base_url }}/users/{{ user_id }}'"));
+    assert!(bookmark.contains("- Bearer {{ token }}"));
+    assert!(bookmark.contains("- --compressed"));
+    assert!(
+        bookmark.contains("name: base_url\n    value: null\n    default: https://api.example.com")
+    );
+    assert!(bookmark.contains("name: token\n    value: null\n    default: null"));
+}
+
+#[test]
+fn should_import_and_export_a_postman_collection() {
+    let project = tempfile::tempdir().unwrap();
+    std::fs::write(
+        project.path().join("collection.json"),
+        r#"{
+          "info": { "name": "Users API", "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json" },
+          "variable": [{ "key": "base_url", "value": "https://api.example.com" }],
+          "item": [{
+            "name": "Users",
+            "item": [{ "name": "Get User", "request": { "method": "GET", "url": "{{base_url}}/users/{{user_id}}" } }]
+          }]
+        }"#,
+    )
+    .unwrap();
+    std::fs::write(
+        project.path().join("staging.json"),
+        r#"{ "values": [{ "key": "user_id", "value": "42", "enabled": true }] }"#,
+    )
+    .unwrap();
+
+    binary()
+        .current_dir(project.path())
+        .args([
+            "import",
+            "postman",
+            "collection.json",
+            "--env",
+            "staging.json",
+        ])
+        .assert()
+        .success();
+
+    let bookmark = std::fs::read_to_string(
+        project
+            .path()
+            .join(".curlz/bookmarks/users/get_get_user.yml"),
+    )
+    .unwrap();
+    assert!(bookmark.contains("slug: users/get-user"));
+    assert!(bookmark.contains("url: '{{base_url}}/users/{{user_id}}'"));
+    let env = std::fs::read_to_string(project.path().join(".env")).unwrap();
+    assert!(env.contains("base_url='https://api.example.com'"));
+    assert!(env.contains("user_id='42'"));
+
+    binary()
+        .current_dir(project.path())
+        .args(["export", "postman", "--name", "Users API"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""name": "users""#))
+        .stdout(predicate::str::contains(r#""name": "get-user""#))
+        .stdout(predicate::str::contains(
+            r#""url": "{{base_url}}/users/{{user_id}}""#,
+        ));
+}
+
+#[test]
+fn should_import_an_openapi_spec_as_bookmarks() {
+    let project = tempfile::tempdir().unwrap();
+    std::fs::write(
+        project.path().join("openapi.yaml"),
+        r#"
+openapi: 3.0.3
+info: { title: Pet Store, version: 1.0.0 }
+servers:
+  - url: https://petstore.example.com/v1
+paths:
+  /pets/{petId}:
+    get:
+      operationId: showPetById
+      parameters:
+        - name: petId
+          in: path
+          required: true
+          description: the id of the pet
+          schema: { type: integer }
+"#,
+    )
+    .unwrap();
+
+    binary()
+        .current_dir(project.path())
+        .args(["import", "openapi", "openapi.yaml"])
+        .assert()
+        .success();
+
+    let bookmark = std::fs::read_to_string(
+        project
+            .path()
+            .join(".curlz/bookmarks/get_show_pet_by_id.yml"),
+    )
+    .unwrap();
+    assert!(bookmark.contains("url: '{{ base_url }}/pets/{{ pet_id }}'"));
+    assert!(bookmark.contains("prompt: the id of the pet\n    type: number"));
+    let env = std::fs::read_to_string(project.path().join(".env")).unwrap();
+    assert!(env.contains("base_url='https://petstore.example.com/v1'"));
+}
+
+#[test]
+fn should_list_bookmarks_by_folder_and_tag() {
+    let project = tempfile::tempdir().unwrap();
+    std::fs::write(
+        project.path().join("openapi.yaml"),
+        r#"
+openapi: 3.0.3
+info: { title: Shop, version: 1.0.0 }
+paths:
+  /payments:
+    post:
+      operationId: createPayment
+      summary: pays an order
+      tags: [payments]
+"#,
+    )
+    .unwrap();
+    let curlz = |args: &[&str]| {
+        let mut cmd = binary();
+        cmd.current_dir(project.path())
+            .env("XDG_CONFIG_HOME", project.path())
+            .args(args);
+        cmd
+    };
+    curlz(&["import", "openapi", "openapi.yaml"])
+        .assert()
+        .success();
+    curlz(&[
+        "import",
+        "curl",
+        "curl https://api.example.com/users/1",
+        "--bookmark-as",
+        "users/get-user",
+        "--non-interactive",
+    ])
+    .assert()
+    .success();
+
+    assert!(project
+        .path()
+        .join(".curlz/bookmarks/users/get_get_user.yml")
+        .exists());
+    curlz(&["bookmark", "list", "--tag", "payments"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "POST    project create-payment [payments] - pays an order",
+        ))
+        .stdout(predicate::str::contains("users/get-user").not());
+    curlz(&["bookmark", "list", "users/"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("GET     project users/get-user"))
+        .stdout(predicate::str::contains("create-payment").not());
+}
+
+#[test]
+fn should_import_a_har_file() {
+    let project = tempfile::tempdir().unwrap();
+    std::fs::write(
+        project.path().join("session.har"),
+        r#"{ "log": { "version": "1.2", "entries": [
+          { "request": { "method": "GET", "url": "https://api.example.com/users?page=2",
+                         "headers": [{ "name": "Accept", "value": "application/json" }] } },
+          { "request": { "method": "GET", "url": "https://cdn.example.org/app.js", "headers": [] } }
+        ] } }"#,
+    )
+    .unwrap();
+
+    binary()
+        .current_dir(project.path())
+        .args(["import", "har", "session.har", "--filter", "example.com"])
+        .assert()
+        .success();
+
+    let bookmark =
+        std::fs::read_to_string(project.path().join(".curlz/bookmarks/get_users.yml")).unwrap();
+    assert!(bookmark.contains("url: https://api.example.com/users?page=2"));
+    assert!(!project
+        .path()
+        .join(".curlz/bookmarks/get_app_js.yml")
+        .exists());
+}
+
+#[test]
+fn should_preview_and_import_a_hurl_file() {
+    let project = tempfile::tempdir().unwrap();
+    std::fs::write(
+        project.path().join("users.hurl"),
+        "GET {{base_url}}/users\nAccept: application/json\n\nHTTP 200\n",
+    )
+    .unwrap();
+    std::fs::write(
+        project.path().join("vars.env"),
+        "base_url=https://api.example.com\n",
+    )
+    .unwrap();
+    let args = [
+        "import",
+        "hurl",
+        "users.hurl",
+        "--variables-file",
+        "vars.env",
+    ];
+
+    binary()
+        .current_dir(project.path())
+        .args(args)
+        .arg("--dry-run")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("would create bookmark `users`"))
+        .stdout(predicate::str::contains(
+            "would write variable `base_url` to .env",
+        ));
+    assert!(!project.path().join(".curlz").exists());
+    assert!(!project.path().join(".env").exists());
+
+    binary()
+        .current_dir(project.path())
+        .args(args)
+        .assert()
+        .success();
+
+    let bookmark =
+        std::fs::read_to_string(project.path().join(".curlz/bookmarks/get_users.yml")).unwrap();
+    assert!(bookmark.contains("url: '{{base_url}}/users'"));
+    let env = std::fs::read_to_string(project.path().join(".env")).unwrap();
+    assert!(env.contains("base_url='https://api.example.com'"));
+
+    // the imported variables are read by default, like by `curlz r`
+    binary()
+        .current_dir(project.path())
+        .args(["codegen", "users", "--lang", "wget", "--render"])
+        .arg("--non-interactive")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("https://api.example.com/users"));
+}
+
+#[test]
+fn should_generate_code_of_a_bookmark() {
+    let project = tempfile::tempdir().unwrap();
+    binary()
+        .current_dir(project.path())
+        .args([
+            "import",
+            "curl",
+            "curl -H 'Accept: application/json' 'https://api.example.com/users'",
+            "--bookmark-as",
+            "users",
+            "--non-interactive",
+        ])
+        .assert()
+        .success();
+
+    binary()
+        .current_dir(project.path())
+        .args(["codegen", "users", "--lang", "python-requests"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("response = requests.get("))
+        .stdout(predicate::str::contains(
+            r#""https://api.example.com/users","#,
+        ))
+        .stdout(predicate::str::contains(r#""Accept": "application/json","#));
+}
+
+#[test]
+fn should_generate_code_of_a_rendered_bookmark_only() {
+    let project = tempfile::tempdir().unwrap();
+    binary()
+        .current_dir(project.path())
+        .args([
+            "import",
+            "curl",
+            r#"curl -H 'X-Tenant: {{ "acme" | upper }}' 'https://api.example.com/users'"#,
+            "--bookmark-as",
+            "users",
+            "--non-interactive",
+        ])
+        .assert()
+        .success();
+
+    binary()
+        .current_dir(project.path())
+        .args(["codegen", "users", "--lang", "httpie"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            r#"the templates `{{ "acme" | upper }}` cannot be turned into code"#,
+        ));
+    binary()
+        .current_dir(project.path())
+        .args(["codegen", "users", "--lang", "httpie", "--render"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("'X-Tenant:ACME'"));
+}
+
+#[test]
+fn should_find_project_and_global_bookmarks_from_a_subdirectory() {
+    let project = tempfile::tempdir().unwrap();
+    let config = tempfile::tempdir().unwrap();
+    let sub_dir = project.path().join("api").join("users");
+    std::fs::create_dir_all(&sub_dir).unwrap();
+    let import = |dir: &std::path::Path, slug: &str, global: bool| {
+        let mut cmd = binary();
+        cmd.current_dir(dir)
+            .env("XDG_CONFIG_HOME", config.path())
+            .args([
+                "import",
+                "curl",
+                "curl https://api.example.com/users",
+                "--bookmark-as",
+                slug,
+                "--non-interactive",
+            ]);
+        if global {
+            cmd.arg("--global");
+        }
+        cmd.assert().success();
+    };
+
+    // the first bookmark creates the `.curlz` folder of the project
+    import(project.path(), "users", false);
+    import(&sub_dir, "all-users", false);
+    import(&sub_dir, "shared-users", true);
+
+    assert!(project
+        .path()
+        .join(".curlz/bookmarks/get_all_users.yml")
+        .exists());
+    assert!(config
+        .path()
+        .join("curlz/bookmarks/get_shared_users.yml")
+        .exists());
+    binary()
+        .current_dir(&sub_dir)
+        .env("XDG_CONFIG_HOME", config.path())
+        .args(["bookmark", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("GET     project all-users"))
+        .stdout(predicate::str::contains("GET     global  shared-users"))
+        .stdout(predicate::str::contains("GET     project users"));
+}
+
+#[test]
+fn should_look_bookmarks_up_by_their_slug_only() {
+    let project = tempfile::tempdir().unwrap();
+    let curlz = |args: &[&str]| {
+        let mut cmd = binary();
+        cmd.current_dir(project.path())
+            .env("XDG_CONFIG_HOME", project.path())
+            .args(args);
+        cmd
+    };
+    curlz(&[
+        "import",
+        "curl",
+        "curl -X POST https://api.example.com/users",
+        "--bookmark-as",
+        "create-user",
+        "--non-interactive",
+    ])
+    .assert()
+    .success();
+
+    curlz(&["codegen", "create-user", "--lang", "httpie"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("POST"));
+    curlz(&["codegen", "create-usr", "--lang", "httpie"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("did you mean `create-user`?"));
+
+    curlz(&[
+        "import",
+        "curl",
+        "curl https://api.example.com/users",
+        "--bookmark-as",
+        "create-user",
+        "--non-interactive",
+    ])
+    .assert()
+    .success();
+    curlz(&[
+        "codegen",
+        "create-user",
+        "--lang",
+        "httpie",
+        "--non-interactive",
+    ])
+    .assert()
+    .failure()
+    .stderr(predicate::str::contains(
+        "the bookmark `create-user` exists for several methods",
+    ));
+}
+
+#[test]
+fn should_show_rename_and_remove_a_bookmark() {
+    let project = tempfile::tempdir().unwrap();
+    let curlz = |args: &[&str]| {
+        let mut cmd = binary();
+        cmd.current_dir(project.path())
+            .env("XDG_CONFIG_HOME", project.path())
+            .args(args);
+        cmd
+    };
+    curlz(&[
+        "import",
+        "curl",
+        "curl https://api.example.com/users",
+        "--bookmark-as",
+        "users",
+        "--non-interactive",
+    ])
+    .assert()
+    .success();
+
+    curlz(&["bookmark", "show", "users"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("slug: users\n"))
+        .stdout(predicate::str::contains(
+            "url: https://api.example.com/users",
+        ));
+    curlz(&["bookmark", "rename", "users", "all-users"])
+        .assert()
+        .success();
+    curlz(&["bookmark", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("GET     project all-users"))
+        .stdout(predicate::str::contains(" users").not());
+    curlz(&["bookmark", "remove", "all-users"])
+        .assert()
+        .success();
+    curlz(&["bookmark", "show", "all-users"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "the bookmark `all-users` does not exist",
+        ));
+}
@@ -1,13 +1,14 @@
 use assert_cmd::prelude::*;
 use std::collections::HashMap;
 use std::ops::Deref;
+use std::path::PathBuf;
 
 use assert_cmd::assert::Assert;
 use dotenvy::dotenv;
 use predicates::str::contains;
 use predicates::{BoxPredicate, Predicate};
 use std::process::Command;
-use wiremock::matchers::{method, path};
+use wiremock::matchers::{header, method, path};
 use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
 
 use curlz::domain::http::{HttpBody, HttpMethod};
@@ -20,7 +21,11 @@ pub struct CurlzTestSuite {
     url_part: String,
     http_method: String,
     payload: HttpBody,
+    headers: Vec<String>,
+    args: Vec<String>,
+    current_dir: Option<PathBuf>,
     defined_variables: HashMap<String, String>,
+    expected_headers: Vec<(String, String)>,
     expected_stdout: BoxPredicate<str>,
 }
 
@@ -31,7 +36,11 @@ impl Default for CurlzTestSuite {
             url_part: "/".to_string(),
             http_method: "GET".to_string(),
             payload: HttpBody::None,
+            headers: Default::default(),
+            args: Default::default(),
+            current_dir: None,
             defined_variables: Default::default(),
+            expected_headers: Default::default(),
             expected_stdout: BoxPredicate::new(contains("")),
         }
     }
@@ -45,12 +54,18 @@ impl CurlzTestSuite {
     /// runs curlz and requests the given url from a local echo http server
     pub async fn send_request(mut self) -> Assert {
         let mock_server = self.prepare_mock_server().await;
+        let mut curlz = binary();
+        if let Some(dir) = self.current_dir.as_ref() {
+            curlz.current_dir(dir);
+        }
 
-        binary()
+        curlz
             .arg("r")
             .args(self.args_method())
             .args(self.args_define())
             .args(self.args_data())
+            .args(self.args_headers())
+            .args(&self.args)
             .arg(self.arg_url(&mock_server))
             .assert()
             .success()
@@ -89,10 +104,35 @@ impl CurlzTestSuite {
         self.expect_payload(predicate)
     }
 
+    /// sets a http header in the form of `"Header-Name: Value"` that is send
+    pub fn with_header(mut self, header: &str) -> Self {
+        self.headers.push(header.to_string());
+        self
+    }
+
+    /// passes further arguments to curlz, e.g. `["-u", "john:secret"]`
+    pub fn with_args<'a>(mut self, args: impl IntoIterator<Item = &'a str>) -> Self {
+        self.args.extend(args.into_iter().map(ToString::to_string));
+        self
+    }
+
+    /// runs curlz within the given working directory
+    pub fn in_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.current_dir = Some(dir.into());
+        self
+    }
+
+    /// the mock server only responds when the request contains the given header
+    pub fn expect_header(mut self, name: &str, value: &str) -> Self {
+        self.expected_headers
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
     /// sets the expected output
-    pub fn expect_payload<P: Predicate<str>>(mut self, predicate: P) -> Self
+    pub fn expect_payload<P>(mut self, predicate: P) -> Self
     where
-        P: Send + Sync + 'static,
+        P: Predicate<str> + Send + Sync + 'static,
     {
         self.expected_stdout = BoxPredicate::new(predicate);
         self
@@ -119,6 +159,13 @@ impl CurlzTestSuite {
         }
     }
 
+    fn args_headers(&self) -> Vec<&str> {
+        self.headers
+            .iter()
+            .flat_map(|header| ["-H", header.as_str()])
+            .collect()
+    }
+
     fn args_define(&self) -> Vec<String> {
         self.defined_variables
             .iter()
@@ -129,11 +176,12 @@ impl CurlzTestSuite {
     async fn prepare_mock_server(&mut self) -> MockServer {
         let mock_server = MockServer::start().await;
 
-        Mock::given(method(self.http_method.as_str()))
-            .and(path(self.url_part.as_str()))
-            .respond_with(EchoResponder::default())
-            .mount(&mock_server)
-            .await;
+        let mut mock =
+            Mock::given(method(self.http_method.as_str())).and(path(self.url_part.as_str()));
+        for (name, value) in self.expected_headers.iter() {
+            mock = mock.and(header(name.as_str(), value.as_str()));
+        }
+        mock.respond_with(EchoResponder).mount(&mock_server).await;
 
         mock_server
     }